@@ -1,17 +1,19 @@
-use cosmwasm_std::{Coin, DepsMut, Env, Response, Uint128};
-use mars_rover::error::{ContractError, ContractResult};
+use cosmwasm_std::{Addr, Coin, Decimal, Deps, DepsMut, Env, Response, Uint128};
+use mars_red_bank_types::red_bank::Market;
+use mars_rover::error::{ContractError, ContractResult, TempCheckMulFracError};
 
 use crate::{
-    state::{DEBT_SHARES, RED_BANK, TOTAL_DEBT_SHARES},
+    health::compute_health,
+    state::{ACCOUNTS_WITH_DEBT, ALLOWED_COINS, DEBT_SHARES, ORACLE, RED_BANK, TOTAL_DEBT_SHARES},
     utils::{assert_coin_is_whitelisted, increment_coin_balance},
 };
 
 pub static DEFAULT_DEBT_SHARES_PER_COIN_BORROWED: Uint128 = Uint128::new(1_000_000);
 
-/// calculate by how many the user's debt units should be increased
-/// if total debt is zero, then we define 1 unit of coin borrowed = 1,000,000 debt unit
-/// else, get debt ownership % and multiply by total existing shares
-///
+/// Worst case value lost to flooring in health computation: one unit each for the deposit value,
+/// its max LTV adjustment and the debt value
+pub const MAX_BORROW_ROUNDING_BUFFER: Uint128 = Uint128::new(3);
+
 /// increment total debt shares, token debt shares, and asset amount
 pub fn borrow(deps: DepsMut, env: Env, account_id: &str, coin: Coin) -> ContractResult<Response> {
     if coin.amount.is_zero() {
@@ -21,16 +23,7 @@ pub fn borrow(deps: DepsMut, env: Env, account_id: &str, coin: Coin) -> Contract
     assert_coin_is_whitelisted(deps.storage, &coin.denom)?;
 
     let red_bank = RED_BANK.load(deps.storage)?;
-    let total_debt_amount =
-        red_bank.query_debt(&deps.querier, &env.contract.address, &coin.denom)?;
-
-    let debt_shares_to_add = if total_debt_amount.is_zero() {
-        coin.amount.checked_mul(DEFAULT_DEBT_SHARES_PER_COIN_BORROWED)?
-    } else {
-        TOTAL_DEBT_SHARES
-            .load(deps.storage, &coin.denom)?
-            .checked_multiply_ratio(coin.amount, total_debt_amount)?
-    };
+    let debt_shares_to_add = debt_shares_to_add(deps.as_ref(), &env.contract.address, &coin)?;
 
     TOTAL_DEBT_SHARES.update(deps.storage, &coin.denom, |shares| {
         shares
@@ -45,6 +38,7 @@ pub fn borrow(deps: DepsMut, env: Env, account_id: &str, coin: Coin) -> Contract
             .checked_add(debt_shares_to_add)
             .map_err(ContractError::Overflow)
     })?;
+    ACCOUNTS_WITH_DEBT.insert(deps.storage, account_id)?;
 
     increment_coin_balance(deps.storage, account_id, &coin)?;
 
@@ -55,3 +49,65 @@ pub fn borrow(deps: DepsMut, env: Env, account_id: &str, coin: Coin) -> Contract
         .add_attribute("debt_shares_added", debt_shares_to_add)
         .add_attribute("coin_borrowed", coin.to_string()))
 }
+
+/// calculate by how many the user's debt units should be increased
+/// if total debt is zero, then we define 1 unit of coin borrowed = 1,000,000 debt unit
+/// else, get debt ownership % and multiply by total existing shares
+pub fn debt_shares_to_add(deps: Deps, rover_addr: &Addr, coin: &Coin) -> ContractResult<Uint128> {
+    let red_bank = RED_BANK.load(deps.storage)?;
+    let total_debt_amount = red_bank.query_debt(&deps.querier, rover_addr, &coin.denom)?;
+
+    let shares = if total_debt_amount.is_zero() {
+        coin.amount.checked_mul(DEFAULT_DEBT_SHARES_PER_COIN_BORROWED)?
+    } else {
+        TOTAL_DEBT_SHARES
+            .load(deps.storage, &coin.denom)?
+            .checked_multiply_ratio(coin.amount, total_debt_amount)?
+    };
+    Ok(shares)
+}
+
+/// The largest amount of denom that can be borrowed while keeping the account at or below max LTV.
+/// Borrowed coins are deposited into the account, so they count towards collateral as well.
+/// A small buffer of value is left unused so the borrow is guaranteed to pass the max LTV assertion.
+pub fn max_borrow_amount(
+    deps: Deps,
+    env: &Env,
+    account_id: &str,
+    denom: &str,
+) -> ContractResult<Uint128> {
+    if !ALLOWED_COINS.contains(deps.storage, denom) {
+        return Ok(Uint128::zero());
+    }
+
+    let health = compute_health(deps, env, account_id)?;
+    let headroom = health
+        .max_ltv_adjusted_collateral
+        .saturating_sub(health.total_debt_value)
+        .saturating_sub(MAX_BORROW_ROUNDING_BUFFER);
+    if headroom.is_zero() {
+        return Ok(Uint128::zero());
+    }
+
+    let red_bank = RED_BANK.load(deps.storage)?;
+    let Market {
+        max_loan_to_value,
+        ..
+    } = red_bank.query_market(&deps.querier, denom)?;
+    let oracle = ORACLE.load(deps.storage)?;
+    let price = oracle.query_price(&deps.querier, denom)?.price;
+    if price.is_zero() {
+        return Ok(Uint128::zero());
+    }
+
+    // At a max LTV of one a borrow adds as much adjusted collateral as debt, so it never changes
+    // the headroom. Limit it to the amount the headroom alone covers instead.
+    if max_loan_to_value >= Decimal::one() {
+        return Ok(headroom.checked_div_floor(price).map_err(|_| TempCheckMulFracError {})?);
+    }
+
+    // Each unit borrowed adds `price` to debt value and `price * max_ltv` to adjusted collateral
+    let net_value_per_unit = Decimal::one().checked_sub(max_loan_to_value)?.checked_mul(price)?;
+
+    Ok(headroom.checked_div_floor(net_value_per_unit).map_err(|_| TempCheckMulFracError {})?)
+}
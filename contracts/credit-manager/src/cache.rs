@@ -0,0 +1,52 @@
+use std::collections::HashMap;
+
+use cosmwasm_std::{Coin, Decimal, Deps, Uint128};
+use mars_red_bank_types::red_bank::Market;
+use mars_rover::{
+    error::{ContractResult, TempCheckMulFracError},
+    msg::query::CoinPrice,
+};
+
+use crate::state::{ORACLE, RED_BANK};
+
+/// Oracle prices and red bank markets queried during a single execution or query.
+/// Health computations touch the same denoms repeatedly (deposits, debts, vault base tokens,
+/// liquidation pricing), so each denom is only queried once per cache.
+#[derive(Default)]
+pub struct QueryCache {
+    prices: HashMap<String, Decimal>,
+    markets: HashMap<String, Market>,
+}
+
+impl QueryCache {
+    /// A cache pre-seeded with prices that take precedence over the oracle's
+    pub fn with_prices(prices: &[CoinPrice]) -> Self {
+        Self {
+            prices: prices.iter().map(|p| (p.denom.clone(), p.price)).collect(),
+            markets: HashMap::new(),
+        }
+    }
+
+    pub fn price(&mut self, deps: &Deps, denom: &str) -> ContractResult<Decimal> {
+        if let Some(price) = self.prices.get(denom) {
+            return Ok(*price);
+        }
+        let price = ORACLE.load(deps.storage)?.query_price(&deps.querier, denom)?.price;
+        self.prices.insert(denom.to_string(), price);
+        Ok(price)
+    }
+
+    pub fn market(&mut self, deps: &Deps, denom: &str) -> ContractResult<Market> {
+        if let Some(market) = self.markets.get(denom) {
+            return Ok(market.clone());
+        }
+        let market = RED_BANK.load(deps.storage)?.query_market(&deps.querier, denom)?;
+        self.markets.insert(denom.to_string(), market.clone());
+        Ok(market)
+    }
+
+    pub fn value(&mut self, deps: &Deps, coin: &Coin) -> ContractResult<Uint128> {
+        let price = self.price(deps, &coin.denom)?;
+        Ok(coin.amount.checked_mul_floor(price).map_err(|_| TempCheckMulFracError {})?)
+    }
+}
@@ -0,0 +1,274 @@
+use std::collections::HashMap;
+
+use cosmwasm_std::{
+    Coin, CosmosMsg, Decimal, Deps, DepsMut, Env, Order, Response, StdResult, Uint128,
+};
+use mars_rover::{
+    error::{ContractError, ContractResult, TempCheckMulFracError},
+    msg::{
+        execute::{ActionAmount, ActionCoin, CallbackMsg},
+        query::Positions,
+    },
+};
+
+use crate::{
+    cache::QueryCache,
+    query::{query_coin_balances, query_positions, query_vault_positions},
+    repay::current_debt_for_denom,
+    state::{COIN_BALANCES, DEBT_SHARES, SWAPPER, ZAPPER},
+    utils::assert_coin_is_whitelisted,
+    vault::lp_token_denoms,
+};
+
+pub fn exit_unlocked_vaults(deps: DepsMut, env: Env, account_id: &str) -> ContractResult<Response> {
+    let positions = query_vault_positions(deps.as_ref(), account_id)?;
+
+    if positions.iter().any(|p| !p.amount.locked().is_zero() || !p.amount.unlocking().is_empty()) {
+        return Err(ContractError::CloseAccountVaultLocked);
+    }
+
+    let msgs = positions
+        .into_iter()
+        .filter(|p| !p.amount.unlocked().is_zero())
+        .map(|p| {
+            CallbackMsg::ExitVault {
+                account_id: account_id.to_string(),
+                vault: p.vault,
+                amount: p.amount.unlocked(),
+                min_receive: None,
+            }
+            .into_cosmos_msg(&env.contract.address)
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_attribute("action", "callback/exit_unlocked_vaults")
+        .add_attribute("account_id", account_id))
+}
+
+pub fn withdraw_all_liquidity(
+    deps: DepsMut,
+    env: Env,
+    account_id: &str,
+) -> ContractResult<Response> {
+    let balances = query_coin_balances(deps.as_ref(), account_id)?;
+    let msgs = lp_token_balances(deps.as_ref(), balances)?
+        .into_iter()
+        .map(|coin| {
+            CallbackMsg::WithdrawLiquidity {
+                account_id: account_id.to_string(),
+                lp_token: ActionCoin {
+                    denom: coin.denom,
+                    amount: ActionAmount::AccountBalance,
+                },
+                min_receive: None,
+            }
+            .into_cosmos_msg(&env.contract.address)
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_attribute("action", "callback/withdraw_all_liquidity")
+        .add_attribute("account_id", account_id))
+}
+
+/// Only the base tokens of vaults configured as LP vaults are known to be LP tokens. Those the
+/// zapper has no liquidity to withdraw for are left to be swapped with the other balances.
+pub fn lp_token_balances(deps: Deps, balances: Vec<Coin>) -> ContractResult<Vec<Coin>> {
+    let lp_denoms = lp_token_denoms(deps)?;
+    let zapper = ZAPPER.load(deps.storage)?;
+    Ok(balances
+        .into_iter()
+        .filter(|coin| {
+            lp_denoms.contains(&coin.denom)
+                && zapper
+                    .estimate_withdraw_liquidity(&deps.querier, coin)
+                    .map(|coins_out| !coins_out.is_empty())
+                    .unwrap_or(false)
+        })
+        .collect())
+}
+
+pub fn swap_for_debts(
+    deps: DepsMut,
+    env: Env,
+    account_id: &str,
+    target_denom: &str,
+    slippage: Decimal,
+) -> ContractResult<Response> {
+    let positions = query_positions(deps.as_ref(), &env, account_id)?;
+    let swaps = debt_swaps(deps.as_ref(), &positions, target_denom, slippage)?;
+
+    let msgs = swaps
+        .into_iter()
+        .map(|(coin_in, denom_out)| {
+            CallbackMsg::SwapExactIn {
+                account_id: account_id.to_string(),
+                coin_in: ActionCoin::from(&coin_in),
+                denom_out,
+                slippage,
+                min_receive: None,
+            }
+            .into_cosmos_msg(&env.contract.address)
+        })
+        .collect::<StdResult<Vec<CosmosMsg>>>()?;
+
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_attribute("action", "callback/swap_for_debts")
+        .add_attribute("account_id", account_id)
+        .add_attribute("target_denom", target_denom))
+}
+
+/// Balances not reserved for repaying a debt of the same denom are sold to cover the shortfall
+/// of each debt. Amounts sold are sized by oracle price and divided by `1 - slippage`, so the swap
+/// still covers the shortfall at the worst accepted execution. The rest is swapped into `target_denom`.
+/// Denoms the swapper has no route for are left in the account, to be refunded with the rest.
+/// Returns each coin to sell with the denom it is sold for.
+pub fn debt_swaps(
+    deps: Deps,
+    positions: &Positions,
+    target_denom: &str,
+    slippage: Decimal,
+) -> ContractResult<Vec<(Coin, String)>> {
+    assert_coin_is_whitelisted(deps.storage, target_denom)?;
+
+    let debts =
+        positions.debts.iter().map(|d| (d.denom.clone(), d.amount)).collect::<HashMap<_, _>>();
+
+    let mut available = positions
+        .deposits
+        .iter()
+        .filter_map(|c| {
+            let reserved = debts.get(&c.denom).copied().unwrap_or_default();
+            let amount = c.amount.saturating_sub(reserved);
+            (!amount.is_zero()).then(|| Coin {
+                denom: c.denom.clone(),
+                amount,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let swapper = SWAPPER.load(deps.storage)?;
+    let has_route = |coin: &Coin, denom_out: &str| {
+        swapper.estimate_exact_in_swap(&deps.querier, coin, denom_out).is_ok()
+    };
+
+    let mut cache = QueryCache::default();
+    let mut swaps: Vec<(Coin, String)> = vec![];
+
+    for debt in &positions.debts {
+        let balance = positions
+            .deposits
+            .iter()
+            .find(|c| c.denom == debt.denom)
+            .map(|c| c.amount)
+            .unwrap_or_default();
+        let shortfall = debt.amount.saturating_sub(balance);
+        if shortfall.is_zero() {
+            continue;
+        }
+
+        let debt_price = cache.price(&deps, &debt.denom)?;
+        let worst_price = debt_price
+            .checked_div(Decimal::one().checked_sub(slippage)?)
+            .map_err(|_| TempCheckMulFracError {})?;
+        let mut value_needed =
+            shortfall.checked_mul_ceil(worst_price).map_err(|_| TempCheckMulFracError {})?;
+
+        for source in available.iter_mut() {
+            if value_needed.is_zero() {
+                break;
+            }
+            if source.amount.is_zero()
+                || source.denom == debt.denom
+                || !has_route(source, &debt.denom)
+            {
+                continue;
+            }
+            let price = cache.price(&deps, &source.denom)?;
+            let source_value =
+                source.amount.checked_mul_floor(price).map_err(|_| TempCheckMulFracError {})?;
+            let amount_in = if source_value <= value_needed {
+                source.amount
+            } else {
+                value_needed
+                    .checked_div_ceil(price)
+                    .map_err(|_| TempCheckMulFracError {})?
+                    .min(source.amount)
+            };
+            value_needed = value_needed.saturating_sub(source_value);
+            source.amount = source.amount.checked_sub(amount_in)?;
+            swaps.push((
+                Coin {
+                    denom: source.denom.clone(),
+                    amount: amount_in,
+                },
+                debt.denom.clone(),
+            ));
+        }
+    }
+
+    for leftover in available {
+        if leftover.amount.is_zero()
+            || leftover.denom == target_denom
+            || !has_route(&leftover, target_denom)
+        {
+            continue;
+        }
+        swaps.push((leftover, target_denom.to_string()));
+    }
+
+    Ok(swaps)
+}
+
+pub fn repay_all_debts(deps: DepsMut, env: Env, account_id: &str) -> ContractResult<Response> {
+    let debt_denoms = DEBT_SHARES
+        .prefix(account_id)
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let msgs = debt_denoms
+        .into_iter()
+        .map(|denom| {
+            assert_can_repay(deps.as_ref(), &env, account_id, &denom)?;
+            Ok(CallbackMsg::Repay {
+                account_id: account_id.to_string(),
+                coin: ActionCoin {
+                    denom,
+                    amount: ActionAmount::AccountBalance,
+                },
+            }
+            .into_cosmos_msg(&env.contract.address)?)
+        })
+        .collect::<ContractResult<Vec<_>>>()?;
+
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_attribute("action", "callback/repay_all_debts")
+        .add_attribute("account_id", account_id))
+}
+
+fn assert_can_repay(deps: Deps, env: &Env, account_id: &str, denom: &str) -> ContractResult<()> {
+    let (debt, _) = current_debt_for_denom(deps, env, account_id, denom)?;
+    let balance =
+        COIN_BALANCES.may_load(deps.storage, (account_id, denom))?.unwrap_or(Uint128::zero());
+    assert_balance_covers_debt(denom, balance, debt)
+}
+
+pub fn assert_balance_covers_debt(
+    denom: &str,
+    balance: Uint128,
+    debt: Uint128,
+) -> ContractResult<()> {
+    if balance < debt {
+        return Err(ContractError::InsufficientFundsToRepay {
+            denom: denom.to_string(),
+            balance,
+            debt,
+        });
+    }
+    Ok(())
+}
@@ -1,6 +1,8 @@
+use std::collections::BTreeSet;
+
 use cosmwasm_std::{
-    entry_point, to_binary, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Reply, Response,
-    StdError,
+    entry_point, to_binary, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Order, Reply, Response,
+    StdError, StdResult, Uint128,
 };
 use cw2::{get_contract_version, set_contract_version, ContractVersion};
 use mars_health::HealthResponse;
@@ -12,18 +14,26 @@ use mars_rover::{
 use semver::Version;
 
 use crate::{
+    borrow::max_borrow_amount,
+    cache::QueryCache,
     emergency_update::emergency_config_update,
     execute::{create_credit_account, dispatch_actions, execute_callback},
-    health::compute_health,
+    health::{
+        compute_health, compute_health_with_cache, liquidation_price, query_positions_with_value,
+    },
     instantiate::store_config,
+    liquidate_coin::preview_liquidation,
     query::{
         query_all_coin_balances, query_all_debt_shares, query_all_total_debt_shares,
         query_all_total_vault_coin_balances, query_all_vault_positions, query_allowed_coins,
-        query_config, query_positions, query_total_debt_shares, query_total_vault_coin_balance,
-        query_vaults_info,
+        query_config, query_liquidatable_accounts, query_positions, query_total_debt_shares,
+        query_total_vault_coin_balance, query_vaults_info,
     },
+    simulate::simulate_actions,
+    state::{ACCOUNTS_WITH_DEBT, DEBT_SHARES, MAX_VAULT_POSITIONS},
     update_config::{update_config, update_nft_config, update_owner},
     vault::handle_unlock_request_reply,
+    withdraw::max_withdraw_amount,
     zap::{estimate_provide_liquidity, estimate_withdraw_liquidity},
 };
 
@@ -90,9 +100,52 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> ContractResult<Binary> {
         QueryMsg::Positions {
             account_id,
         } => to_binary(&query_positions(deps, &env, &account_id)?),
+        QueryMsg::PositionsWithValue {
+            account_id,
+        } => to_binary(&query_positions_with_value(deps, &env, &account_id)?),
         QueryMsg::Health {
             account_id,
         } => to_binary::<HealthResponse>(&Into::into(compute_health(deps, &env, &account_id)?)),
+        QueryMsg::HealthUnderScenario {
+            account_id,
+            price_overrides,
+        } => to_binary::<HealthResponse>(&Into::into(compute_health_with_cache(
+            deps,
+            &env,
+            &mut QueryCache::with_prices(&price_overrides),
+            &account_id,
+        )?)),
+        QueryMsg::LiquidationPrice {
+            account_id,
+            denom,
+        } => to_binary(&liquidation_price(deps, &env, &account_id, &denom)?),
+        QueryMsg::MaxBorrowAmount {
+            account_id,
+            denom,
+        } => to_binary(&max_borrow_amount(deps, &env, &account_id, &denom)?),
+        QueryMsg::MaxWithdrawAmount {
+            account_id,
+            denom,
+        } => to_binary(&max_withdraw_amount(deps, &env, &account_id, &denom)?),
+        QueryMsg::LiquidatableAccounts {
+            start_after,
+            limit,
+        } => to_binary(&query_liquidatable_accounts(deps, &env, start_after, limit)?),
+        QueryMsg::PreviewLiquidation {
+            liquidatee_account_id,
+            debt_coin,
+            request,
+        } => to_binary(&preview_liquidation(
+            deps,
+            &env,
+            &liquidatee_account_id,
+            &debt_coin,
+            &request,
+        )?),
+        QueryMsg::SimulateActions {
+            account_id,
+            actions,
+        } => to_binary(&simulate_actions(deps, &env, &account_id, &actions)?),
         QueryMsg::AllCoinBalances {
             start_after,
             limit,
@@ -157,6 +210,21 @@ pub fn migrate(deps: DepsMut, _env: Env, _msg: Empty) -> ContractResult<Response
 
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
+    // Backfill the index of accounts with debt
+    let accounts_with_debt = DEBT_SHARES
+        .keys(deps.storage, None, None, Order::Ascending)
+        .map(|res| res.map(|(account_id, _)| account_id))
+        .collect::<StdResult<BTreeSet<_>>>()?;
+    for account_id in &accounts_with_debt {
+        ACCOUNTS_WITH_DEBT.insert(deps.storage, account_id)?;
+    }
+
+    // Seed the vault position limit with the single vault accounts were restricted to before it
+    // was configurable
+    if MAX_VAULT_POSITIONS.may_load(deps.storage)?.is_none() {
+        MAX_VAULT_POSITIONS.save(deps.storage, &Uint128::one())?;
+    }
+
     Ok(Response::new()
         .add_attribute("action", "migrate")
         .add_attribute("from_version", version)
@@ -0,0 +1,144 @@
+use cosmwasm_std::{Coin, Decimal, Deps, DepsMut, Env, Response, StdResult, Uint128};
+use mars_rover::{
+    error::{ContractError, ContractResult, TempCheckMulFracError},
+    msg::{
+        execute::{ActionCoin, CallbackMsg},
+        query::Positions,
+    },
+};
+
+use crate::{
+    cache::QueryCache,
+    health::{compute_health_from_positions, value_positions},
+    query::query_positions,
+    state::SWAPPER,
+    utils::assert_coin_is_whitelisted,
+};
+
+/// Sells collateral for the debt denom and repays the proceeds, sized so that the account's max
+/// LTV health factor ends at `target_max_ltv_health_factor`
+pub fn deleverage(
+    deps: DepsMut,
+    env: Env,
+    account_id: &str,
+    target_max_ltv_health_factor: Decimal,
+    collateral_denom: &str,
+    debt_denom: &str,
+    slippage: Decimal,
+) -> ContractResult<Response> {
+    let positions = query_positions(deps.as_ref(), &env, account_id)?;
+    let (coin_in, repay_amount) = deleverage_amounts(
+        deps.as_ref(),
+        &mut QueryCache::default(),
+        &positions,
+        target_max_ltv_health_factor,
+        collateral_denom,
+        debt_denom,
+        slippage,
+    )?;
+
+    let msgs = [
+        CallbackMsg::SwapExactIn {
+            account_id: account_id.to_string(),
+            coin_in: ActionCoin::from(&coin_in),
+            denom_out: debt_denom.to_string(),
+            slippage,
+            min_receive: None,
+        },
+        CallbackMsg::Repay {
+            account_id: account_id.to_string(),
+            coin: ActionCoin::from(&Coin {
+                denom: debt_denom.to_string(),
+                amount: repay_amount,
+            }),
+        },
+    ]
+    .iter()
+    .map(|callback| callback.into_cosmos_msg(&env.contract.address))
+    .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_attribute("action", "callback/deleverage")
+        .add_attribute("account_id", account_id)
+        .add_attribute("coin_in", coin_in.to_string())
+        .add_attribute("debt_repaid", repay_amount.to_string()))
+}
+
+/// Solves for the collateral value v to sell, given the max LTV adjusted collateral C, total debt
+/// value D, the collateral's max LTV l and the target health factor h. The swap is assumed to lose
+/// `slippage` s, with the proceeds repaying debt:
+///     (C - l * v) / (D - (1 - s) * v) = h
+///     v = (h * D - C) / (h * (1 - s) - l)
+/// The debt repaid is the swapper's estimate for the sale less `slippage`, which is the least the
+/// swap accepts, capped at the outstanding debt.
+/// Returns the collateral coin to sell and the debt amount to repay.
+pub fn deleverage_amounts(
+    deps: Deps,
+    cache: &mut QueryCache,
+    positions: &Positions,
+    target_max_ltv_health_factor: Decimal,
+    collateral_denom: &str,
+    debt_denom: &str,
+    slippage: Decimal,
+) -> ContractResult<(Coin, Uint128)> {
+    assert_coin_is_whitelisted(deps.storage, debt_denom)?;
+
+    if collateral_denom == debt_denom {
+        return Err(ContractError::RequirementsNotMet(
+            "Collateral and debt denoms must differ to deleverage".to_string(),
+        ));
+    }
+
+    let health = compute_health_from_positions(deps, cache, positions)?;
+    let valued = value_positions(deps, cache, positions)?;
+
+    let collateral =
+        valued.deposits.iter().find(|c| c.coin.denom == collateral_denom).ok_or_else(|| {
+            ContractError::RequirementsNotMet(format!("No balance of {collateral_denom}"))
+        })?;
+    let debt = valued.debts.iter().find(|d| d.denom == debt_denom).ok_or(ContractError::NoDebt)?;
+
+    let h = target_max_ltv_health_factor;
+    let target_adjusted_collateral =
+        health.total_debt_value.checked_mul_ceil(h).map_err(|_| TempCheckMulFracError {})?;
+    if target_adjusted_collateral <= health.max_ltv_adjusted_collateral {
+        return Err(ContractError::RequirementsNotMet(format!(
+            "Max LTV health factor is already at or above {h}"
+        )));
+    }
+
+    let retained = Decimal::one().checked_sub(slippage)?;
+    let denominator = h.checked_mul(retained)?;
+    if denominator <= collateral.max_ltv {
+        return Err(ContractError::RequirementsNotMet(format!(
+            "Selling {collateral_denom} cannot raise max LTV health factor to {h}"
+        )));
+    }
+    let value_to_sell = target_adjusted_collateral
+        .checked_sub(health.max_ltv_adjusted_collateral)?
+        .checked_div_ceil(denominator - collateral.max_ltv)
+        .map_err(|_| TempCheckMulFracError {})?;
+
+    let amount_in = value_to_sell
+        .checked_div_ceil(collateral.coin.price)
+        .map_err(|_| TempCheckMulFracError {})?;
+    if amount_in > collateral.coin.amount {
+        return Err(ContractError::RequirementsNotMet(format!(
+            "Not enough {collateral_denom} collateral to reach max LTV health factor of {h}"
+        )));
+    }
+    let coin_in = Coin {
+        denom: collateral_denom.to_string(),
+        amount: amount_in,
+    };
+
+    let estimate =
+        SWAPPER.load(deps.storage)?.estimate_exact_in_swap(&deps.querier, &coin_in, debt_denom)?;
+    let repay_amount = estimate
+        .checked_mul_floor(retained)
+        .map_err(|_| TempCheckMulFracError {})?
+        .min(debt.amount);
+
+    Ok((coin_in, repay_amount))
+}
@@ -10,18 +10,24 @@ use mars_rover::{
 
 use crate::{
     borrow::borrow,
+    close_account::{
+        exit_unlocked_vaults, repay_all_debts, swap_for_debts, withdraw_all_liquidity,
+    },
+    deleverage::deleverage,
     deposit::deposit,
     health::{assert_max_ltv, compute_health},
+    leverage::leverage,
     liquidate_coin::liquidate_coin,
     refund::refund_coin_balances,
     repay::repay,
     state::ACCOUNT_NFT,
-    swap::swap_exact_in,
+    swap::{swap_exact_in, swap_exact_out},
     update_coin_balances::update_coin_balance,
     utils::{assert_is_token_owner, assert_not_contract_in_config},
     vault::{
-        assert_only_one_vault_position, enter_vault, exit_vault, exit_vault_unlocked,
-        liquidate_vault, request_vault_unlock, update_vault_coin_balance,
+        assert_max_vault_positions, cancel_vault_unlock, enter_vault, enter_vault_with_zap,
+        exit_vault, exit_vault_into, exit_vault_unlocked, liquidate_vault, request_vault_unlock,
+        swap_withdrawn_into, update_vault_coin_balance, vault_positions_count, zap_into_vault,
     },
     withdraw::withdraw,
     zap::{provide_liquidity, withdraw_liquidity},
@@ -55,6 +61,7 @@ pub fn dispatch_actions(
     let mut callbacks: Vec<CallbackMsg> = vec![];
     let mut received_coins = Coins::try_from(info.funds)?;
     let prev_health = compute_health(deps.as_ref(), &env, account_id)?;
+    let prev_vault_positions = vault_positions_count(deps.storage, account_id)?;
 
     for action in actions {
         match action {
@@ -77,10 +84,24 @@ pub fn dispatch_actions(
             Action::EnterVault {
                 vault,
                 coin,
+                min_receive,
             } => callbacks.push(CallbackMsg::EnterVault {
                 account_id: account_id.to_string(),
                 vault: vault.check(deps.api)?,
                 coin: coin.clone(),
+                min_receive: *min_receive,
+            }),
+            Action::EnterVaultWithZap {
+                vault,
+                coins_in,
+                slippage,
+                min_vault_tokens,
+            } => callbacks.push(CallbackMsg::EnterVaultWithZap {
+                account_id: account_id.to_string(),
+                vault: vault.check(deps.api)?,
+                coins_in: coins_in.clone(),
+                slippage: *slippage,
+                min_vault_tokens: *min_vault_tokens,
             }),
             Action::LiquidateCoin {
                 liquidatee_account_id,
@@ -108,19 +129,45 @@ pub fn dispatch_actions(
                 coin_in,
                 denom_out,
                 slippage,
+                min_receive,
             } => callbacks.push(CallbackMsg::SwapExactIn {
                 account_id: account_id.to_string(),
                 coin_in: coin_in.clone(),
                 denom_out: denom_out.clone(),
                 slippage: *slippage,
+                min_receive: *min_receive,
+            }),
+            Action::SwapExactOut {
+                coin_out,
+                denom_in,
+                max_in,
+            } => callbacks.push(CallbackMsg::SwapExactOut {
+                account_id: account_id.to_string(),
+                coin_out: coin_out.clone(),
+                denom_in: denom_in.clone(),
+                max_in: *max_in,
             }),
             Action::ExitVault {
                 vault,
                 amount,
+                min_receive,
             } => callbacks.push(CallbackMsg::ExitVault {
                 account_id: account_id.to_string(),
                 vault: vault.check(deps.api)?,
                 amount: *amount,
+                min_receive: *min_receive,
+            }),
+            Action::ExitVaultInto {
+                vault,
+                amount,
+                denom_out,
+                slippage,
+            } => callbacks.push(CallbackMsg::ExitVaultInto {
+                account_id: account_id.to_string(),
+                vault: vault.check(deps.api)?,
+                amount: *amount,
+                denom_out: denom_out.clone(),
+                slippage: *slippage,
             }),
             Action::RequestVaultUnlock {
                 vault,
@@ -130,13 +177,23 @@ pub fn dispatch_actions(
                 vault: vault.check(deps.api)?,
                 amount: *amount,
             }),
+            Action::CancelVaultUnlock {
+                vault,
+                id,
+            } => callbacks.push(CallbackMsg::CancelVaultUnlock {
+                account_id: account_id.to_string(),
+                vault: vault.check(deps.api)?,
+                position_id: *id,
+            }),
             Action::ExitVaultUnlocked {
                 id,
                 vault,
+                min_receive,
             } => callbacks.push(CallbackMsg::ExitVaultUnlocked {
                 account_id: account_id.to_string(),
                 vault: vault.check(deps.api)?,
                 position_id: *id,
+                min_receive: *min_receive,
             }),
             Action::ProvideLiquidity {
                 coins_in,
@@ -150,15 +207,63 @@ pub fn dispatch_actions(
             }),
             Action::WithdrawLiquidity {
                 lp_token,
+                min_receive,
             } => callbacks.push(CallbackMsg::WithdrawLiquidity {
                 account_id: account_id.to_string(),
                 lp_token: lp_token.clone(),
+                min_receive: min_receive.clone(),
             }),
             Action::RefundAllCoinBalances {} => {
                 callbacks.push(CallbackMsg::RefundAllCoinBalances {
                     account_id: account_id.to_string(),
                 })
             }
+            Action::CloseAccount {
+                target_denom,
+                slippage,
+            } => callbacks.extend([
+                CallbackMsg::ExitUnlockedVaults {
+                    account_id: account_id.to_string(),
+                },
+                CallbackMsg::WithdrawAllLiquidity {
+                    account_id: account_id.to_string(),
+                },
+                CallbackMsg::SwapForDebts {
+                    account_id: account_id.to_string(),
+                    target_denom: target_denom.clone(),
+                    slippage: *slippage,
+                },
+                CallbackMsg::RepayAllDebts {
+                    account_id: account_id.to_string(),
+                },
+                CallbackMsg::RefundAllCoinBalances {
+                    account_id: account_id.to_string(),
+                },
+            ]),
+            Action::Deleverage {
+                target_max_ltv_health_factor,
+                collateral_denom,
+                debt_denom,
+                slippage,
+            } => callbacks.push(CallbackMsg::Deleverage {
+                account_id: account_id.to_string(),
+                target_max_ltv_health_factor: *target_max_ltv_health_factor,
+                collateral_denom: collateral_denom.clone(),
+                debt_denom: debt_denom.clone(),
+                slippage: *slippage,
+            }),
+            Action::Leverage {
+                collateral_denom,
+                borrow_denom,
+                target_leverage,
+                slippage,
+            } => callbacks.push(CallbackMsg::Leverage {
+                account_id: account_id.to_string(),
+                collateral_denom: collateral_denom.clone(),
+                borrow_denom: borrow_denom.clone(),
+                target_leverage: *target_leverage,
+                slippage: *slippage,
+            }),
         }
     }
 
@@ -169,9 +274,10 @@ pub fn dispatch_actions(
     }
 
     callbacks.extend([
-        // Fields of Mars ONLY assertion. Only one vault position per credit account
-        CallbackMsg::AssertOneVaultPositionOnly {
+        // Health checks query every vault held, so the number of vault positions is capped
+        CallbackMsg::AssertMaxVaultPositions {
             account_id: account_id.to_string(),
+            prev_vault_positions,
         },
         // after user selected actions, we assert LTV is either:
         // - Healthy, if prior to actions MaxLTV health factor >= 1 or None
@@ -225,16 +331,40 @@ pub fn execute_callback(
             account_id,
             vault,
             coin,
-        } => enter_vault(deps, &env.contract.address, &account_id, vault, &coin),
+            min_receive,
+        } => enter_vault(deps, &env.contract.address, &account_id, vault, &coin, min_receive),
+        CallbackMsg::EnterVaultWithZap {
+            account_id,
+            vault,
+            coins_in,
+            slippage,
+            min_vault_tokens,
+        } => enter_vault_with_zap(
+            deps,
+            env,
+            &account_id,
+            vault,
+            coins_in,
+            slippage,
+            min_vault_tokens,
+        ),
+        CallbackMsg::ZapIntoVault {
+            account_id,
+            vault,
+            retained,
+            min_vault_tokens,
+        } => zap_into_vault(deps, env, &account_id, vault, retained, min_vault_tokens),
         CallbackMsg::UpdateVaultCoinBalance {
             vault,
             account_id,
             previous_total_balance,
+            min_receive,
         } => update_vault_coin_balance(
             deps,
             vault,
             &account_id,
             previous_total_balance,
+            min_receive,
             &env.contract.address,
         ),
         CallbackMsg::LiquidateCoin {
@@ -270,26 +400,63 @@ pub fn execute_callback(
             coin_in,
             denom_out,
             slippage,
-        } => swap_exact_in(deps, env, &account_id, &coin_in, &denom_out, slippage),
+            min_receive,
+        } => swap_exact_in(deps, env, &account_id, &coin_in, &denom_out, slippage, min_receive),
+        CallbackMsg::SwapExactOut {
+            account_id,
+            coin_out,
+            denom_in,
+            max_in,
+        } => swap_exact_out(deps, env, &account_id, &coin_out, &denom_in, max_in),
         CallbackMsg::UpdateCoinBalance {
             account_id,
             previous_balance,
-        } => update_coin_balance(deps, env, &account_id, &previous_balance),
+            min_receive,
+        } => update_coin_balance(deps, env, &account_id, &previous_balance, min_receive),
         CallbackMsg::ExitVault {
             account_id,
             vault,
             amount,
-        } => exit_vault(deps, env, &account_id, vault, amount),
+            min_receive,
+        } => exit_vault(deps, env, &account_id, vault, amount, min_receive),
+        CallbackMsg::ExitVaultInto {
+            account_id,
+            vault,
+            amount,
+            denom_out,
+            slippage,
+        } => exit_vault_into(deps, env, &account_id, vault, amount, &denom_out, slippage),
+        CallbackMsg::SwapWithdrawnInto {
+            account_id,
+            retained,
+            lp_underlying,
+            denom_out,
+            slippage,
+        } => swap_withdrawn_into(
+            deps,
+            env,
+            &account_id,
+            retained,
+            lp_underlying,
+            &denom_out,
+            slippage,
+        ),
         CallbackMsg::RequestVaultUnlock {
             account_id,
             vault,
             amount,
         } => request_vault_unlock(deps, &account_id, vault, amount),
+        CallbackMsg::CancelVaultUnlock {
+            account_id,
+            vault,
+            position_id,
+        } => cancel_vault_unlock(deps, &env.contract.address, &account_id, vault, position_id),
         CallbackMsg::ExitVaultUnlocked {
             account_id,
             vault,
             position_id,
-        } => exit_vault_unlocked(deps, env, &account_id, vault, position_id),
+            min_receive,
+        } => exit_vault_unlocked(deps, env, &account_id, vault, position_id, min_receive),
         CallbackMsg::ProvideLiquidity {
             account_id,
             coins_in,
@@ -299,12 +466,58 @@ pub fn execute_callback(
         CallbackMsg::WithdrawLiquidity {
             account_id,
             lp_token,
-        } => withdraw_liquidity(deps, env, &account_id, &lp_token),
-        CallbackMsg::AssertOneVaultPositionOnly {
+            min_receive,
+        } => withdraw_liquidity(deps, env, &account_id, &lp_token, min_receive),
+        CallbackMsg::AssertMaxVaultPositions {
             account_id,
-        } => assert_only_one_vault_position(deps, &account_id),
+            prev_vault_positions,
+        } => assert_max_vault_positions(deps.as_ref(), &account_id, prev_vault_positions),
         CallbackMsg::RefundAllCoinBalances {
             account_id,
         } => refund_coin_balances(deps, env, &account_id),
+        CallbackMsg::ExitUnlockedVaults {
+            account_id,
+        } => exit_unlocked_vaults(deps, env, &account_id),
+        CallbackMsg::WithdrawAllLiquidity {
+            account_id,
+        } => withdraw_all_liquidity(deps, env, &account_id),
+        CallbackMsg::SwapForDebts {
+            account_id,
+            target_denom,
+            slippage,
+        } => swap_for_debts(deps, env, &account_id, &target_denom, slippage),
+        CallbackMsg::RepayAllDebts {
+            account_id,
+        } => repay_all_debts(deps, env, &account_id),
+        CallbackMsg::Deleverage {
+            account_id,
+            target_max_ltv_health_factor,
+            collateral_denom,
+            debt_denom,
+            slippage,
+        } => deleverage(
+            deps,
+            env,
+            &account_id,
+            target_max_ltv_health_factor,
+            &collateral_denom,
+            &debt_denom,
+            slippage,
+        ),
+        CallbackMsg::Leverage {
+            account_id,
+            collateral_denom,
+            borrow_denom,
+            target_leverage,
+            slippage,
+        } => leverage(
+            deps,
+            env,
+            &account_id,
+            &collateral_denom,
+            &borrow_denom,
+            target_leverage,
+            slippage,
+        ),
     }
 }
@@ -0,0 +1,133 @@
+use std::cmp::min;
+
+use cosmwasm_std::{Coin, Decimal, Deps, DepsMut, Env, Response, StdResult};
+use mars_health::Health;
+use mars_rover::{
+    error::{ContractError, ContractResult, TempCheckMulFracError},
+    msg::execute::{ActionCoin, CallbackMsg},
+};
+
+use crate::{
+    borrow::MAX_BORROW_ROUNDING_BUFFER, cache::QueryCache, health::compute_health_with_cache,
+    state::ALLOWED_COINS, utils::assert_coin_is_whitelisted,
+};
+
+/// Borrows and swaps the borrowed coins into collateral. Looping borrow & swap until the target is
+/// reached ends in the same position as borrowing the final amount at once, so the amount is solved
+/// for directly and executed as a single borrow & swap.
+pub fn leverage(
+    deps: DepsMut,
+    env: Env,
+    account_id: &str,
+    collateral_denom: &str,
+    borrow_denom: &str,
+    target_leverage: Decimal,
+    slippage: Decimal,
+) -> ContractResult<Response> {
+    let mut cache = QueryCache::default();
+    let health = compute_health_with_cache(deps.as_ref(), &env, &mut cache, account_id)?;
+    let borrow_coin = leverage_borrow_amount(
+        deps.as_ref(),
+        &mut cache,
+        &health,
+        collateral_denom,
+        borrow_denom,
+        target_leverage,
+        slippage,
+    )?;
+
+    let msgs = [
+        CallbackMsg::Borrow {
+            account_id: account_id.to_string(),
+            coin: borrow_coin.clone(),
+        },
+        CallbackMsg::SwapExactIn {
+            account_id: account_id.to_string(),
+            coin_in: ActionCoin::from(&borrow_coin),
+            denom_out: collateral_denom.to_string(),
+            slippage,
+            min_receive: None,
+        },
+    ]
+    .iter()
+    .map(|callback| callback.into_cosmos_msg(&env.contract.address))
+    .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_attribute("action", "callback/leverage")
+        .add_attribute("account_id", account_id)
+        .add_attribute("coin_borrowed", borrow_coin.to_string())
+        .add_attribute("denom_out", collateral_denom))
+}
+
+/// Leverage is total collateral value C over equity (C - D). Borrowing value x and swapping it into
+/// collateral at `slippage` s adds x * (1 - s) to collateral and x to debt. Solving for target L:
+///     (C + x * (1 - s)) / (C + x * (1 - s) - D - x) = L
+///     x = (L * (C - D) - C) / (1 - s + L * s)
+/// The amount is capped so that max LTV adjusted collateral M still covers debt, where the
+/// collateral's max LTV l weighs the swap proceeds:
+///     x_max = (M - D) / (1 - l * (1 - s))
+pub fn leverage_borrow_amount(
+    deps: Deps,
+    cache: &mut QueryCache,
+    health: &Health,
+    collateral_denom: &str,
+    borrow_denom: &str,
+    target_leverage: Decimal,
+    slippage: Decimal,
+) -> ContractResult<Coin> {
+    assert_coin_is_whitelisted(deps.storage, collateral_denom)?;
+    assert_coin_is_whitelisted(deps.storage, borrow_denom)?;
+
+    if collateral_denom == borrow_denom {
+        return Err(ContractError::RequirementsNotMet(
+            "Collateral and borrow denoms must differ to leverage".to_string(),
+        ));
+    }
+
+    let collateral = health.total_collateral_value;
+    let debt = health.total_debt_value;
+
+    let target_collateral = collateral
+        .saturating_sub(debt)
+        .checked_mul_floor(target_leverage)
+        .map_err(|_| TempCheckMulFracError {})?;
+    if target_collateral <= collateral {
+        return Err(ContractError::RequirementsNotMet(format!(
+            "Leverage is already at or above {target_leverage}"
+        )));
+    }
+
+    let retained = Decimal::one().checked_sub(slippage)?;
+    let value_to_borrow = (target_collateral - collateral)
+        .checked_div_floor(retained.checked_add(target_leverage.checked_mul(slippage)?)?)
+        .map_err(|_| TempCheckMulFracError {})?;
+
+    let collateral_max_ltv = if ALLOWED_COINS.contains(deps.storage, collateral_denom) {
+        cache.market(&deps, collateral_denom)?.max_loan_to_value
+    } else {
+        Decimal::zero()
+    };
+    let headroom = health
+        .max_ltv_adjusted_collateral
+        .saturating_sub(debt)
+        .saturating_sub(MAX_BORROW_ROUNDING_BUFFER);
+    let max_value_to_borrow = headroom
+        .checked_div_floor(Decimal::one().checked_sub(collateral_max_ltv.checked_mul(retained)?)?)
+        .map_err(|_| TempCheckMulFracError {})?;
+
+    let borrow_price = cache.price(&deps, borrow_denom)?;
+    let amount = min(value_to_borrow, max_value_to_borrow)
+        .checked_div_floor(borrow_price)
+        .map_err(|_| TempCheckMulFracError {})?;
+
+    if amount.is_zero() {
+        return Err(ContractError::NoAmount);
+    }
+
+    Ok(Coin {
+        denom: borrow_denom.to_string(),
+        amount,
+    })
+}
@@ -1,15 +1,20 @@
 pub mod contract;
 
 pub mod borrow;
+pub mod cache;
+pub mod close_account;
+pub mod deleverage;
 pub mod deposit;
 pub mod emergency_update;
 pub mod execute;
 pub mod health;
 pub mod instantiate;
+pub mod leverage;
 pub mod liquidate_coin;
 pub mod query;
 pub mod refund;
 pub mod repay;
+pub mod simulate;
 pub mod state;
 pub mod swap;
 pub mod update_coin_balances;
@@ -1,20 +1,24 @@
 use std::ops::Add;
 
 use cosmwasm_std::{
-    Coin, CosmosMsg, Decimal, DepsMut, Env, QuerierWrapper, Response, StdError, Storage, Uint128,
+    Coin, CosmosMsg, Decimal, Deps, DepsMut, Env, Response, StdError, Storage, Uint128,
 };
 use mars_rover::{
-    adapters::oracle::Oracle,
     error::{ContractError, ContractResult, TempCheckMulFracError},
-    msg::execute::CallbackMsg,
+    msg::{
+        execute::CallbackMsg,
+        query::{LiquidationLimit, LiquidationPreview, LiquidationRequest},
+    },
     traits::Stringify,
 };
 
 use crate::{
-    health::compute_health,
+    cache::QueryCache,
+    health::compute_health_with_cache,
     repay::current_debt_for_denom,
-    state::{COIN_BALANCES, MAX_CLOSE_FACTOR, ORACLE, RED_BANK},
+    state::{COIN_BALANCES, MAX_CLOSE_FACTOR},
     utils::{decrement_coin_balance, increment_coin_balance},
+    vault::preview_vault_liquidation,
 };
 
 pub fn liquidate_coin(
@@ -25,17 +29,17 @@ pub fn liquidate_coin(
     debt_coin: Coin,
     request_coin_denom: &str,
 ) -> ContractResult<Response> {
-    let request_coin_balance = COIN_BALANCES
-        .load(deps.storage, (liquidatee_account_id, request_coin_denom))
-        .map_err(|_| ContractError::CoinNotAvailable(request_coin_denom.to_string()))?;
-
-    let (debt, request) = calculate_liquidation(
-        &deps,
+    let LiquidationPreview {
+        debt,
+        request,
+        ..
+    } = preview_coin_liquidation(
+        deps.as_ref(),
         &env,
+        &mut QueryCache::default(),
         liquidatee_account_id,
         &debt_coin,
         request_coin_denom,
-        request_coin_balance,
     )?;
 
     let repay_msg =
@@ -54,22 +58,73 @@ pub fn liquidate_coin(
         .add_attribute("coin_liquidated", request.to_string()))
 }
 
+pub fn preview_liquidation(
+    deps: Deps,
+    env: &Env,
+    liquidatee_account_id: &str,
+    debt_coin: &Coin,
+    request: &LiquidationRequest,
+) -> ContractResult<LiquidationPreview> {
+    let cache = &mut QueryCache::default();
+    match request {
+        LiquidationRequest::Coin(denom) => {
+            preview_coin_liquidation(deps, env, cache, liquidatee_account_id, debt_coin, denom)
+        }
+        LiquidationRequest::Vault {
+            request_vault,
+            position_type,
+        } => preview_vault_liquidation(
+            deps,
+            env,
+            cache,
+            liquidatee_account_id,
+            debt_coin,
+            &request_vault.check(deps.api)?,
+            position_type,
+        ),
+    }
+}
+
+fn preview_coin_liquidation(
+    deps: Deps,
+    env: &Env,
+    cache: &mut QueryCache,
+    liquidatee_account_id: &str,
+    debt_coin: &Coin,
+    request_coin_denom: &str,
+) -> ContractResult<LiquidationPreview> {
+    let request_coin_balance = COIN_BALANCES
+        .load(deps.storage, (liquidatee_account_id, request_coin_denom))
+        .map_err(|_| ContractError::CoinNotAvailable(request_coin_denom.to_string()))?;
+
+    calculate_liquidation(
+        deps,
+        env,
+        cache,
+        liquidatee_account_id,
+        debt_coin,
+        request_coin_denom,
+        request_coin_balance,
+    )
+}
+
 /// Calculates precise debt & request coin amounts to liquidate
 /// The debt amount will be adjusted down if:
 /// - Exceeds liquidatee's total debt for denom
 /// - Not enough liquidatee request coin balance to match
 /// - The value of the debt repaid exceeds the maximum close factor %
-/// Returns -> (Debt Coin, Request Coin)
+/// Returns -> LiquidationPreview with the debt & request coins and the binding limit
 pub fn calculate_liquidation(
-    deps: &DepsMut,
+    deps: Deps,
     env: &Env,
+    cache: &mut QueryCache,
     liquidatee_account_id: &str,
     debt_coin: &Coin,
     request_coin: &str,
     request_coin_balance: Uint128,
-) -> ContractResult<(Coin, Coin)> {
+) -> ContractResult<LiquidationPreview> {
     // Assert the liquidatee's credit account is liquidatable
-    let health = compute_health(deps.as_ref(), env, liquidatee_account_id)?;
+    let health = compute_health_with_cache(deps, env, cache, liquidatee_account_id)?;
     if !health.is_liquidatable() {
         return Err(ContractError::NotLiquidatable {
             account_id: liquidatee_account_id.to_string(),
@@ -79,7 +134,7 @@ pub fn calculate_liquidation(
 
     // Ensure debt repaid does not exceed liquidatee's total debt for denom
     let (total_debt_amount, _) =
-        current_debt_for_denom(deps.as_ref(), env, liquidatee_account_id, &debt_coin.denom)?;
+        current_debt_for_denom(deps, env, liquidatee_account_id, &debt_coin.denom)?;
 
     // Ensure debt amount does not exceed close factor % of the liquidatee's total debt value
     let close_factor = MAX_CLOSE_FACTOR.load(deps.storage)?;
@@ -87,62 +142,60 @@ pub fn calculate_liquidation(
         .total_debt_value
         .checked_mul_floor(close_factor)
         .map_err(|_| TempCheckMulFracError {})?;
-    let oracle = ORACLE.load(deps.storage)?;
-    let debt_res = oracle.query_price(&deps.querier, &debt_coin.denom)?;
+    let debt_price = cache.price(&deps, &debt_coin.denom)?;
     let max_close_amount =
-        max_close_value.checked_div_floor(debt_res.price).map_err(|_| TempCheckMulFracError {})?;
+        max_close_value.checked_div_floor(debt_price).map_err(|_| TempCheckMulFracError {})?;
 
     // Calculate the maximum debt possible to repay given liquidatee's request coin balance
     // FORMULA: debt amount = request value / (1 + liquidation bonus %) / debt price
-    let request_res = oracle.query_price(&deps.querier, request_coin)?;
+    let request_price = cache.price(&deps, request_coin)?;
     let max_request_value = request_coin_balance
-        .checked_mul_floor(request_res.price)
+        .checked_mul_floor(request_price)
         .map_err(|_| TempCheckMulFracError {})?;
-    let liq_bonus_rate = RED_BANK
-        .load(deps.storage)?
-        .query_market(&deps.querier, &debt_coin.denom)?
-        .liquidation_bonus;
+    let liq_bonus_rate = cache.market(&deps, &debt_coin.denom)?.liquidation_bonus;
     let request_coin_adjusted_max_debt = max_request_value
         .checked_div_floor(Decimal::one().add(liq_bonus_rate))
         .map_err(|_| TempCheckMulFracError {})?
-        .checked_div_floor(debt_res.price)
+        .checked_div_floor(debt_price)
         .map_err(|_| TempCheckMulFracError {})?;
 
-    let final_debt_to_repay = *vec![
-        debt_coin.amount,
-        total_debt_amount,
-        max_close_amount,
-        request_coin_adjusted_max_debt,
+    // On ties, the earliest limit in the list is reported
+    let (final_debt_to_repay, limit) = [
+        (debt_coin.amount, LiquidationLimit::RequestedAmount),
+        (total_debt_amount, LiquidationLimit::TotalDebt),
+        (max_close_amount, LiquidationLimit::CloseFactor),
+        (request_coin_adjusted_max_debt, LiquidationLimit::CollateralBalance),
     ]
-    .iter()
-    .min()
+    .into_iter()
+    .min_by_key(|(amount, _)| *amount)
     .ok_or_else(|| StdError::generic_err("Minimum not found"))?;
 
     // Calculate exact request coin amount to give to liquidator
     // FORMULA: request amount = debt value * (1 + liquidation bonus %) / request coin price
     let request_amount = final_debt_to_repay
-        .checked_mul_floor(debt_res.price)
+        .checked_mul_floor(debt_price)
         .map_err(|_| TempCheckMulFracError {})?
         .checked_mul_floor(liq_bonus_rate.add(Decimal::one()))
         .map_err(|_| TempCheckMulFracError {})?
-        .checked_div_floor(request_res.price)
+        .checked_div_floor(request_price)
         .map_err(|_| TempCheckMulFracError {})?;
 
-    // (Debt Coin, Request Coin)
-    let result = (
-        Coin {
-            denom: debt_coin.denom.clone(),
-            amount: final_debt_to_repay,
-        },
-        Coin {
-            denom: request_coin.to_string(),
-            amount: request_amount,
-        },
-    );
-
-    assert_liquidation_profitable(&deps.querier, &oracle, result.clone())?;
-
-    Ok(result)
+    let debt = Coin {
+        denom: debt_coin.denom.clone(),
+        amount: final_debt_to_repay,
+    };
+    let request = Coin {
+        denom: request_coin.to_string(),
+        amount: request_amount,
+    };
+
+    assert_liquidation_profitable(&deps, cache, (debt.clone(), request.clone()))?;
+
+    Ok(LiquidationPreview {
+        debt,
+        request,
+        limit,
+    })
 }
 
 pub fn repay_debt(
@@ -167,12 +220,12 @@ pub fn repay_debt(
 /// In scenarios with small amounts or large gap between coin prices, there is a possibility
 /// that the liquidation will result in loss for the liquidator. This assertion prevents this.
 fn assert_liquidation_profitable(
-    querier: &QuerierWrapper,
-    oracle: &Oracle,
+    deps: &Deps,
+    cache: &mut QueryCache,
     (debt_coin, request_coin): (Coin, Coin),
 ) -> ContractResult<()> {
-    let debt_value = oracle.query_total_value(querier, &[debt_coin.clone()])?;
-    let request_value = oracle.query_total_value(querier, &[request_coin.clone()])?;
+    let debt_value = cache.value(deps, &debt_coin)?;
+    let request_value = cache.value(deps, &request_coin)?;
 
     if debt_value >= request_value {
         return Err(ContractError::LiquidationNotProfitable {
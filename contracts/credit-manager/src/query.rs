@@ -4,16 +4,18 @@ use mars_rover::{
     adapters::vault::{Vault, VaultBase, VaultPosition, VaultUnchecked},
     error::ContractResult,
     msg::query::{
-        CoinBalanceResponseItem, ConfigResponse, DebtAmount, DebtShares, Positions,
-        SharesResponseItem, VaultInfoResponse, VaultPositionResponseItem, VaultWithBalance,
+        CoinBalanceResponseItem, ConfigResponse, DebtAmount, DebtShares, LiquidatableAccount,
+        LiquidatableAccountsResponse, Positions, SharesResponseItem, VaultInfoResponse,
+        VaultPositionResponseItem, VaultWithBalance,
     },
 };
 
 use crate::{
+    health::compute_health,
     state::{
-        ACCOUNT_NFT, ALLOWED_COINS, COIN_BALANCES, DEBT_SHARES, MAX_CLOSE_FACTOR,
-        MAX_UNLOCKING_POSITIONS, ORACLE, OWNER, RED_BANK, SWAPPER, TOTAL_DEBT_SHARES,
-        VAULT_CONFIGS, VAULT_POSITIONS, ZAPPER,
+        ACCOUNTS_WITH_DEBT, ACCOUNT_NFT, ALLOWED_COINS, COIN_BALANCES, DEBT_SHARES,
+        MAX_CLOSE_FACTOR, MAX_UNLOCKING_POSITIONS, MAX_VAULT_POSITIONS, ORACLE, OWNER, RED_BANK,
+        SWAPPER, TOTAL_DEBT_SHARES, VAULT_CONFIGS, VAULT_POSITIONS, ZAPPER,
     },
     utils::debt_shares_to_amount,
     vault::vault_utilization_in_deposit_cap_denom,
@@ -30,6 +32,7 @@ pub fn query_config(deps: Deps) -> ContractResult<ConfigResponse> {
         oracle: ORACLE.load(deps.storage)?.address().into(),
         max_close_factor: MAX_CLOSE_FACTOR.load(deps.storage)?,
         max_unlocking_positions: MAX_UNLOCKING_POSITIONS.load(deps.storage)?,
+        max_vault_positions: MAX_VAULT_POSITIONS.load(deps.storage)?,
         swapper: SWAPPER.load(deps.storage)?.address().into(),
         zapper: ZAPPER.load(deps.storage)?.address().into(),
     })
@@ -217,6 +220,43 @@ pub fn query_allowed_coins(
         .collect::<StdResult<Vec<_>>>()
 }
 
+pub fn query_liquidatable_accounts(
+    deps: Deps,
+    env: &Env,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> ContractResult<LiquidatableAccountsResponse> {
+    let start = start_after.as_ref().map(|account_id| Bound::exclusive(account_id.as_str()));
+
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+    // Health is computed for every account scanned, so the limit applies to the scan
+    let scanned = ACCOUNTS_WITH_DEBT
+        .items(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+    let next_start_after = if scanned.len() == limit {
+        scanned.last().cloned()
+    } else {
+        None
+    };
+
+    let mut accounts = vec![];
+    for account_id in scanned {
+        let health = compute_health(deps, env, &account_id)?;
+        if health.is_liquidatable() {
+            accounts.push(LiquidatableAccount {
+                account_id,
+                health: health.into(),
+            });
+        }
+    }
+    Ok(LiquidatableAccountsResponse {
+        accounts,
+        next_start_after,
+    })
+}
+
 pub fn query_total_debt_shares(deps: Deps, denom: &str) -> StdResult<DebtShares> {
     let shares = TOTAL_DEBT_SHARES.load(deps.storage, denom)?;
     Ok(DebtShares {
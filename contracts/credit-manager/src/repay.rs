@@ -1,13 +1,13 @@
 use std::cmp::min;
 
-use cosmwasm_std::{Coin, Deps, DepsMut, Env, Response, Uint128};
+use cosmwasm_std::{Coin, Deps, DepsMut, Env, Order, Response, Uint128};
 use mars_rover::{
     error::{ContractError, ContractResult},
     msg::execute::ActionCoin,
 };
 
 use crate::{
-    state::{DEBT_SHARES, RED_BANK, TOTAL_DEBT_SHARES},
+    state::{ACCOUNTS_WITH_DEBT, DEBT_SHARES, RED_BANK, TOTAL_DEBT_SHARES},
     utils::{debt_shares_to_amount, decrement_coin_balance},
 };
 
@@ -20,16 +20,19 @@ pub fn repay(
     // Ensure repayment does not exceed max debt on account
     let (debt_amount, debt_shares) =
         current_debt_for_denom(deps.as_ref(), &env, account_id, &coin.denom)?;
-    let amount_to_repay = min(debt_amount, coin.amount.value().unwrap_or(Uint128::MAX));
-    let coin_to_repay = Coin {
-        denom: coin.denom.to_string(),
-        amount: amount_to_repay,
-    };
-    let shares_to_repay = debt_amount_to_shares(deps.as_ref(), &env, &coin_to_repay)?;
+    let (coin_to_repay, shares_to_repay) = repay_amounts(deps.as_ref(), &env, debt_amount, coin)?;
 
     // Decrement token's debt position
-    if amount_to_repay == debt_amount {
+    if coin_to_repay.amount == debt_amount {
         DEBT_SHARES.remove(deps.storage, (account_id, &coin.denom));
+        let has_other_debts = DEBT_SHARES
+            .prefix(account_id)
+            .keys(deps.storage, None, None, Order::Ascending)
+            .next()
+            .is_some();
+        if !has_other_debts {
+            ACCOUNTS_WITH_DEBT.remove(deps.storage, account_id)?;
+        }
     } else {
         DEBT_SHARES.save(
             deps.storage,
@@ -59,6 +62,22 @@ pub fn repay(
         .add_attribute("coin_repaid", coin_to_repay.to_string()))
 }
 
+/// The coin repaid for a debt of `debt_amount`, and the debt shares it is worth. Repayment is
+/// capped at the debt.
+pub fn repay_amounts(
+    deps: Deps,
+    env: &Env,
+    debt_amount: Uint128,
+    coin: &ActionCoin,
+) -> ContractResult<(Coin, Uint128)> {
+    let coin_to_repay = Coin {
+        denom: coin.denom.to_string(),
+        amount: min(debt_amount, coin.amount.value().unwrap_or(Uint128::MAX)),
+    };
+    let shares_to_repay = debt_amount_to_shares(deps, env, &coin_to_repay)?;
+    Ok((coin_to_repay, shares_to_repay))
+}
+
 fn debt_amount_to_shares(deps: Deps, env: &Env, coin: &Coin) -> ContractResult<Uint128> {
     let red_bank = RED_BANK.load(deps.storage)?;
     let total_debt_shares = TOTAL_DEBT_SHARES.load(deps.storage, &coin.denom)?;
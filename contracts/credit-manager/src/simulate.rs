@@ -0,0 +1,827 @@
+use std::iter;
+
+use cosmwasm_std::{Coin, Deps, Env, Uint128};
+use mars_rover::{
+    adapters::vault::{
+        UnlockingChange, UpdateType, Vault, VaultPosition, VaultPositionType, VaultPositionUpdate,
+        VaultUnlockingPosition,
+    },
+    coins::Coins,
+    error::{ContractError, ContractResult},
+    msg::{
+        execute::{Action, ActionAmount, ActionCoin},
+        query::{DebtAmount, LiquidationPreview, LiquidationRequest, SimulateActionsResponse},
+    },
+    traits::Denoms,
+};
+
+use crate::{
+    borrow::debt_shares_to_add,
+    cache::QueryCache,
+    close_account::{assert_balance_covers_debt, debt_swaps, lp_token_balances},
+    deleverage::deleverage_amounts,
+    health::{
+        assert_health_change_allowed, compute_health_from_positions, compute_health_with_cache,
+    },
+    leverage::leverage_borrow_amount,
+    liquidate_coin::preview_liquidation,
+    query::query_positions,
+    repay::repay_amounts,
+    state::{SWAPPER, ZAPPER},
+    utils::{assert_coin_is_whitelisted, assert_coins_are_whitelisted},
+    vault::{
+        assert_can_add_unlocking_position, assert_denom_matches_vault_reqs,
+        assert_deposit_is_under_cap, assert_vault_is_whitelisted,
+        assert_vault_positions_within_max, lp_underlying_denoms,
+    },
+};
+
+/// Applies actions to an in-memory copy of the account's positions, mirroring the validations
+/// and balance changes each action would make when dispatched through `UpdateCreditAccount`.
+/// Validations and amount sizing are shared with the execute handlers; only the state changes
+/// are applied to the in-memory positions instead of storage. Coins received from the swapper,
+/// zapper and vaults are their estimates, which the swapper only returns for swaps that pass its
+/// oracle check.
+pub fn simulate_actions(
+    deps: Deps,
+    env: &Env,
+    account_id: &str,
+    actions: &[Action],
+) -> ContractResult<SimulateActionsResponse> {
+    let mut cache = QueryCache::default();
+    let prev_health = compute_health_with_cache(deps, env, &mut cache, account_id)?;
+
+    let mut positions = query_positions(deps, env, account_id)?;
+    let mut deposits = Coins::try_from(positions.deposits.clone())?;
+    let prev_vault_positions = positions.vaults.len();
+
+    for action in actions {
+        match action {
+            Action::Deposit(coin) => {
+                assert_coin_is_whitelisted(deps.storage, &coin.denom)?;
+                increment(&mut deposits, coin)?;
+            }
+            Action::Withdraw(coin) => {
+                if coin.amount.is_zero() {
+                    return Err(ContractError::NoAmount);
+                }
+                deposits.deduct(coin)?;
+            }
+            Action::Borrow(coin) => {
+                borrow(deps, env, &mut positions.debts, &mut deposits, coin)?;
+            }
+            Action::Repay(coin) => {
+                repay(deps, env, &mut positions.debts, &mut deposits, coin)?;
+            }
+            Action::EnterVault {
+                vault,
+                coin,
+                min_receive,
+            } => {
+                let vault = vault.check(deps.api)?;
+                let coin_to_enter = Coin {
+                    denom: coin.denom.clone(),
+                    amount: match coin.amount {
+                        ActionAmount::Exact(a) => a,
+                        ActionAmount::AccountBalance => {
+                            deposits.amount(&coin.denom).unwrap_or_default()
+                        }
+                    },
+                };
+                enter_vault(
+                    deps,
+                    env,
+                    &mut positions.vaults,
+                    &mut deposits,
+                    &vault,
+                    &coin_to_enter,
+                    *min_receive,
+                )?;
+            }
+            Action::ExitVault {
+                vault,
+                amount,
+                min_receive,
+            } => {
+                let vault = vault.check(deps.api)?;
+                let underlying =
+                    exit_vault(deps, &mut positions.vaults, &mut deposits, &vault, *amount)?;
+                assert_estimate_above_min(underlying.amount, &underlying.denom, *min_receive)?;
+            }
+            Action::RequestVaultUnlock {
+                vault,
+                amount,
+            } => {
+                let vault = vault.check(deps.api)?;
+                assert_vault_is_whitelisted(deps.storage, &vault)?;
+                vault.query_lockup_duration(&deps.querier).map_err(|_| {
+                    ContractError::RequirementsNotMet(
+                        "This vault does not require lockup. Call withdraw directly.".to_string(),
+                    )
+                })?;
+                let unlocking_positions = positions
+                    .vaults
+                    .iter()
+                    .find(|p| p.vault == vault)
+                    .map(|p| p.amount.unlocking().positions().len())
+                    .unwrap_or_default();
+                assert_can_add_unlocking_position(deps.storage, unlocking_positions)?;
+
+                update_vault_position(
+                    &mut positions.vaults,
+                    &vault,
+                    VaultPositionUpdate::Locked(UpdateType::Decrement(*amount)),
+                )?;
+
+                // The vault assigns the position id on execution, so a placeholder is used here
+                let info = vault.query_info(&deps.querier)?;
+                let underlying = vault.query_preview_redeem(&deps.querier, *amount)?;
+                update_vault_position(
+                    &mut positions.vaults,
+                    &vault,
+                    VaultPositionUpdate::Unlocking(UnlockingChange::Add(VaultUnlockingPosition {
+                        id: 0,
+                        coin: Coin {
+                            denom: info.base_token,
+                            amount: underlying,
+                        },
+                    })),
+                )?;
+            }
+            Action::CancelVaultUnlock {
+                vault,
+                id,
+            } => {
+                let vault = vault.check(deps.api)?;
+                assert_vault_is_whitelisted(deps.storage, &vault)?;
+
+                let matching_unlock = positions
+                    .vaults
+                    .iter()
+                    .find(|p| p.vault == vault)
+                    .and_then(|p| p.amount.get_unlocking_position(*id))
+                    .ok_or_else(|| ContractError::NoPositionMatch(id.to_string()))?;
+
+                update_vault_position(
+                    &mut positions.vaults,
+                    &vault,
+                    VaultPositionUpdate::Unlocking(UnlockingChange::Decrement {
+                        id: *id,
+                        amount: matching_unlock.coin.amount,
+                    }),
+                )?;
+                let vault_coins =
+                    vault.query_preview_deposit(&deps.querier, matching_unlock.coin.amount)?;
+                update_vault_position(
+                    &mut positions.vaults,
+                    &vault,
+                    VaultPositionUpdate::Locked(UpdateType::Increment(vault_coins)),
+                )?;
+            }
+            Action::ExitVaultUnlocked {
+                id,
+                vault,
+                min_receive,
+            } => {
+                let vault = vault.check(deps.api)?;
+                assert_vault_is_whitelisted(deps.storage, &vault)?;
+
+                let matching_unlock = positions
+                    .vaults
+                    .iter()
+                    .find(|p| p.vault == vault)
+                    .and_then(|p| p.amount.get_unlocking_position(*id))
+                    .ok_or_else(|| ContractError::NoPositionMatch(id.to_string()))?;
+                let unlocking_position = vault.query_unlocking_position(&deps.querier, *id)?;
+                if !unlocking_position.release_at.is_expired(&env.block) {
+                    return Err(ContractError::UnlockNotReady {});
+                }
+
+                update_vault_position(
+                    &mut positions.vaults,
+                    &vault,
+                    VaultPositionUpdate::Unlocking(UnlockingChange::Decrement {
+                        id: *id,
+                        amount: matching_unlock.coin.amount,
+                    }),
+                )?;
+                assert_estimate_above_min(
+                    matching_unlock.coin.amount,
+                    &matching_unlock.coin.denom,
+                    *min_receive,
+                )?;
+                increment(&mut deposits, &matching_unlock.coin)?;
+            }
+            Action::SwapExactIn {
+                coin_in,
+                denom_out,
+                min_receive,
+                ..
+            } => {
+                let coin_in_to_trade = Coin {
+                    denom: coin_in.denom.clone(),
+                    amount: match coin_in.amount {
+                        ActionAmount::Exact(a) => a,
+                        ActionAmount::AccountBalance => {
+                            deposits.amount(&coin_in.denom).unwrap_or_default()
+                        }
+                    },
+                };
+                let amount_out = swap_exact_in(deps, &mut deposits, &coin_in_to_trade, denom_out)?;
+                assert_estimate_above_min(amount_out, denom_out, *min_receive)?;
+            }
+            Action::SwapExactOut {
+                coin_out,
+                denom_in,
+                max_in,
+            } => {
+                assert_coin_is_whitelisted(deps.storage, &coin_out.denom)?;
+                if coin_out.amount.is_zero() || max_in.is_zero() {
+                    return Err(ContractError::NoAmount);
+                }
+
+                let swapper = SWAPPER.load(deps.storage)?;
+                let amount_in =
+                    swapper.estimate_exact_out_swap(&deps.querier, coin_out, denom_in)?;
+                if amount_in > *max_in {
+                    return Err(ContractError::RequirementsNotMet(format!(
+                        "Estimated {amount_in} {denom_in} is above max in of {max_in}"
+                    )));
+                }
+
+                deposits.deduct(&Coin {
+                    denom: denom_in.clone(),
+                    amount: amount_in,
+                })?;
+                increment(&mut deposits, coin_out)?;
+            }
+            Action::ProvideLiquidity {
+                coins_in,
+                lp_token_out,
+                minimum_receive,
+            } => {
+                let coins_to_provide = coins_in
+                    .iter()
+                    .map(|c| to_coin(&deposits, c))
+                    .collect::<ContractResult<Vec<_>>>()?;
+                let lp_amount =
+                    provide_liquidity(deps, &mut deposits, coins_to_provide, lp_token_out)?;
+                if lp_amount < *minimum_receive {
+                    return Err(ContractError::RequirementsNotMet(format!(
+                        "Estimated {lp_amount} {lp_token_out} is below minimum receive of {minimum_receive}"
+                    )));
+                }
+            }
+            Action::WithdrawLiquidity {
+                lp_token,
+                min_receive,
+            } => {
+                let lp_token = Coin {
+                    denom: lp_token.denom.clone(),
+                    amount: match lp_token.amount {
+                        ActionAmount::Exact(a) => a,
+                        ActionAmount::AccountBalance => {
+                            deposits.amount(&lp_token.denom).unwrap_or_default()
+                        }
+                    },
+                };
+                let coins_out = withdraw_liquidity(deps, &mut deposits, &lp_token)?;
+
+                for min in min_receive.iter().flatten() {
+                    let amount_out = coins_out
+                        .iter()
+                        .find(|c| c.denom == min.denom)
+                        .map(|c| c.amount)
+                        .unwrap_or_default();
+                    assert_estimate_above_min(amount_out, &min.denom, Some(min.amount))?;
+                }
+            }
+            Action::RefundAllCoinBalances {} => deposits = Coins::default(),
+            Action::CloseAccount {
+                target_denom,
+                slippage,
+            } => {
+                if positions
+                    .vaults
+                    .iter()
+                    .any(|p| !p.amount.locked().is_zero() || !p.amount.unlocking().is_empty())
+                {
+                    return Err(ContractError::CloseAccountVaultLocked);
+                }
+                for position in positions.vaults.clone() {
+                    exit_vault(
+                        deps,
+                        &mut positions.vaults,
+                        &mut deposits,
+                        &position.vault,
+                        position.amount.unlocked(),
+                    )?;
+                }
+
+                for coin in lp_token_balances(deps, deposits.to_vec())? {
+                    withdraw_liquidity(deps, &mut deposits, &coin)?;
+                }
+
+                positions.deposits = deposits.to_vec();
+                for (coin_in, denom_out) in debt_swaps(deps, &positions, target_denom, *slippage)? {
+                    swap_exact_in(deps, &mut deposits, &coin_in, &denom_out)?;
+                }
+
+                for debt in positions.debts.clone() {
+                    let balance = deposits.amount(&debt.denom).unwrap_or_default();
+                    assert_balance_covers_debt(&debt.denom, balance, debt.amount)?;
+                    repay(
+                        deps,
+                        env,
+                        &mut positions.debts,
+                        &mut deposits,
+                        &ActionCoin {
+                            denom: debt.denom,
+                            amount: ActionAmount::AccountBalance,
+                        },
+                    )?;
+                }
+
+                deposits = Coins::default();
+            }
+            Action::Deleverage {
+                target_max_ltv_health_factor,
+                collateral_denom,
+                debt_denom,
+                slippage,
+            } => {
+                positions.deposits = deposits.to_vec();
+                let (coin_in, repay_amount) = deleverage_amounts(
+                    deps,
+                    &mut cache,
+                    &positions,
+                    *target_max_ltv_health_factor,
+                    collateral_denom,
+                    debt_denom,
+                    *slippage,
+                )?;
+                swap_exact_in(deps, &mut deposits, &coin_in, debt_denom)?;
+                repay(
+                    deps,
+                    env,
+                    &mut positions.debts,
+                    &mut deposits,
+                    &ActionCoin::from(&Coin {
+                        denom: debt_denom.clone(),
+                        amount: repay_amount,
+                    }),
+                )?;
+            }
+            Action::Leverage {
+                collateral_denom,
+                borrow_denom,
+                target_leverage,
+                slippage,
+            } => {
+                positions.deposits = deposits.to_vec();
+                let health = compute_health_from_positions(deps, &mut cache, &positions)?;
+                let borrow_coin = leverage_borrow_amount(
+                    deps,
+                    &mut cache,
+                    &health,
+                    collateral_denom,
+                    borrow_denom,
+                    *target_leverage,
+                    *slippage,
+                )?;
+                borrow(deps, env, &mut positions.debts, &mut deposits, &borrow_coin)?;
+                swap_exact_in(deps, &mut deposits, &borrow_coin, collateral_denom)?;
+            }
+            Action::EnterVaultWithZap {
+                vault,
+                coins_in,
+                min_vault_tokens,
+                ..
+            } => {
+                let vault = vault.check(deps.api)?;
+                assert_vault_is_whitelisted(deps.storage, &vault)?;
+                assert_coins_are_whitelisted(deps.storage, coins_in.to_denoms())?;
+
+                let coins_in = coins_in
+                    .iter()
+                    .map(|coin| Coin {
+                        denom: coin.denom.clone(),
+                        amount: match coin.amount {
+                            ActionAmount::Exact(a) => a,
+                            ActionAmount::AccountBalance => {
+                                deposits.amount(&coin.denom).unwrap_or_default()
+                            }
+                        },
+                    })
+                    .collect::<Vec<_>>();
+                if coins_in.is_empty() || coins_in.iter().any(|c| c.amount.is_zero()) {
+                    return Err(ContractError::NoAmount);
+                }
+
+                let base_token = vault.query_info(&deps.querier)?.base_token;
+                let underlying = lp_underlying_denoms(deps.storage, &vault)?;
+                let zap_denoms = iter::once(&base_token).chain(&underlying).collect::<Vec<_>>();
+
+                // Balances of the zap denoms that are not part of the zap stay in the account
+                let retained = zap_denoms
+                    .iter()
+                    .map(|denom| {
+                        let zapped: Uint128 =
+                            coins_in.iter().filter(|c| c.denom == **denom).map(|c| c.amount).sum();
+                        Ok(Coin {
+                            denom: denom.to_string(),
+                            amount: deposits
+                                .amount(denom)
+                                .unwrap_or_default()
+                                .checked_sub(zapped)?,
+                        })
+                    })
+                    .collect::<ContractResult<Vec<_>>>()?;
+
+                let swap_denom_out = underlying.first().unwrap_or(&base_token);
+                for coin in coins_in.iter().filter(|c| !zap_denoms.contains(&&c.denom)) {
+                    swap_exact_in(deps, &mut deposits, coin, swap_denom_out)?;
+                }
+
+                let zapped_underlying = retained
+                    .iter()
+                    .filter(|c| c.denom != base_token)
+                    .map(|c| {
+                        Ok(Coin {
+                            denom: c.denom.clone(),
+                            amount: deposits
+                                .amount(&c.denom)
+                                .unwrap_or_default()
+                                .checked_sub(c.amount)?,
+                        })
+                    })
+                    .collect::<ContractResult<Vec<_>>>()?
+                    .into_iter()
+                    .filter(|c| !c.amount.is_zero())
+                    .collect::<Vec<_>>();
+                if !zapped_underlying.is_empty() {
+                    provide_liquidity(deps, &mut deposits, zapped_underlying, &base_token)?;
+                }
+
+                let base_retained = retained
+                    .iter()
+                    .find(|c| c.denom == base_token)
+                    .map(|c| c.amount)
+                    .unwrap_or_default();
+                let base_zapped = Coin {
+                    denom: base_token.clone(),
+                    amount: deposits
+                        .amount(&base_token)
+                        .unwrap_or_default()
+                        .checked_sub(base_retained)?,
+                };
+                if base_zapped.amount.is_zero() {
+                    return Err(ContractError::NoAmount);
+                }
+                enter_vault(
+                    deps,
+                    env,
+                    &mut positions.vaults,
+                    &mut deposits,
+                    &vault,
+                    &base_zapped,
+                    Some(*min_vault_tokens),
+                )?;
+            }
+            Action::ExitVaultInto {
+                vault,
+                amount,
+                denom_out,
+                ..
+            } => {
+                assert_coin_is_whitelisted(deps.storage, denom_out)?;
+                let vault = vault.check(deps.api)?;
+                let withdrawn =
+                    exit_vault(deps, &mut positions.vaults, &mut deposits, &vault, *amount)?;
+
+                if !withdrawn.amount.is_zero() && withdrawn.denom != *denom_out {
+                    if lp_underlying_denoms(deps.storage, &vault)?.is_empty() {
+                        swap_exact_in(deps, &mut deposits, &withdrawn, denom_out)?;
+                    } else {
+                        for coin in withdraw_liquidity(deps, &mut deposits, &withdrawn)? {
+                            if coin.denom != *denom_out {
+                                swap_exact_in(deps, &mut deposits, &coin, denom_out)?;
+                            }
+                        }
+                    }
+                }
+            }
+            Action::LiquidateCoin {
+                liquidatee_account_id,
+                debt_coin,
+                request_coin_denom,
+            } => {
+                let LiquidationPreview {
+                    debt,
+                    request,
+                    ..
+                } = preview_liquidation(
+                    deps,
+                    env,
+                    liquidatee_account_id,
+                    debt_coin,
+                    &LiquidationRequest::Coin(request_coin_denom.clone()),
+                )?;
+                deposits.deduct(&debt)?;
+                increment(&mut deposits, &request)?;
+            }
+            Action::LiquidateVault {
+                liquidatee_account_id,
+                debt_coin,
+                request_vault,
+                position_type,
+            } => {
+                let LiquidationPreview {
+                    debt,
+                    request,
+                    ..
+                } = preview_liquidation(
+                    deps,
+                    env,
+                    liquidatee_account_id,
+                    debt_coin,
+                    &LiquidationRequest::Vault {
+                        request_vault: request_vault.clone(),
+                        position_type: position_type.clone(),
+                    },
+                )?;
+                deposits.deduct(&debt)?;
+
+                // Vault coins requested are withdrawn, so the liquidator receives the base token
+                let vault = request_vault.check(deps.api)?;
+                let withdrawn = match position_type {
+                    VaultPositionType::UNLOCKING => request,
+                    _ => Coin {
+                        denom: vault.query_info(&deps.querier)?.base_token,
+                        amount: vault.query_preview_redeem(&deps.querier, request.amount)?,
+                    },
+                };
+                increment(&mut deposits, &withdrawn)?;
+            }
+        }
+    }
+
+    assert_vault_positions_within_max(
+        deps.storage,
+        Uint128::from(positions.vaults.len() as u128),
+        Uint128::from(prev_vault_positions as u128),
+    )?;
+
+    positions.deposits = deposits.to_vec();
+    positions.vaults.sort_by(|a, b| a.vault.address.cmp(&b.vault.address));
+
+    let new_health = compute_health_from_positions(deps, &mut cache, &positions)?;
+    let passes_max_ltv_check =
+        assert_health_change_allowed(account_id, &prev_health, &new_health).is_ok();
+
+    Ok(SimulateActionsResponse {
+        positions,
+        health: new_health.into(),
+        passes_max_ltv_check,
+    })
+}
+
+fn borrow(
+    deps: Deps,
+    env: &Env,
+    debts: &mut Vec<DebtAmount>,
+    deposits: &mut Coins,
+    coin: &Coin,
+) -> ContractResult<()> {
+    if coin.amount.is_zero() {
+        return Err(ContractError::NoAmount);
+    }
+    assert_coin_is_whitelisted(deps.storage, &coin.denom)?;
+    let shares = debt_shares_to_add(deps, &env.contract.address, coin)?;
+    increment_debt(debts, coin, shares)?;
+    increment(deposits, coin)
+}
+
+fn repay(
+    deps: Deps,
+    env: &Env,
+    debts: &mut Vec<DebtAmount>,
+    deposits: &mut Coins,
+    coin: &ActionCoin,
+) -> ContractResult<()> {
+    let index = debts.iter().position(|d| d.denom == coin.denom).ok_or(ContractError::NoDebt)?;
+    let (coin_to_repay, shares_to_repay) = repay_amounts(deps, env, debts[index].amount, coin)?;
+
+    // A full repayment clears all of the account's debt shares
+    if coin_to_repay.amount == debts[index].amount {
+        debts.remove(index);
+    } else {
+        let debt = &mut debts[index];
+        debt.amount = debt.amount.checked_sub(coin_to_repay.amount)?;
+        debt.shares = debt.shares.checked_sub(shares_to_repay)?;
+    }
+    deposits.deduct(&coin_to_repay)?;
+    Ok(())
+}
+
+/// Returns the estimated amount of `denom_out` received
+fn swap_exact_in(
+    deps: Deps,
+    deposits: &mut Coins,
+    coin_in: &Coin,
+    denom_out: &str,
+) -> ContractResult<Uint128> {
+    assert_coin_is_whitelisted(deps.storage, denom_out)?;
+    if coin_in.amount.is_zero() {
+        return Err(ContractError::NoAmount);
+    }
+
+    deposits.deduct(coin_in)?;
+
+    let swapper = SWAPPER.load(deps.storage)?;
+    let amount_out = swapper.estimate_exact_in_swap(&deps.querier, coin_in, denom_out)?;
+    increment(
+        deposits,
+        &Coin {
+            denom: denom_out.to_string(),
+            amount: amount_out,
+        },
+    )?;
+    Ok(amount_out)
+}
+
+fn enter_vault(
+    deps: Deps,
+    env: &Env,
+    vaults: &mut Vec<VaultPosition>,
+    deposits: &mut Coins,
+    vault: &Vault,
+    coin: &Coin,
+    min_receive: Option<Uint128>,
+) -> ContractResult<()> {
+    assert_coin_is_whitelisted(deps.storage, &coin.denom)?;
+    assert_vault_is_whitelisted(deps.storage, vault)?;
+    assert_denom_matches_vault_reqs(deps.querier, vault, coin)?;
+    assert_deposit_is_under_cap(deps, vault, coin, &env.contract.address)?;
+
+    deposits.deduct(coin)?;
+
+    let vault_coins = vault.query_preview_deposit(&deps.querier, coin.amount)?;
+    let vault_token = vault.query_info(&deps.querier)?.vault_token;
+    assert_estimate_above_min(vault_coins, &vault_token, min_receive)?;
+    let update = match vault.query_lockup_duration(&deps.querier) {
+        Ok(_) => VaultPositionUpdate::Locked(UpdateType::Increment(vault_coins)),
+        Err(_) => VaultPositionUpdate::Unlocked(UpdateType::Increment(vault_coins)),
+    };
+    update_vault_position(vaults, vault, update)
+}
+
+/// Returns the estimated base token withdrawn
+fn exit_vault(
+    deps: Deps,
+    vaults: &mut Vec<VaultPosition>,
+    deposits: &mut Coins,
+    vault: &Vault,
+    amount: Uint128,
+) -> ContractResult<Coin> {
+    assert_vault_is_whitelisted(deps.storage, vault)?;
+    update_vault_position(
+        vaults,
+        vault,
+        VaultPositionUpdate::Unlocked(UpdateType::Decrement(amount)),
+    )?;
+    let underlying = Coin {
+        denom: vault.query_info(&deps.querier)?.base_token,
+        amount: vault.query_preview_redeem(&deps.querier, amount)?,
+    };
+    increment(deposits, &underlying)?;
+    Ok(underlying)
+}
+
+/// Returns the estimated amount of `lp_token_out` received
+fn provide_liquidity(
+    deps: Deps,
+    deposits: &mut Coins,
+    coins_in: Vec<Coin>,
+    lp_token_out: &str,
+) -> ContractResult<Uint128> {
+    assert_coin_is_whitelisted(deps.storage, lp_token_out)?;
+    assert_coins_are_whitelisted(deps.storage, coins_in.to_denoms())?;
+
+    for coin in &coins_in {
+        deposits.deduct(coin)?;
+    }
+
+    let zapper = ZAPPER.load(deps.storage)?;
+    let lp_amount = zapper.estimate_provide_liquidity(&deps.querier, lp_token_out, &coins_in)?;
+    increment(
+        deposits,
+        &Coin {
+            denom: lp_token_out.to_string(),
+            amount: lp_amount,
+        },
+    )?;
+    Ok(lp_amount)
+}
+
+/// Returns the estimated coins withdrawn
+fn withdraw_liquidity(
+    deps: Deps,
+    deposits: &mut Coins,
+    lp_token: &Coin,
+) -> ContractResult<Vec<Coin>> {
+    assert_coin_is_whitelisted(deps.storage, &lp_token.denom)?;
+    if lp_token.amount.is_zero() {
+        return Err(ContractError::NoAmount);
+    }
+
+    let zapper = ZAPPER.load(deps.storage)?;
+    let coins_out = zapper.estimate_withdraw_liquidity(&deps.querier, lp_token)?;
+    assert_coins_are_whitelisted(deps.storage, coins_out.to_denoms())?;
+
+    deposits.deduct(lp_token)?;
+    for coin in &coins_out {
+        increment(deposits, coin)?;
+    }
+    Ok(coins_out)
+}
+
+fn assert_estimate_above_min(
+    amount_out: Uint128,
+    denom_out: &str,
+    min_receive: Option<Uint128>,
+) -> ContractResult<()> {
+    if let Some(min_receive) = min_receive {
+        if amount_out < min_receive {
+            return Err(ContractError::RequirementsNotMet(format!(
+                "Estimated {amount_out} {denom_out} is below minimum receive of {min_receive}"
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn increment(deposits: &mut Coins, coin: &Coin) -> ContractResult<()> {
+    if !coin.amount.is_zero() {
+        deposits.add(coin)?;
+    }
+    Ok(())
+}
+
+fn to_coin(deposits: &Coins, coin: &ActionCoin) -> ContractResult<Coin> {
+    let balance = deposits.amount(&coin.denom).ok_or_else(|| {
+        ContractError::RequirementsNotMet(format!("No balance of {}", coin.denom))
+    })?;
+    Ok(Coin {
+        denom: coin.denom.clone(),
+        amount: match coin.amount {
+            ActionAmount::Exact(a) => a,
+            ActionAmount::AccountBalance => balance,
+        },
+    })
+}
+
+fn increment_debt(debts: &mut Vec<DebtAmount>, coin: &Coin, shares: Uint128) -> ContractResult<()> {
+    match debts.iter_mut().find(|d| d.denom == coin.denom) {
+        Some(debt) => {
+            debt.amount = debt.amount.checked_add(coin.amount)?;
+            debt.shares = debt.shares.checked_add(shares)?;
+        }
+        None => {
+            debts.push(DebtAmount {
+                denom: coin.denom.clone(),
+                shares,
+                amount: coin.amount,
+            });
+            debts.sort_by(|a, b| a.denom.cmp(&b.denom));
+        }
+    }
+    Ok(())
+}
+
+fn update_vault_position(
+    vaults: &mut Vec<VaultPosition>,
+    vault: &Vault,
+    update: VaultPositionUpdate,
+) -> ContractResult<()> {
+    let index = vaults.iter().position(|p| p.vault == *vault);
+    let mut amount = match index {
+        Some(i) => vaults[i].amount.clone(),
+        None => update.default_amount(),
+    };
+
+    amount.update(update)?;
+
+    match index {
+        Some(i) if amount.is_empty() => {
+            vaults.remove(i);
+        }
+        Some(i) => vaults[i].amount = amount,
+        None if amount.is_empty() => {}
+        None => vaults.push(VaultPosition {
+            vault: vault.clone(),
+            amount,
+        }),
+    }
+    Ok(())
+}
@@ -26,10 +26,12 @@ pub const OWNER: Owner = Owner::new("owner");
 pub const ALLOWED_COINS: Set<&str> = Set::new("allowed_coins");
 pub const MAX_CLOSE_FACTOR: Item<Decimal> = Item::new("max_close_factor");
 pub const MAX_UNLOCKING_POSITIONS: Item<Uint128> = Item::new("max_unlocking_positions");
+pub const MAX_VAULT_POSITIONS: Item<Uint128> = Item::new("max_vault_positions");
 
 // Positions
 pub const COIN_BALANCES: Map<(&str, &str), Uint128> = Map::new("coin_balance"); // Map<(AccountId, Denom), Amount>
 pub const DEBT_SHARES: Map<(&str, &str), Uint128> = Map::new("debt_shares"); // Map<(AccountId, Denom), Shares>
+pub const ACCOUNTS_WITH_DEBT: Set<&str> = Set::new("accounts_with_debt"); // Index of account ids with DEBT_SHARES
 pub const TOTAL_DEBT_SHARES: Map<&str, Uint128> = Map::new("total_debt_shares"); // Map<Denom, Shares>
 pub const VAULT_POSITIONS: Map<(&str, Addr), VaultPositionAmount> = Map::new("vault_positions"); // Map<(AccountId, VaultAddr), VaultPositionAmount>
 
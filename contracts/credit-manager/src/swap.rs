@@ -6,7 +6,10 @@ use mars_rover::{
 
 use crate::{
     state::{COIN_BALANCES, SWAPPER},
-    utils::{assert_coin_is_whitelisted, decrement_coin_balance, update_balance_msg},
+    utils::{
+        assert_coin_is_whitelisted, decrement_coin_balance, update_balance_with_min_receive_msg,
+        update_balances_msgs,
+    },
 };
 
 pub fn swap_exact_in(
@@ -16,6 +19,7 @@ pub fn swap_exact_in(
     coin_in: &ActionCoin,
     denom_out: &str,
     slippage: Decimal,
+    min_receive: Option<Uint128>,
 ) -> ContractResult<Response> {
     assert_coin_is_whitelisted(deps.storage, denom_out)?;
 
@@ -36,8 +40,13 @@ pub fn swap_exact_in(
     decrement_coin_balance(deps.storage, account_id, &coin_in_to_trade)?;
 
     // Updates coin balances for account after the swap has taken place
-    let update_coin_balance_msg =
-        update_balance_msg(&deps.querier, &env.contract.address, account_id, denom_out)?;
+    let update_coin_balance_msg = update_balance_with_min_receive_msg(
+        &deps.querier,
+        &env.contract.address,
+        account_id,
+        denom_out,
+        min_receive,
+    )?;
 
     let swapper = SWAPPER.load(deps.storage)?;
 
@@ -49,3 +58,44 @@ pub fn swap_exact_in(
         .add_attribute("coin_in", coin_in_to_trade.to_string())
         .add_attribute("denom_out", denom_out))
 }
+
+pub fn swap_exact_out(
+    deps: DepsMut,
+    env: Env,
+    account_id: &str,
+    coin_out: &Coin,
+    denom_in: &str,
+    max_in: Uint128,
+) -> ContractResult<Response> {
+    assert_coin_is_whitelisted(deps.storage, &coin_out.denom)?;
+
+    if coin_out.amount.is_zero() || max_in.is_zero() {
+        return Err(ContractError::NoAmount);
+    }
+
+    // Account must hold all of max in, though only the amount spent is deducted
+    COIN_BALANCES
+        .may_load(deps.storage, (account_id, denom_in))?
+        .unwrap_or(Uint128::zero())
+        .checked_sub(max_in)?;
+
+    // The amount spent is only known after the swap. Both coin balances are updated from the
+    // change in Rover's balances, which accounts for the refund of any unspent input.
+    let update_coin_balance_msgs = update_balances_msgs(
+        &deps.querier,
+        &env.contract.address,
+        account_id,
+        vec![&coin_out.denom, denom_in],
+    )?;
+
+    let swapper = SWAPPER.load(deps.storage)?;
+
+    Ok(Response::new()
+        .add_message(swapper.swap_exact_out_msg(coin_out, denom_in, max_in)?)
+        .add_messages(update_coin_balance_msgs)
+        .add_attribute("action", "swapper/exact_out")
+        .add_attribute("account_id", account_id)
+        .add_attribute("coin_out", coin_out.to_string())
+        .add_attribute("denom_in", denom_in)
+        .add_attribute("max_in", max_in))
+}
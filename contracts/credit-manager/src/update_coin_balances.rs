@@ -1,8 +1,8 @@
 use cosmwasm_std::{
     Addr, BalanceResponse, BankQuery, Coin, DepsMut, Env, QuerierWrapper, QueryRequest, Response,
-    StdResult,
+    StdResult, Uint128,
 };
-use mars_rover::error::ContractResult;
+use mars_rover::error::{ContractError, ContractResult};
 
 use crate::utils::{decrement_coin_balance, increment_coin_balance};
 
@@ -22,8 +22,21 @@ pub fn update_coin_balance(
     env: Env,
     account_id: &str,
     prev: &Coin,
+    min_receive: Option<Uint128>,
 ) -> ContractResult<Response> {
     let curr = query_balance(&deps.querier, &env.contract.address, &prev.denom)?;
+
+    if let Some(min_receive) = min_receive {
+        let received = curr.amount.saturating_sub(prev.amount);
+        if received < min_receive {
+            return Err(ContractError::ReceivedBelowMinimum {
+                denom: curr.denom,
+                received,
+                min_receive,
+            });
+        }
+    }
+
     if prev.amount > curr.amount {
         let amount_to_reduce = prev.amount.checked_sub(curr.amount)?;
         let coin_to_reduce = Coin {
@@ -10,8 +10,8 @@ use mars_rover::{
 use crate::{
     instantiate::{assert_lte_to_one, assert_no_duplicate_coins, assert_no_duplicate_vaults},
     state::{
-        ACCOUNT_NFT, ALLOWED_COINS, MAX_CLOSE_FACTOR, MAX_UNLOCKING_POSITIONS, ORACLE, OWNER,
-        RED_BANK, SWAPPER, VAULT_CONFIGS, ZAPPER,
+        ACCOUNT_NFT, ALLOWED_COINS, MAX_CLOSE_FACTOR, MAX_UNLOCKING_POSITIONS, MAX_VAULT_POSITIONS,
+        ORACLE, OWNER, RED_BANK, SWAPPER, VAULT_CONFIGS, ZAPPER,
     },
 };
 
@@ -103,6 +103,13 @@ pub fn update_config(
             .add_attribute("value", num.to_string());
     }
 
+    if let Some(num) = updates.max_vault_positions {
+        MAX_VAULT_POSITIONS.save(deps.storage, &num)?;
+        response = response
+            .add_attribute("key", "max_vault_positions")
+            .add_attribute("value", num.to_string());
+    }
+
     Ok(response)
 }
 
@@ -42,7 +42,7 @@ pub fn query_nft_token_owner(deps: Deps, account_id: &str) -> ContractResult<Str
     Ok(res.owner)
 }
 
-pub fn assert_coin_is_whitelisted(storage: &mut dyn Storage, denom: &str) -> ContractResult<()> {
+pub fn assert_coin_is_whitelisted(storage: &dyn Storage, denom: &str) -> ContractResult<()> {
     let is_whitelisted = ALLOWED_COINS.contains(storage, denom);
     if !is_whitelisted {
         return Err(ContractError::NotWhitelisted(denom.to_string()));
@@ -51,7 +51,7 @@ pub fn assert_coin_is_whitelisted(storage: &mut dyn Storage, denom: &str) -> Con
 }
 
 pub fn assert_coins_are_whitelisted(
-    storage: &mut dyn Storage,
+    storage: &dyn Storage,
     denoms: Vec<&str>,
 ) -> ContractResult<()> {
     denoms.iter().try_for_each(|denom| assert_coin_is_whitelisted(storage, denom))
@@ -91,6 +91,17 @@ pub fn update_balance_msg(
     rover_addr: &Addr,
     account_id: &str,
     denom: &str,
+) -> StdResult<CosmosMsg> {
+    update_balance_with_min_receive_msg(querier, rover_addr, account_id, denom, None)
+}
+
+/// Same as `update_balance_msg`, but fails if less than `min_receive` is credited to the account
+pub fn update_balance_with_min_receive_msg(
+    querier: &QuerierWrapper,
+    rover_addr: &Addr,
+    account_id: &str,
+    denom: &str,
+    min_receive: Option<Uint128>,
 ) -> StdResult<CosmosMsg> {
     let previous_balance = query_balance(querier, rover_addr, denom)?;
     Ok(CosmosMsg::Wasm(WasmMsg::Execute {
@@ -99,6 +110,7 @@ pub fn update_balance_msg(
         msg: to_binary(&ExecuteMsg::Callback(CallbackMsg::UpdateCoinBalance {
             account_id: account_id.to_string(),
             previous_balance,
+            min_receive,
         }))?,
     }))
 }
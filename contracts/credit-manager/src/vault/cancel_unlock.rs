@@ -0,0 +1,54 @@
+use cosmwasm_std::{Addr, DepsMut, Response};
+use mars_rover::{
+    adapters::vault::{UnlockingChange, Vault, VaultPositionUpdate},
+    error::{ContractError, ContractResult},
+    msg::execute::CallbackMsg,
+};
+
+use crate::{
+    state::VAULT_POSITIONS,
+    vault::utils::{assert_vault_is_whitelisted, update_vault_position},
+};
+
+pub fn cancel_vault_unlock(
+    deps: DepsMut,
+    rover_addr: &Addr,
+    account_id: &str,
+    vault: Vault,
+    position_id: u64,
+) -> ContractResult<Response> {
+    assert_vault_is_whitelisted(deps.storage, &vault)?;
+
+    let vault_position = VAULT_POSITIONS.load(deps.storage, (account_id, vault.address.clone()))?;
+    let matching_unlock = vault_position
+        .get_unlocking_position(position_id)
+        .ok_or_else(|| ContractError::NoPositionMatch(position_id.to_string()))?;
+
+    update_vault_position(
+        deps.storage,
+        account_id,
+        &vault.address,
+        VaultPositionUpdate::Unlocking(UnlockingChange::Decrement {
+            id: position_id,
+            amount: matching_unlock.coin.amount,
+        }),
+    )?;
+
+    // Vault coins returned for the cancelled position are added to the locked amount
+    let previous_total_balance = vault.query_balance(&deps.querier, rover_addr)?;
+    let update_vault_balance_msg = CallbackMsg::UpdateVaultCoinBalance {
+        vault: vault.clone(),
+        account_id: account_id.to_string(),
+        previous_total_balance,
+        min_receive: None,
+    }
+    .into_cosmos_msg(rover_addr)?;
+
+    Ok(Response::new()
+        .add_message(vault.cancel_unlock_msg(position_id)?)
+        .add_message(update_vault_balance_msg)
+        .add_attribute("action", "vault/cancel_unlock")
+        .add_attribute("account_id", account_id)
+        .add_attribute("vault_addr", vault.address.to_string())
+        .add_attribute("position_id", position_id.to_string()))
+}
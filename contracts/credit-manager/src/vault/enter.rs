@@ -1,5 +1,6 @@
 use cosmwasm_std::{
-    to_binary, Addr, Coin, CosmosMsg, Deps, DepsMut, QuerierWrapper, Response, Uint128, WasmMsg,
+    to_binary, Addr, Coin, CosmosMsg, Deps, DepsMut, QuerierWrapper, Response, Storage, Uint128,
+    WasmMsg,
 };
 use mars_rover::{
     adapters::vault::{UpdateType, Vault, VaultPositionUpdate},
@@ -11,12 +12,11 @@ use mars_rover::{
 };
 
 use crate::{
-    query::query_vault_positions,
-    state::{COIN_BALANCES, ORACLE, VAULT_CONFIGS},
+    state::{COIN_BALANCES, MAX_VAULT_POSITIONS, ORACLE, VAULT_CONFIGS},
     utils::{assert_coin_is_whitelisted, decrement_coin_balance},
     vault::{
         rover_vault_balance_value,
-        utils::{assert_vault_is_whitelisted, update_vault_position},
+        utils::{assert_vault_is_whitelisted, update_vault_position, vault_positions_count},
     },
 };
 
@@ -26,6 +26,7 @@ pub fn enter_vault(
     account_id: &str,
     vault: Vault,
     coin: &ActionCoin,
+    min_receive: Option<Uint128>,
 ) -> ContractResult<Response> {
     let amount = match coin.amount {
         ActionAmount::Exact(a) => a,
@@ -53,6 +54,7 @@ pub fn enter_vault(
             vault: vault.clone(),
             account_id: account_id.to_string(),
             previous_total_balance: current_balance,
+            min_receive,
         }))?,
     });
 
@@ -70,6 +72,7 @@ pub fn update_vault_coin_balance(
     vault: Vault,
     account_id: &str,
     previous_total_balance: Uint128,
+    min_receive: Option<Uint128>,
     rover_addr: &Addr,
 ) -> ContractResult<Response> {
     let current_balance = vault.query_balance(&deps.querier, rover_addr)?;
@@ -79,6 +82,15 @@ pub fn update_vault_coin_balance(
     }
 
     let diff = current_balance.checked_sub(previous_total_balance)?;
+    if let Some(min_receive) = min_receive {
+        if diff < min_receive {
+            return Err(ContractError::ReceivedBelowMinimum {
+                denom: vault.query_info(&deps.querier)?.vault_token,
+                received: diff,
+                min_receive,
+            });
+        }
+    }
     let duration = vault.query_lockup_duration(&deps.querier).ok();
 
     update_vault_position(
@@ -138,12 +150,31 @@ pub fn assert_deposit_is_under_cap(
     Ok(())
 }
 
-pub fn assert_only_one_vault_position(deps: DepsMut, account_id: &str) -> ContractResult<Response> {
-    let vaults = query_vault_positions(deps.as_ref(), account_id)?;
-    if vaults.len() > 1 {
-        return Err(ContractError::OnlyOneVaultPositionAllowed);
-    }
+pub fn assert_max_vault_positions(
+    deps: Deps,
+    account_id: &str,
+    prev_vault_positions: Uint128,
+) -> ContractResult<Response> {
+    let new_amount = vault_positions_count(deps.storage, account_id)?;
+    assert_vault_positions_within_max(deps.storage, new_amount, prev_vault_positions)?;
 
     Ok(Response::new()
-        .add_attribute("action", "rover/credit-manager/callback/assert_only_one_vault_position"))
+        .add_attribute("action", "rover/credit-manager/callback/assert_max_vault_positions"))
+}
+
+pub fn assert_vault_positions_within_max(
+    storage: &dyn Storage,
+    new_amount: Uint128,
+    prev_amount: Uint128,
+) -> ContractResult<()> {
+    let maximum = MAX_VAULT_POSITIONS.load(storage)?;
+
+    // Lowering the maximum should not lock accounts out of managing their existing positions
+    if new_amount > maximum && new_amount > prev_amount {
+        return Err(ContractError::ExceedsMaxVaultPositions {
+            new_amount,
+            maximum,
+        });
+    }
+    Ok(())
 }
@@ -0,0 +1,177 @@
+use std::iter;
+
+use cosmwasm_std::{Coin, Decimal, DepsMut, Env, Response, StdResult, Uint128};
+use mars_rover::{
+    adapters::vault::Vault,
+    error::{ContractError, ContractResult},
+    msg::execute::{ActionAmount, ActionCoin, CallbackMsg},
+    traits::{Denoms, Stringify},
+};
+
+use crate::{
+    state::COIN_BALANCES,
+    utils::assert_coins_are_whitelisted,
+    vault::{
+        enter_vault,
+        utils::{assert_vault_is_whitelisted, lp_underlying_denoms},
+    },
+};
+
+/// Coins that are neither the vault's base token nor, for LP base tokens, one of its underlying
+/// denoms are swapped into the first underlying denom, or the base token if it is not an LP token.
+/// The amounts received are only known once the swaps have executed, so depositing is left to
+/// `ZapIntoVault`.
+pub fn enter_vault_with_zap(
+    deps: DepsMut,
+    env: Env,
+    account_id: &str,
+    vault: Vault,
+    coins_in: Vec<ActionCoin>,
+    slippage: Decimal,
+    min_vault_tokens: Uint128,
+) -> ContractResult<Response> {
+    assert_vault_is_whitelisted(deps.storage, &vault)?;
+    assert_coins_are_whitelisted(deps.storage, coins_in.to_denoms())?;
+
+    let coins_in = coins_in
+        .iter()
+        .map(|coin| {
+            Ok(Coin {
+                denom: coin.denom.clone(),
+                amount: match coin.amount {
+                    ActionAmount::Exact(a) => a,
+                    ActionAmount::AccountBalance => COIN_BALANCES
+                        .may_load(deps.storage, (account_id, &coin.denom))?
+                        .unwrap_or_default(),
+                },
+            })
+        })
+        .collect::<ContractResult<Vec<_>>>()?;
+    if coins_in.is_empty() || coins_in.iter().any(|c| c.amount.is_zero()) {
+        return Err(ContractError::NoAmount);
+    }
+
+    let base_token = vault.query_info(&deps.querier)?.base_token;
+    let underlying = lp_underlying_denoms(deps.storage, &vault)?;
+    let zap_denoms = iter::once(&base_token).chain(&underlying).cloned().collect::<Vec<_>>();
+
+    // Balances of the zap denoms that are not part of the zap stay in the account
+    let retained = zap_denoms
+        .iter()
+        .map(|denom| {
+            let balance =
+                COIN_BALANCES.may_load(deps.storage, (account_id, denom))?.unwrap_or_default();
+            let zapped: Uint128 =
+                coins_in.iter().filter(|c| c.denom == *denom).map(|c| c.amount).sum();
+            Ok(Coin {
+                denom: denom.clone(),
+                amount: balance.checked_sub(zapped)?,
+            })
+        })
+        .collect::<ContractResult<Vec<_>>>()?;
+
+    let swap_denom_out = underlying.first().unwrap_or(&base_token);
+    let mut callbacks = coins_in
+        .iter()
+        .filter(|c| !zap_denoms.contains(&c.denom))
+        .map(|c| CallbackMsg::SwapExactIn {
+            account_id: account_id.to_string(),
+            coin_in: ActionCoin::from(c),
+            denom_out: swap_denom_out.clone(),
+            slippage,
+            min_receive: None,
+        })
+        .collect::<Vec<_>>();
+    callbacks.push(CallbackMsg::ZapIntoVault {
+        account_id: account_id.to_string(),
+        vault: vault.clone(),
+        retained,
+        min_vault_tokens,
+    });
+
+    let msgs = callbacks
+        .iter()
+        .map(|callback| callback.into_cosmos_msg(&env.contract.address))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_attribute("action", "vault/enter_with_zap")
+        .add_attribute("account_id", account_id)
+        .add_attribute("vault_addr", vault.address.to_string())
+        .add_attribute("coins_in", coins_in.as_slice().to_string()))
+}
+
+pub fn zap_into_vault(
+    deps: DepsMut,
+    env: Env,
+    account_id: &str,
+    vault: Vault,
+    retained: Vec<Coin>,
+    min_vault_tokens: Uint128,
+) -> ContractResult<Response> {
+    let zapped = retained
+        .iter()
+        .map(|c| {
+            let balance =
+                COIN_BALANCES.may_load(deps.storage, (account_id, &c.denom))?.unwrap_or_default();
+            Ok(Coin {
+                denom: c.denom.clone(),
+                amount: balance.checked_sub(c.amount)?,
+            })
+        })
+        .collect::<ContractResult<Vec<_>>>()?
+        .into_iter()
+        .filter(|c| !c.amount.is_zero())
+        .collect::<Vec<_>>();
+    if zapped.is_empty() {
+        return Err(ContractError::NoAmount);
+    }
+
+    let base_token = vault.query_info(&deps.querier)?.base_token;
+    let (base_coins, underlying): (Vec<_>, Vec<_>) =
+        zapped.iter().cloned().partition(|c| c.denom == base_token);
+    if let ([coin], []) = (base_coins.as_slice(), underlying.as_slice()) {
+        return enter_vault(
+            deps,
+            &env.contract.address,
+            account_id,
+            vault,
+            &ActionCoin::from(coin),
+            Some(min_vault_tokens),
+        );
+    }
+
+    // Underlying of an LP base token. Base tokens zapped alongside stay above the retained balance
+    // and are deposited with the LP tokens received. Vault coins received are checked against the
+    // minimum once deposited, so no minimum is set for the LP tokens.
+    let lp_retained =
+        retained.iter().find(|c| c.denom == base_token).map(|c| c.amount).unwrap_or_default();
+    let msgs = [
+        CallbackMsg::ProvideLiquidity {
+            account_id: account_id.to_string(),
+            coins_in: underlying.iter().map(ActionCoin::from).collect(),
+            lp_token_out: base_token.clone(),
+            minimum_receive: Uint128::zero(),
+        },
+        CallbackMsg::ZapIntoVault {
+            account_id: account_id.to_string(),
+            vault: vault.clone(),
+            retained: vec![Coin {
+                denom: base_token,
+                amount: lp_retained,
+            }],
+            min_vault_tokens,
+        },
+    ]
+    .iter()
+    .map(|callback| callback.into_cosmos_msg(&env.contract.address))
+    .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_attribute("action", "vault/zap_into_vault")
+        .add_attribute("account_id", account_id)
+        .add_attribute("vault_addr", vault.address.to_string())
+        .add_attribute("coins_zapped", zapped.as_slice().to_string()))
+}
@@ -15,6 +15,7 @@ pub fn exit_vault(
     account_id: &str,
     vault: Vault,
     amount: Uint128,
+    min_receive: Option<Uint128>,
 ) -> ContractResult<Response> {
     assert_vault_is_whitelisted(deps.storage, &vault)?;
 
@@ -39,6 +40,7 @@ pub fn exit_vault(
         msg: to_binary(&RoverExecuteMsg::Callback(CallbackMsg::UpdateCoinBalance {
             account_id: account_id.to_string(),
             previous_balance,
+            min_receive,
         }))?,
     });
 
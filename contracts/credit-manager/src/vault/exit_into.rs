@@ -0,0 +1,121 @@
+use cosmwasm_std::{Coin, Decimal, DepsMut, Env, Response, StdResult, Uint128};
+use mars_rover::{
+    adapters::vault::Vault,
+    error::ContractResult,
+    msg::execute::{ActionCoin, CallbackMsg},
+    traits::Stringify,
+};
+
+use crate::{
+    state::COIN_BALANCES,
+    utils::assert_coin_is_whitelisted,
+    vault::{exit_vault, utils::lp_underlying_denoms},
+};
+
+/// The amount of base token received is only known once the vault has been exited, so converting
+/// it into `denom_out` is left to `SwapWithdrawnInto`
+pub fn exit_vault_into(
+    deps: DepsMut,
+    env: Env,
+    account_id: &str,
+    vault: Vault,
+    amount: Uint128,
+    denom_out: &str,
+    slippage: Decimal,
+) -> ContractResult<Response> {
+    assert_coin_is_whitelisted(deps.storage, denom_out)?;
+
+    let base_token = vault.query_info(&deps.querier)?.base_token;
+    let lp_underlying = lp_underlying_denoms(deps.storage, &vault)?;
+    let retained = Coin {
+        amount: COIN_BALANCES
+            .may_load(deps.storage, (account_id, &base_token))?
+            .unwrap_or(Uint128::zero()),
+        denom: base_token,
+    };
+    let swap_msg = CallbackMsg::SwapWithdrawnInto {
+        account_id: account_id.to_string(),
+        retained: vec![retained],
+        lp_underlying,
+        denom_out: denom_out.to_string(),
+        slippage,
+    }
+    .into_cosmos_msg(&env.contract.address)?;
+
+    let res = exit_vault(deps, env, account_id, vault, amount, None)?;
+    Ok(res.add_message(swap_msg).add_attribute("denom_out", denom_out))
+}
+
+pub fn swap_withdrawn_into(
+    deps: DepsMut,
+    env: Env,
+    account_id: &str,
+    retained: Vec<Coin>,
+    lp_underlying: Vec<String>,
+    denom_out: &str,
+    slippage: Decimal,
+) -> ContractResult<Response> {
+    let mut withdrawn = vec![];
+    let mut callbacks = vec![];
+
+    for coin in retained {
+        let balance = COIN_BALANCES
+            .may_load(deps.storage, (account_id, &coin.denom))?
+            .unwrap_or(Uint128::zero());
+        let coin = Coin {
+            amount: balance.checked_sub(coin.amount)?,
+            denom: coin.denom,
+        };
+        if coin.amount.is_zero() || coin.denom == denom_out {
+            continue;
+        }
+
+        if lp_underlying.is_empty() {
+            callbacks.push(CallbackMsg::SwapExactIn {
+                account_id: account_id.to_string(),
+                coin_in: ActionCoin::from(&coin),
+                denom_out: denom_out.to_string(),
+                slippage,
+                min_receive: None,
+            });
+        } else {
+            // Underlying coins received are only known once liquidity has been withdrawn
+            let retained_underlying = lp_underlying
+                .iter()
+                .map(|denom| {
+                    Ok(Coin {
+                        amount: COIN_BALANCES
+                            .may_load(deps.storage, (account_id, denom))?
+                            .unwrap_or(Uint128::zero()),
+                        denom: denom.clone(),
+                    })
+                })
+                .collect::<StdResult<Vec<_>>>()?;
+            callbacks.push(CallbackMsg::WithdrawLiquidity {
+                account_id: account_id.to_string(),
+                lp_token: ActionCoin::from(&coin),
+                min_receive: None,
+            });
+            callbacks.push(CallbackMsg::SwapWithdrawnInto {
+                account_id: account_id.to_string(),
+                retained: retained_underlying,
+                lp_underlying: vec![],
+                denom_out: denom_out.to_string(),
+                slippage,
+            });
+        }
+        withdrawn.push(coin);
+    }
+
+    let msgs = callbacks
+        .iter()
+        .map(|callback| callback.into_cosmos_msg(&env.contract.address))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_attribute("action", "vault/swap_withdrawn_into")
+        .add_attribute("account_id", account_id)
+        .add_attribute("coins_withdrawn", withdrawn.as_slice().to_string())
+        .add_attribute("denom_out", denom_out))
+}
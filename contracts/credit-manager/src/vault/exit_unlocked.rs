@@ -1,4 +1,4 @@
-use cosmwasm_std::{to_binary, CosmosMsg, DepsMut, Env, Response, WasmMsg};
+use cosmwasm_std::{to_binary, CosmosMsg, DepsMut, Env, Response, Uint128, WasmMsg};
 use cw_vault_standard::extensions::lockup::UnlockingPosition;
 use mars_rover::{
     adapters::vault::{UnlockingChange, Vault, VaultPositionUpdate},
@@ -19,6 +19,7 @@ pub fn exit_vault_unlocked(
     account_id: &str,
     vault: Vault,
     position_id: u64,
+    min_receive: Option<Uint128>,
 ) -> ContractResult<Response> {
     assert_vault_is_whitelisted(deps.storage, &vault)?;
 
@@ -53,6 +54,7 @@ pub fn exit_vault_unlocked(
         msg: to_binary(&ExecuteMsg::Callback(CallbackMsg::UpdateCoinBalance {
             account_id: account_id.to_string(),
             previous_balance,
+            min_receive,
         }))?,
     });
 
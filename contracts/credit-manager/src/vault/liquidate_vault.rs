@@ -1,6 +1,6 @@
 use std::cmp::min;
 
-use cosmwasm_std::{Coin, DepsMut, Env, Response, Uint128};
+use cosmwasm_std::{Coin, Deps, DepsMut, Env, Response, Uint128};
 use cw_vault_standard::VaultInfoResponse;
 use mars_rover::{
     adapters::vault::{
@@ -8,9 +8,11 @@ use mars_rover::{
         VaultPositionType, VaultPositionUpdate,
     },
     error::{ContractError, ContractResult},
+    msg::query::LiquidationPreview,
 };
 
 use crate::{
+    cache::QueryCache,
     liquidate_coin::{calculate_liquidation, repay_debt},
     state::VAULT_POSITIONS,
     utils::update_balance_msg,
@@ -66,6 +68,59 @@ pub fn liquidate_vault(
     }
 }
 
+pub fn preview_vault_liquidation(
+    deps: Deps,
+    env: &Env,
+    cache: &mut QueryCache,
+    liquidatee_account_id: &str,
+    debt_coin: &Coin,
+    request_vault: &Vault,
+    position_type: &VaultPositionType,
+) -> ContractResult<LiquidationPreview> {
+    let liquidatee_position = VAULT_POSITIONS
+        .load(deps.storage, (liquidatee_account_id, request_vault.address.clone()))?;
+    let vault_info = request_vault.query_info(&deps.querier)?;
+
+    match (liquidatee_position, position_type) {
+        (VaultPositionAmount::Unlocked(a), VaultPositionType::UNLOCKED) => {
+            calculate_vault_liquidation(
+                deps,
+                env,
+                cache,
+                liquidatee_account_id,
+                debt_coin,
+                request_vault,
+                a.total(),
+                &vault_info,
+            )
+        }
+        (VaultPositionAmount::Locking(a), VaultPositionType::LOCKED) => {
+            calculate_vault_liquidation(
+                deps,
+                env,
+                cache,
+                liquidatee_account_id,
+                debt_coin,
+                request_vault,
+                a.locked.total(),
+                &vault_info,
+            )
+        }
+        (position @ VaultPositionAmount::Locking(_), VaultPositionType::UNLOCKING) => {
+            calculate_liquidation(
+                deps,
+                env,
+                cache,
+                liquidatee_account_id,
+                debt_coin,
+                &vault_info.base_token,
+                position.unlocking().total(),
+            )
+        }
+        _ => Err(ContractError::MismatchedVaultType),
+    }
+}
+
 fn liquidate_unlocked(
     deps: DepsMut,
     env: Env,
@@ -77,9 +132,14 @@ fn liquidate_unlocked(
 ) -> ContractResult<Response> {
     let vault_info = request_vault.query_info(&deps.querier)?;
 
-    let (debt, request) = calculate_vault_liquidation(
-        &deps,
+    let LiquidationPreview {
+        debt,
+        request,
+        ..
+    } = calculate_vault_liquidation(
+        deps.as_ref(),
         &env,
+        &mut QueryCache::default(),
         liquidatee_account_id,
         &debt_coin,
         &request_vault,
@@ -120,26 +180,29 @@ fn liquidate_unlocked(
 /// Converts vault coins to their underlying value. This allows for pricing and liquidation
 /// values to be determined. Afterward, the final amount is converted back into vault coins.
 fn calculate_vault_liquidation(
-    deps: &DepsMut,
+    deps: Deps,
     env: &Env,
+    cache: &mut QueryCache,
     liquidatee_account_id: &str,
     debt_coin: &Coin,
     request_vault: &Vault,
     amount: Uint128,
     vault_info: &VaultInfoResponse,
-) -> ContractResult<(Coin, Coin)> {
+) -> ContractResult<LiquidationPreview> {
     let total_underlying = request_vault.query_preview_redeem(&deps.querier, amount)?;
-    let (debt, mut request) = calculate_liquidation(
+    let mut preview = calculate_liquidation(
         deps,
         env,
+        cache,
         liquidatee_account_id,
         debt_coin,
         &vault_info.base_token,
         total_underlying,
     )?;
-    request.denom = vault_info.vault_token.clone();
-    request.amount = amount.checked_multiply_ratio(request.amount, total_underlying)?;
-    Ok((debt, request))
+    preview.request.denom = vault_info.vault_token.clone();
+    preview.request.amount =
+        amount.checked_multiply_ratio(preview.request.amount, total_underlying)?;
+    Ok(preview)
 }
 
 fn liquidate_unlocking(
@@ -153,9 +216,14 @@ fn liquidate_unlocking(
 ) -> ContractResult<Response> {
     let vault_info = request_vault.query_info(&deps.querier)?;
 
-    let (debt, request) = calculate_liquidation(
-        &deps,
+    let LiquidationPreview {
+        debt,
+        request,
+        ..
+    } = calculate_liquidation(
+        deps.as_ref(),
         &env,
+        &mut QueryCache::default(),
         liquidatee_account_id,
         &debt_coin,
         &vault_info.base_token,
@@ -220,9 +288,14 @@ fn liquidate_locked(
 ) -> ContractResult<Response> {
     let vault_info = request_vault.query_info(&deps.querier)?;
 
-    let (debt, request) = calculate_vault_liquidation(
-        &deps,
+    let LiquidationPreview {
+        debt,
+        request,
+        ..
+    } = calculate_vault_liquidation(
+        deps.as_ref(),
         &env,
+        &mut QueryCache::default(),
         liquidatee_account_id,
         &debt_coin,
         &request_vault,
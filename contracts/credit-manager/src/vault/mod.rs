@@ -1,9 +1,13 @@
 pub use self::{
-    enter::*, exit::*, exit_unlocked::*, liquidate_vault::*, request_unlock::*, utils::*,
+    cancel_unlock::*, enter::*, enter_with_zap::*, exit::*, exit_into::*, exit_unlocked::*,
+    liquidate_vault::*, request_unlock::*, utils::*,
 };
 
+mod cancel_unlock;
 mod enter;
+mod enter_with_zap;
 mod exit;
+mod exit_into;
 mod exit_unlocked;
 mod liquidate_vault;
 mod request_unlock;
@@ -1,4 +1,4 @@
-use cosmwasm_std::{Addr, Coin, Deps, StdResult, Storage, Uint128};
+use cosmwasm_std::{Addr, Coin, Deps, Order, StdResult, Storage, Uint128};
 use mars_rover::{
     adapters::vault::{Vault, VaultPositionAmount, VaultPositionUpdate},
     error::{ContractError, ContractResult, TempCheckMulFracError},
@@ -29,13 +29,20 @@ pub fn assert_under_max_unlocking_limit(
     account_id: &str,
     vault: &Vault,
 ) -> ContractResult<()> {
-    let maximum = MAX_UNLOCKING_POSITIONS.load(storage)?;
-    let new_amount = VAULT_POSITIONS
+    let unlocking_positions = VAULT_POSITIONS
         .may_load(storage, (account_id, vault.address.clone()))?
         .map(|p| p.unlocking().positions().len())
-        .map(|len| Uint128::from(len as u128))
-        .unwrap_or(Uint128::zero())
-        .checked_add(Uint128::one())?;
+        .unwrap_or_default();
+    assert_can_add_unlocking_position(storage, unlocking_positions)
+}
+
+/// Fails if one more unlocking position would exceed the maximum per vault
+pub fn assert_can_add_unlocking_position(
+    storage: &dyn Storage,
+    unlocking_positions: usize,
+) -> ContractResult<()> {
+    let maximum = MAX_UNLOCKING_POSITIONS.load(storage)?;
+    let new_amount = Uint128::from(unlocking_positions as u128).checked_add(Uint128::one())?;
 
     if new_amount > maximum {
         return Err(ContractError::ExceedsMaxUnlockingPositions {
@@ -46,6 +53,13 @@ pub fn assert_under_max_unlocking_limit(
     Ok(())
 }
 
+/// Number of vaults the account holds a position in
+pub fn vault_positions_count(storage: &dyn Storage, account_id: &str) -> StdResult<Uint128> {
+    let count =
+        VAULT_POSITIONS.prefix(account_id).keys(storage, None, None, Order::Ascending).count();
+    Ok(Uint128::from(count as u128))
+}
+
 pub fn update_vault_position(
     storage: &mut dyn Storage,
     account_id: &str,
@@ -105,3 +119,27 @@ pub fn rover_vault_balance_value(
     let balance_value = vault.query_value(&deps.querier, &oracle, rover_vault_coin_balance)?;
     Ok(balance_value)
 }
+
+/// Underlying denoms of the vault's base token, as configured for the vault. Empty if the base
+/// token is not an LP token.
+pub fn lp_underlying_denoms(storage: &dyn Storage, vault: &Vault) -> ContractResult<Vec<String>> {
+    let config = VAULT_CONFIGS.load(storage, &vault.address)?;
+    Ok(config.lp_underlying_denoms.unwrap_or_default())
+}
+
+/// Base tokens of the vaults configured with LP underlying denoms, i.e. the LP tokens the zapper
+/// can withdraw liquidity for
+pub fn lp_token_denoms(deps: Deps) -> ContractResult<Vec<String>> {
+    let mut denoms = vec![];
+    for item in VAULT_CONFIGS.range(deps.storage, None, None, Order::Ascending) {
+        let (vault_addr, config) = item?;
+        if config.lp_underlying_denoms.is_none() {
+            continue;
+        }
+        let base_token = Vault::new(vault_addr).query_info(&deps.querier)?.base_token;
+        if !denoms.contains(&base_token) {
+            denoms.push(base_token);
+        }
+    }
+    Ok(denoms)
+}
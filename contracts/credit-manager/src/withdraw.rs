@@ -1,7 +1,14 @@
-use cosmwasm_std::{Addr, BankMsg, Coin, CosmosMsg, DepsMut, Response};
-use mars_rover::error::{ContractError, ContractResult};
+use cosmwasm_std::{
+    Addr, BankMsg, Coin, CosmosMsg, Decimal, Deps, DepsMut, Env, Response, Uint128,
+};
+use mars_red_bank_types::red_bank::Market;
+use mars_rover::error::{ContractError, ContractResult, TempCheckMulFracError};
 
-use crate::utils::decrement_coin_balance;
+use crate::{
+    health::compute_health,
+    state::{ALLOWED_COINS, COIN_BALANCES, ORACLE, RED_BANK},
+    utils::decrement_coin_balance,
+};
 
 pub fn withdraw(
     deps: DepsMut,
@@ -27,3 +34,73 @@ pub fn withdraw(
         .add_attribute("account_id", account_id)
         .add_attribute("coin_withdrawn", coin.to_string()))
 }
+
+/// The largest amount of denom that can be withdrawn while keeping the account at or below max LTV.
+/// If the coin does not count towards max LTV (or there is no debt), the full balance is returned.
+pub fn max_withdraw_amount(
+    deps: Deps,
+    env: &Env,
+    account_id: &str,
+    denom: &str,
+) -> ContractResult<Uint128> {
+    let balance =
+        COIN_BALANCES.may_load(deps.storage, (account_id, denom))?.unwrap_or_else(Uint128::zero);
+    if balance.is_zero() {
+        return Ok(Uint128::zero());
+    }
+
+    let red_bank = RED_BANK.load(deps.storage)?;
+    let Market {
+        max_loan_to_value,
+        ..
+    } = red_bank.query_market(&deps.querier, denom)?;
+
+    // If coin has been de-listed, it does not contribute to max LTV
+    let max_ltv = if ALLOWED_COINS.contains(deps.storage, denom) {
+        max_loan_to_value
+    } else {
+        Decimal::zero()
+    };
+
+    let health = compute_health(deps, env, account_id)?;
+    if health.total_debt_value.is_zero() || max_ltv.is_zero() {
+        return Ok(balance);
+    }
+    if health.is_above_max_ltv() {
+        return Ok(Uint128::zero());
+    }
+
+    let oracle = ORACLE.load(deps.storage)?;
+    let price = oracle.query_price(&deps.querier, denom)?.price;
+    if price.is_zero() {
+        return Ok(balance);
+    }
+
+    // The withdrawn coin's max LTV adjusted value may drop by at most this amount
+    let headroom = health.max_ltv_adjusted_collateral.checked_sub(health.total_debt_value)?;
+    let current_adjusted_value = max_ltv_adjusted_value(balance, price, max_ltv)?;
+    if current_adjusted_value <= headroom {
+        return Ok(balance);
+    }
+
+    // Smallest amount left in the account whose adjusted value still covers the debt.
+    // Mirrors the rounding in health computation: floor(floor(amount * price) * max_ltv)
+    let min_adjusted_value = current_adjusted_value.checked_sub(headroom)?;
+    let min_value =
+        min_adjusted_value.checked_div_ceil(max_ltv).map_err(|_| TempCheckMulFracError {})?;
+    let min_amount = min_value.checked_div_ceil(price).map_err(|_| TempCheckMulFracError {})?;
+
+    Ok(balance.saturating_sub(min_amount))
+}
+
+fn max_ltv_adjusted_value(
+    amount: Uint128,
+    price: Decimal,
+    max_ltv: Decimal,
+) -> ContractResult<Uint128> {
+    Ok(amount
+        .checked_mul_floor(price)
+        .map_err(|_| TempCheckMulFracError {})?
+        .checked_mul_floor(max_ltv)
+        .map_err(|_| TempCheckMulFracError {})?)
+}
@@ -1,4 +1,4 @@
-use cosmwasm_std::{Coin, Deps, DepsMut, Env, Response, Uint128};
+use cosmwasm_std::{Coin, Deps, DepsMut, Env, Response, StdResult, Uint128};
 use mars_rover::{
     error::{ContractError, ContractResult},
     msg::execute::{ActionAmount, ActionCoin},
@@ -9,7 +9,7 @@ use crate::{
     state::{COIN_BALANCES, ZAPPER},
     utils::{
         assert_coin_is_whitelisted, assert_coins_are_whitelisted, decrement_coin_balance,
-        update_balance_msg, update_balances_msgs,
+        update_balance_msg, update_balance_with_min_receive_msg,
     },
 };
 
@@ -60,6 +60,7 @@ pub fn withdraw_liquidity(
     env: Env,
     account_id: &str,
     lp_token_action: &ActionCoin,
+    min_receive: Option<Vec<Coin>>,
 ) -> ContractResult<Response> {
     assert_coin_is_whitelisted(deps.storage, &lp_token_action.denom)?;
 
@@ -81,16 +82,35 @@ pub fn withdraw_liquidity(
     let coins_out = zapper.estimate_withdraw_liquidity(&deps.querier, &lp_token)?;
     assert_coins_are_whitelisted(deps.storage, coins_out.to_denoms())?;
 
+    let min_receive = min_receive.unwrap_or_default();
+    // Minimums for coins the withdrawal does not return can never be met
+    if let Some(min) =
+        min_receive.iter().find(|min| !coins_out.iter().any(|c| c.denom == min.denom))
+    {
+        return Err(ContractError::ReceivedBelowMinimum {
+            denom: min.denom.clone(),
+            received: Uint128::zero(),
+            min_receive: min.amount,
+        });
+    }
+
     decrement_coin_balance(deps.storage, account_id, &lp_token)?;
 
     // After unzap is complete, update account's coin balances
     let zap_msg = zapper.withdraw_liquidity_msg(&lp_token)?;
-    let update_balances_msgs = update_balances_msgs(
-        &deps.querier,
-        &env.contract.address,
-        account_id,
-        coins_out.to_denoms(),
-    )?;
+    let update_balances_msgs = coins_out
+        .iter()
+        .map(|coin| {
+            let min = min_receive.iter().find(|min| min.denom == coin.denom).map(|min| min.amount);
+            update_balance_with_min_receive_msg(
+                &deps.querier,
+                &env.contract.address,
+                account_id,
+                &coin.denom,
+                min,
+            )
+        })
+        .collect::<StdResult<Vec<_>>>()?;
 
     Ok(Response::new()
         .add_message(zap_msg)
@@ -1,7 +1,7 @@
 use std::mem::take;
 
 use anyhow::Result as AnyResult;
-use cosmwasm_std::{coins, testing::MockApi, Addr, Coin, Decimal, Uint128};
+use cosmwasm_std::{coins, testing::MockApi, Addr, Coin, Decimal, StdResult, Uint128};
 use cw_multi_test::{App, AppResponse, BankSudo, BasicApp, Executor, SudoMsg};
 use cw_vault_standard::{
     extensions::lockup::{LockupQueryMsg, UnlockingPosition},
@@ -36,7 +36,9 @@ use mars_rover::{
         execute::{Action, CallbackMsg, EmergencyUpdate},
         instantiate::{ConfigUpdates, VaultInstantiateConfig},
         query::{
-            CoinBalanceResponseItem, ConfigResponse, DebtShares, Positions, SharesResponseItem,
+            CoinBalanceResponseItem, CoinPrice as RoverCoinPrice, ConfigResponse, DebtShares,
+            LiquidatableAccountsResponse, LiquidationPreview, LiquidationRequest, Positions,
+            PositionsWithValue, SharesResponseItem, SimulateActionsResponse,
             VaultInfoResponse as RoverVaultInfoResponse, VaultPositionResponseItem,
             VaultWithBalance,
         },
@@ -75,6 +77,7 @@ pub struct MockEnvBuilder {
     pub accounts_to_fund: Vec<AccountToFund>,
     pub max_close_factor: Option<Decimal>,
     pub max_unlocking_positions: Option<Uint128>,
+    pub max_vault_positions: Option<Uint128>,
 }
 
 #[allow(clippy::new_ret_no_self)]
@@ -94,6 +97,7 @@ impl MockEnv {
             accounts_to_fund: vec![],
             max_close_factor: None,
             max_unlocking_positions: None,
+            max_vault_positions: None,
         }
     }
 
@@ -255,6 +259,18 @@ impl MockEnv {
             .unwrap()
     }
 
+    pub fn query_positions_with_value(&self, account_id: &str) -> PositionsWithValue {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.rover.clone(),
+                &QueryMsg::PositionsWithValue {
+                    account_id: account_id.to_string(),
+                },
+            )
+            .unwrap()
+    }
+
     pub fn query_health(&self, account_id: &str) -> HealthResponse {
         self.app
             .wrap()
@@ -267,6 +283,109 @@ impl MockEnv {
             .unwrap()
     }
 
+    pub fn query_health_under_scenario(
+        &self,
+        account_id: &str,
+        price_overrides: Vec<RoverCoinPrice>,
+    ) -> HealthResponse {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.rover.clone(),
+                &QueryMsg::HealthUnderScenario {
+                    account_id: account_id.to_string(),
+                    price_overrides,
+                },
+            )
+            .unwrap()
+    }
+
+    pub fn query_liquidation_price(&self, account_id: &str, denom: &str) -> Option<Decimal> {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.rover.clone(),
+                &QueryMsg::LiquidationPrice {
+                    account_id: account_id.to_string(),
+                    denom: denom.to_string(),
+                },
+            )
+            .unwrap()
+    }
+
+    pub fn query_max_borrow_amount(&self, account_id: &str, denom: &str) -> Uint128 {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.rover.clone(),
+                &QueryMsg::MaxBorrowAmount {
+                    account_id: account_id.to_string(),
+                    denom: denom.to_string(),
+                },
+            )
+            .unwrap()
+    }
+
+    pub fn query_max_withdraw_amount(&self, account_id: &str, denom: &str) -> Uint128 {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.rover.clone(),
+                &QueryMsg::MaxWithdrawAmount {
+                    account_id: account_id.to_string(),
+                    denom: denom.to_string(),
+                },
+            )
+            .unwrap()
+    }
+
+    pub fn query_liquidatable_accounts(
+        &self,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> LiquidatableAccountsResponse {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.rover.clone(),
+                &QueryMsg::LiquidatableAccounts {
+                    start_after,
+                    limit,
+                },
+            )
+            .unwrap()
+    }
+
+    pub fn query_liquidation_preview(
+        &self,
+        liquidatee_account_id: &str,
+        debt_coin: Coin,
+        request: LiquidationRequest,
+    ) -> StdResult<LiquidationPreview> {
+        self.app.wrap().query_wasm_smart(
+            self.rover.clone(),
+            &QueryMsg::PreviewLiquidation {
+                liquidatee_account_id: liquidatee_account_id.to_string(),
+                debt_coin,
+                request,
+            },
+        )
+    }
+
+    pub fn simulate_actions(
+        &self,
+        account_id: &str,
+        actions: Vec<Action>,
+    ) -> StdResult<SimulateActionsResponse> {
+        self.app.wrap().query_wasm_smart(
+            self.rover.clone(),
+            &QueryMsg::SimulateActions {
+                account_id: account_id.to_string(),
+                actions,
+            },
+        )
+    }
+
     pub fn query_balance(&self, addr: &Addr, denom: &str) -> Coin {
         self.app.wrap().query_balance(addr.clone(), denom).unwrap()
     }
@@ -599,6 +718,7 @@ impl MockEnvBuilder {
             self.get_allowed_coins().iter().map(|info| info.denom.clone()).collect();
         let max_close_factor = self.get_max_close_factor();
         let max_unlocking_positions = self.get_max_unlocking_positions();
+        let max_vault_positions = self.get_max_vault_positions();
 
         let mut vault_configs = vec![];
         vault_configs.extend(self.deploy_vaults());
@@ -618,6 +738,7 @@ impl MockEnvBuilder {
                 oracle,
                 max_close_factor,
                 max_unlocking_positions,
+                max_vault_positions,
                 swapper,
                 zapper,
             },
@@ -761,6 +882,12 @@ impl MockEnvBuilder {
                 max_ltv: vault.max_ltv,
                 liquidation_threshold: vault.liquidation_threshold,
                 whitelisted: vault.whitelisted,
+                lp_underlying_denoms: (vault.base_token_denom == lp_token_info().denom).then(
+                    || {
+                        let (denom_a, denom_b) = lp_pair_denoms();
+                        vec![denom_a, denom_b]
+                    },
+                ),
             },
         }
     }
@@ -774,6 +901,7 @@ impl MockEnvBuilder {
                 Addr::unchecked("swapper-instantiator"),
                 &SwapperInstantiateMsg {
                     owner: self.get_owner().to_string(),
+                    oracle_check: None,
                 },
                 &[],
                 "mock-vault",
@@ -800,7 +928,7 @@ impl MockEnvBuilder {
                 oracle: oracle.clone(),
                 lp_configs: vec![LpConfig {
                     lp_token_denom: lp_token.denom.to_string(),
-                    lp_pair_denoms: ("uatom".to_string(), "uosmo".to_string()),
+                    lp_pair_denoms: lp_pair_denoms(),
                 }],
             },
             &[],
@@ -853,6 +981,10 @@ impl MockEnvBuilder {
         self.max_unlocking_positions.unwrap_or_else(|| Uint128::new(100))
     }
 
+    fn get_max_vault_positions(&self) -> Uint128 {
+        self.max_vault_positions.unwrap_or_else(|| Uint128::new(10))
+    }
+
     //--------------------------------------------------------------------------------------------------
     // Setter functions
     //--------------------------------------------------------------------------------------------------
@@ -928,6 +1060,11 @@ impl MockEnvBuilder {
         self.max_unlocking_positions = Some(Uint128::new(max));
         self
     }
+
+    pub fn max_vault_positions(&mut self, max: u128) -> &mut Self {
+        self.max_vault_positions = Some(Uint128::new(max));
+        self
+    }
 }
 
 //--------------------------------------------------------------------------------------------------
@@ -961,3 +1098,8 @@ fn propose_new_nft_minter(app: &mut App, nft_contract: Addr, old_minter: &Addr,
     };
     app.execute_contract(old_minter.clone(), nft_contract, &proposal_msg, &[]).unwrap();
 }
+
+/// Underlying denoms the mock zapper provides liquidity for the LP token with
+fn lp_pair_denoms() -> (String, String) {
+    ("uatom".to_string(), "uosmo".to_string())
+}
@@ -1,14 +1,18 @@
 use std::ops::{Mul, Sub};
 
-use cosmwasm_std::{coin, coins, Addr, Uint128};
+use cosmwasm_std::{coin, coins, Addr, Decimal, Uint128};
 use mars_credit_manager::borrow::DEFAULT_DEBT_SHARES_PER_COIN_BORROWED;
 use mars_rover::{
     error::ContractError,
-    msg::execute::Action::{Borrow, Deposit},
+    msg::{
+        execute::Action::{Borrow, Deposit},
+        query::CoinPrice,
+    },
 };
 
 use crate::helpers::{
-    assert_err, uosmo_info, AccountToFund, MockEnv, DEFAULT_RED_BANK_COIN_BALANCE,
+    assert_err, uatom_info, uosmo_info, AccountToFund, CoinInfo, MockEnv,
+    DEFAULT_RED_BANK_COIN_BALANCE,
 };
 
 pub mod helpers;
@@ -215,3 +219,115 @@ fn debt_shares_with_debt_amount() {
 
     assert_eq!(total.shares, debt_position_a.shares + debt_position_b.shares);
 }
+
+#[test]
+fn max_borrow_amount_keeps_account_healthy() {
+    let coin_info = uatom_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .allowed_coins(&[coin_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: coins(300, coin_info.denom.clone()),
+        })
+        .build()
+        .unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    let max_borrow = mock.query_max_borrow_amount(&account_id, &coin_info.denom);
+    assert_eq!(max_borrow, Uint128::zero());
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![Deposit(coin_info.to_coin(300))],
+        &[coin(300, coin_info.denom.clone())],
+    )
+    .unwrap();
+
+    // headroom: 300 * 0.82 - 3 (rounding buffer) = 243
+    // each unit borrowed reduces headroom by 1 * (1 - 0.82) = 0.18
+    let max_borrow = mock.query_max_borrow_amount(&account_id, &coin_info.denom);
+    assert_eq!(max_borrow, Uint128::new(1350));
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![Borrow(coin_info.to_coin(max_borrow.u128()))],
+        &[],
+    )
+    .unwrap();
+
+    let health = mock.query_health(&account_id);
+    assert!(!health.above_max_ltv);
+
+    let max_borrow = mock.query_max_borrow_amount(&account_id, &coin_info.denom);
+    assert_eq!(max_borrow, Uint128::zero());
+}
+
+#[test]
+fn max_borrow_amount_at_max_ltv_of_one() {
+    let coin_info = CoinInfo {
+        max_ltv: Decimal::one(),
+        liquidation_threshold: Decimal::one(),
+        ..uatom_info()
+    };
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .allowed_coins(&[coin_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: coins(300, coin_info.denom.clone()),
+        })
+        .build()
+        .unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![Deposit(coin_info.to_coin(300))],
+        &[coin(300, coin_info.denom.clone())],
+    )
+    .unwrap();
+
+    // Borrowing leaves the headroom unchanged, so it is limited to the value the headroom covers:
+    // 300 * 1 - 3 (rounding buffer) = 297
+    let max_borrow = mock.query_max_borrow_amount(&account_id, &coin_info.denom);
+    assert_eq!(max_borrow, Uint128::new(297));
+}
+
+#[test]
+fn max_borrow_amount_of_zero_priced_coin() {
+    let atom_info = uatom_info();
+    let osmo_info = uosmo_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .allowed_coins(&[atom_info.clone(), osmo_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: coins(300, atom_info.denom.clone()),
+        })
+        .build()
+        .unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![Deposit(atom_info.to_coin(300))],
+        &[coin(300, atom_info.denom.clone())],
+    )
+    .unwrap();
+
+    mock.price_change(CoinPrice {
+        denom: osmo_info.denom.clone(),
+        price: Decimal::zero(),
+    });
+
+    let max_borrow = mock.query_max_borrow_amount(&account_id, &osmo_info.denom);
+    assert_eq!(max_borrow, Uint128::zero());
+}
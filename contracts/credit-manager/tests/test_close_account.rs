@@ -0,0 +1,221 @@
+use cosmwasm_std::{Addr, Decimal, Uint128};
+use mars_rover::{
+    error::ContractError,
+    msg::execute::Action::{Borrow, CloseAccount, Deposit, EnterVault, ProvideLiquidity},
+};
+
+use crate::helpers::{
+    assert_err, locked_vault_info, lp_token_info, uatom_info, unlocked_vault_info, uosmo_info,
+    AccountToFund, MockEnv,
+};
+
+pub mod helpers;
+
+#[test]
+fn close_account_fails_with_locked_vault_position() {
+    let lp_token = lp_token_info();
+    let leverage_vault = locked_vault_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .allowed_coins(&[lp_token.clone()])
+        .vault_configs(&[leverage_vault.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![lp_token.to_coin(200)],
+        })
+        .build()
+        .unwrap();
+
+    let vault = mock.get_vault(&leverage_vault);
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    let res = mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(lp_token.to_coin(200)),
+            EnterVault {
+                vault,
+                coin: lp_token.to_action_coin(200),
+                min_receive: None,
+            },
+            CloseAccount {
+                target_denom: lp_token.denom.clone(),
+                slippage: Decimal::percent(5),
+            },
+        ],
+        &[lp_token.to_coin(200)],
+    );
+
+    assert_err(res, ContractError::CloseAccountVaultLocked);
+}
+
+#[test]
+fn close_account_exits_unlocked_vault() {
+    let uosmo_info = uosmo_info();
+    let lp_token = lp_token_info();
+    let leverage_vault = unlocked_vault_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .allowed_coins(&[lp_token.clone(), uosmo_info.clone()])
+        .vault_configs(&[leverage_vault.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![lp_token.to_coin(200)],
+        })
+        .build()
+        .unwrap();
+
+    let vault = mock.get_vault(&leverage_vault);
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(lp_token.to_coin(200)),
+            EnterVault {
+                vault: vault.clone(),
+                coin: lp_token.to_action_coin(200),
+                min_receive: None,
+            },
+        ],
+        &[lp_token.to_coin(200)],
+    )
+    .unwrap();
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![CloseAccount {
+            target_denom: uosmo_info.denom.clone(),
+            slippage: Decimal::percent(5),
+        }],
+        &[],
+    )
+    .unwrap();
+
+    let res = mock.query_positions(&account_id);
+    assert_eq!(res.deposits.len(), 0);
+    assert_eq!(res.vaults.len(), 0);
+
+    let vault_token_balance = mock.query_balance(&mock.rover, &leverage_vault.vault_token_denom);
+    assert_eq!(vault_token_balance.amount, Uint128::zero());
+
+    // The 200 LP tokens withdrawn from the vault are swapped for 1337 uosmo
+    let lp_balance = mock.query_balance(&user, &lp_token.denom);
+    assert_eq!(lp_balance.amount, Uint128::zero());
+    let osmo_balance = mock.query_balance(&user, &uosmo_info.denom);
+    assert_eq!(osmo_balance.amount, Uint128::new(1337));
+}
+
+#[test]
+fn close_account_repays_debts_and_refunds() {
+    let uosmo_info = uosmo_info();
+    let uatom_info = uatom_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .allowed_coins(&[uosmo_info.clone(), uatom_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![uatom_info.to_coin(300)],
+        })
+        .build()
+        .unwrap();
+
+    let account_id = mock.create_credit_account(&user).unwrap();
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![Deposit(uatom_info.to_coin(300)), Borrow(uosmo_info.to_coin(100))],
+        &[uatom_info.to_coin(300)],
+    )
+    .unwrap();
+
+    let res = mock.query_positions(&account_id);
+    assert_eq!(res.debts.len(), 1);
+    assert_eq!(res.debts.first().unwrap().amount, Uint128::new(101));
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![CloseAccount {
+            target_denom: uosmo_info.denom.clone(),
+            slippage: Decimal::percent(5),
+        }],
+        &[],
+    )
+    .unwrap();
+
+    let res = mock.query_positions(&account_id);
+    assert_eq!(res.deposits.len(), 0);
+    assert_eq!(res.debts.len(), 0);
+    assert_eq!(res.vaults.len(), 0);
+
+    // 1 uatom is sold to cover the 1 uosmo shortfall, the remaining 299 uatom are sold for the
+    // target denom. Each mock swap returns 1337 uosmo.
+    let osmo_balance = mock.query_balance(&user, &uosmo_info.denom);
+    assert_eq!(osmo_balance.amount, Uint128::new(100 + 1337 * 2 - 101));
+    let atom_balance = mock.query_balance(&user, &uatom_info.denom);
+    assert_eq!(atom_balance.amount, Uint128::zero());
+}
+
+#[test]
+fn close_account_withdraws_liquidity() {
+    let uosmo_info = uosmo_info();
+    let uatom_info = uatom_info();
+    let lp_token = lp_token_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .allowed_coins(&[lp_token.clone(), uosmo_info.clone(), uatom_info.clone()])
+        .vault_configs(&[unlocked_vault_info()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![uatom_info.to_coin(100), uosmo_info.to_coin(50)],
+        })
+        .build()
+        .unwrap();
+
+    let account_id = mock.create_credit_account(&user).unwrap();
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(uatom_info.to_coin(100)),
+            Deposit(uosmo_info.to_coin(50)),
+            ProvideLiquidity {
+                coins_in: vec![uatom_info.to_action_coin(100), uosmo_info.to_action_coin(50)],
+                lp_token_out: lp_token.denom.clone(),
+                minimum_receive: Uint128::zero(),
+            },
+        ],
+        &[uatom_info.to_coin(100), uosmo_info.to_coin(50)],
+    )
+    .unwrap();
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![CloseAccount {
+            target_denom: uosmo_info.denom.clone(),
+            slippage: Decimal::percent(5),
+        }],
+        &[],
+    )
+    .unwrap();
+
+    let res = mock.query_positions(&account_id);
+    assert_eq!(res.deposits.len(), 0);
+
+    // LP token is withdrawn into 100 uatom & 50 uosmo, with the uatom swapped for 1337 uosmo
+    let lp_balance = mock.query_balance(&user, &lp_token.denom);
+    assert_eq!(lp_balance.amount, Uint128::zero());
+    let atom_balance = mock.query_balance(&user, &uatom_info.denom);
+    assert_eq!(atom_balance.amount, Uint128::zero());
+    let osmo_balance = mock.query_balance(&user, &uosmo_info.denom);
+    assert_eq!(osmo_balance.amount, Uint128::new(50 + 1337));
+}
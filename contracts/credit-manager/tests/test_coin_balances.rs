@@ -20,6 +20,7 @@ fn only_rover_can_call_update_coin_balances() {
         CallbackMsg::UpdateCoinBalance {
             account_id,
             previous_balance: coin(1, "utest"),
+            min_receive: None,
         },
     );
     assert_err(res, ContractError::ExternalInvocation)
@@ -53,6 +54,7 @@ fn user_does_not_have_enough_to_pay_diff() {
         CallbackMsg::UpdateCoinBalance {
             account_id,
             previous_balance: coin(601, osmo_info.denom),
+            min_receive: None,
         },
     );
 
@@ -94,6 +96,7 @@ fn user_gets_rebalanced_down() {
         CallbackMsg::UpdateCoinBalance {
             account_id: account_id.clone(),
             previous_balance: coin(500, osmo_info.denom.clone()),
+            min_receive: None,
         },
     )
     .unwrap();
@@ -139,6 +142,7 @@ fn user_gets_rebalanced_up() {
         CallbackMsg::UpdateCoinBalance {
             account_id: account_id.clone(),
             previous_balance: coin(300, osmo_info.denom.clone()),
+            min_receive: None,
         },
     )
     .unwrap();
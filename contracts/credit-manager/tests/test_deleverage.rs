@@ -0,0 +1,111 @@
+use cosmwasm_std::{Addr, Decimal, Uint128};
+use mars_rover::{
+    error::ContractError,
+    msg::execute::Action::{Borrow, Deleverage, Deposit, Withdraw},
+};
+
+use crate::helpers::{
+    assert_err, get_coin, get_debt, uatom_info, uosmo_info, AccountToFund, MockEnv,
+};
+
+pub mod helpers;
+
+#[test]
+fn deleverage_fails_if_already_above_target() {
+    let uosmo_info = uosmo_info();
+    let uatom_info = uatom_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .allowed_coins(&[uosmo_info.clone(), uatom_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![uatom_info.to_coin(100)],
+        })
+        .build()
+        .unwrap();
+
+    let account_id = mock.create_credit_account(&user).unwrap();
+    let res = mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(uatom_info.to_coin(100)),
+            Borrow(uosmo_info.to_coin(10)),
+            Deleverage {
+                target_max_ltv_health_factor: Decimal::from_atomics(13u128, 1).unwrap(),
+                collateral_denom: uatom_info.denom.clone(),
+                debt_denom: uosmo_info.denom.clone(),
+                slippage: Decimal::percent(5),
+            },
+        ],
+        &[uatom_info.to_coin(100)],
+    );
+
+    assert_err(
+        res,
+        ContractError::RequirementsNotMet(
+            "Max LTV health factor is already at or above 1.3".to_string(),
+        ),
+    );
+}
+
+#[test]
+fn deleverage_to_target_health_factor() {
+    let uosmo_info = uosmo_info();
+    let uatom_info = uatom_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .allowed_coins(&[uosmo_info.clone(), uatom_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![uatom_info.to_coin(400)],
+        })
+        .build()
+        .unwrap();
+
+    let account_id = mock.create_credit_account(&user).unwrap();
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(uatom_info.to_coin(400)),
+            Borrow(uosmo_info.to_coin(1300)),
+            Withdraw(uosmo_info.to_coin(1300)),
+        ],
+        &[uatom_info.to_coin(400)],
+    )
+    .unwrap();
+
+    // Max LTV adjusted collateral: 400 uatom * 1 * 0.82 = 328
+    // Debt: 1301 uosmo * 0.25 = 325
+    let health = mock.query_health(&account_id);
+    assert_eq!(health.max_ltv_adjusted_collateral, Uint128::new(328));
+    assert_eq!(health.total_debt_value, Uint128::new(325));
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![Deleverage {
+            target_max_ltv_health_factor: Decimal::from_atomics(11u128, 1).unwrap(),
+            collateral_denom: uatom_info.denom.clone(),
+            debt_denom: uosmo_info.denom.clone(),
+            slippage: Decimal::percent(5),
+        }],
+        &[],
+    )
+    .unwrap();
+
+    // Value to sell: (1.1 * 325 - 328) / (1.1 * 0.95 - 0.82) = 30 / 0.225 = 133.33 -> 134 uatom
+    // Debt repaid: the swapper's estimate less slippage, 1337 uosmo * 0.95 = 1270 uosmo
+    let positions = mock.query_positions(&account_id);
+    let atom_balance = get_coin(&uatom_info.denom, &positions.deposits);
+    assert_eq!(atom_balance.amount, Uint128::new(400 - 134));
+    let debt = get_debt(&uosmo_info.denom, &positions.debts);
+    assert_eq!(debt.amount, Uint128::new(1301 - 1270));
+
+    // Mock swapper always returns 1337 uosmo, the excess over the repaid amount is kept as a deposit
+    let osmo_balance = get_coin(&uosmo_info.denom, &positions.deposits);
+    assert_eq!(osmo_balance.amount, Uint128::new(1337 - 1270));
+}
@@ -0,0 +1,68 @@
+use cosmwasm_std::{Addr, Decimal};
+use mars_mock_oracle::msg::CoinPrice;
+use mars_rover::msg::execute::Action::{Borrow, Deposit};
+
+use crate::helpers::{uatom_info, uosmo_info, AccountToFund, MockEnv};
+
+pub mod helpers;
+
+#[test]
+fn only_liquidatable_accounts_with_debt_are_returned() {
+    let uosmo_info = uosmo_info();
+    let uatom_info = uatom_info();
+
+    let users = ["no_debt", "healthy", "unhealthy_a", "unhealthy_b"].map(Addr::unchecked);
+
+    let mut mock_builder = MockEnv::new();
+    mock_builder.allowed_coins(&[uosmo_info.clone(), uatom_info.clone()]);
+    for user in &users {
+        mock_builder.fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![uosmo_info.to_coin(300)],
+        });
+    }
+    let mut mock = mock_builder.build().unwrap();
+
+    let borrow_amounts = [0, 1, 50, 50];
+    let mut account_ids = vec![];
+    for (user, borrow_amount) in users.iter().zip(borrow_amounts) {
+        let account_id = mock.create_credit_account(user).unwrap();
+        let mut actions = vec![Deposit(uosmo_info.to_coin(300))];
+        if borrow_amount > 0 {
+            actions.push(Borrow(uatom_info.to_coin(borrow_amount)));
+        }
+        mock.update_credit_account(&account_id, user, actions, &[uosmo_info.to_coin(300)]).unwrap();
+        account_ids.push(account_id);
+    }
+
+    assert!(mock.query_liquidatable_accounts(None, None).accounts.is_empty());
+
+    mock.price_change(CoinPrice {
+        denom: uatom_info.denom,
+        price: Decimal::from_atomics(20u128, 0).unwrap(),
+    });
+
+    let res = mock.query_liquidatable_accounts(None, None);
+    assert_eq!(res.next_start_after, None);
+    let accounts = res.accounts;
+    assert_eq!(accounts.len(), 2);
+    assert_eq!(accounts[0].account_id, account_ids[2]);
+    assert_eq!(accounts[1].account_id, account_ids[3]);
+    assert!(accounts.iter().all(|a| a.health.liquidatable));
+    assert_eq!(accounts[0].health, mock.query_health(&account_ids[2]));
+
+    // The limit applies to accounts scanned, so the healthy account takes up the first page
+    let first_page = mock.query_liquidatable_accounts(None, Some(1));
+    assert!(first_page.accounts.is_empty());
+    assert_eq!(first_page.next_start_after, Some(account_ids[1].clone()));
+
+    let second_page = mock.query_liquidatable_accounts(first_page.next_start_after, Some(2));
+    assert_eq!(second_page.accounts.len(), 2);
+    assert_eq!(second_page.accounts[0].account_id, account_ids[2]);
+    assert_eq!(second_page.accounts[1].account_id, account_ids[3]);
+    assert_eq!(second_page.next_start_after, Some(account_ids[3].clone()));
+
+    let last_page = mock.query_liquidatable_accounts(second_page.next_start_after, Some(2));
+    assert!(last_page.accounts.is_empty());
+    assert_eq!(last_page.next_start_after, None);
+}
@@ -41,6 +41,7 @@ fn pagination_on_all_vault_coin_balances_query_works() {
         actions.extend([Action::EnterVault {
             vault: mock.get_vault(v),
             coin: lp_token.to_action_coin(10),
+            min_receive: None,
         }]);
     });
 
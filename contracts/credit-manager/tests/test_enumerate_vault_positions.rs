@@ -42,6 +42,7 @@ fn pagination_on_all_vault_positions_query_works() {
         actions.extend([Action::EnterVault {
             vault: mock.get_vault(v),
             coin: lp_token.to_action_coin(10),
+            min_receive: None,
         }]);
     });
 
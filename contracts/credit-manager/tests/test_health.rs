@@ -12,7 +12,7 @@ use mars_rover::{
             ActionAmount, ActionCoin,
         },
         instantiate::{ConfigUpdates, VaultInstantiateConfig},
-        query::DebtAmount,
+        query::{CoinPrice as RoverCoinPrice, DebtAmount},
     },
 };
 
@@ -700,6 +700,7 @@ fn delisted_vaults_drop_max_ltv() {
             EnterVault {
                 vault,
                 coin: lp_token.to_action_coin(200),
+                min_receive: None,
             },
         ],
         &[lp_token.to_coin(200)],
@@ -717,6 +718,7 @@ fn delisted_vaults_drop_max_ltv() {
             max_ltv: v.config.max_ltv,
             liquidation_threshold: v.config.liquidation_threshold,
             whitelisted: false,
+            lp_underlying_denoms: None,
         },
     };
 
@@ -780,6 +782,7 @@ fn vault_base_token_delisting_drops_max_ltv() {
             EnterVault {
                 vault,
                 coin: lp_token.to_action_coin(200),
+                min_receive: None,
             },
         ],
         &[lp_token.to_coin(200)],
@@ -899,6 +902,244 @@ fn can_take_actions_if_ltv_does_not_weaken() {
     .unwrap();
 }
 
+#[test]
+fn price_overrides_apply_to_deposits_and_debts() {
+    let uosmo_info = uosmo_info();
+    let uatom_info = uatom_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .allowed_coins(&[uosmo_info.clone(), uatom_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: coins(300, uosmo_info.denom.clone()),
+        })
+        .build()
+        .unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![Deposit(uosmo_info.to_coin(300)), Borrow(uatom_info.to_coin(50))],
+        &[uosmo_info.to_coin(300)],
+    )
+    .unwrap();
+
+    let health = mock.query_health(&account_id);
+    assert!(!health.liquidatable);
+
+    // Overriding with the oracle's own prices changes nothing
+    let unchanged = mock.query_health_under_scenario(
+        &account_id,
+        vec![RoverCoinPrice {
+            denom: uatom_info.denom.clone(),
+            price: uatom_info.price,
+        }],
+    );
+    assert_eq!(unchanged, health);
+
+    let scenario = mock.query_health_under_scenario(
+        &account_id,
+        vec![RoverCoinPrice {
+            denom: uatom_info.denom.clone(),
+            price: Decimal::from_atomics(10u128, 0).unwrap(),
+        }],
+    );
+    assert_eq!(scenario.total_debt_value, Uint128::new(510));
+    assert_eq!(scenario.total_collateral_value, Uint128::new(575));
+    assert_eq!(scenario.liquidation_threshold_adjusted_collateral, Uint128::new(508));
+    assert!(scenario.liquidatable);
+
+    // Oracle prices remain untouched
+    assert_eq!(mock.query_health(&account_id), health);
+}
+
+#[test]
+fn price_overrides_apply_to_vault_base_tokens() {
+    let lp_token = lp_token_info();
+    let leverage_vault = unlocked_vault_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .allowed_coins(&[lp_token.clone()])
+        .vault_configs(&[leverage_vault.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![lp_token.to_coin(200)],
+        })
+        .build()
+        .unwrap();
+
+    let vault = mock.get_vault(&leverage_vault);
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(lp_token.to_coin(200)),
+            EnterVault {
+                vault,
+                coin: lp_token.to_action_coin(200),
+                min_receive: None,
+            },
+        ],
+        &[lp_token.to_coin(200)],
+    )
+    .unwrap();
+
+    let health = mock.query_health(&account_id);
+    assert_eq!(health.total_collateral_value, Uint128::new(1974));
+
+    let scenario = mock.query_health_under_scenario(
+        &account_id,
+        vec![RoverCoinPrice {
+            denom: lp_token.denom.clone(),
+            price: lp_token.price.mul(Decimal::percent(80)),
+        }],
+    );
+    assert_eq!(scenario.total_collateral_value, Uint128::new(1579));
+}
+
+#[test]
+fn liquidation_price_of_collateral_and_debt_denoms() {
+    let uosmo_info = uosmo_info();
+    let uatom_info = uatom_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .allowed_coins(&[uosmo_info.clone(), uatom_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: coins(300, uosmo_info.denom.clone()),
+        })
+        .build()
+        .unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![Deposit(uosmo_info.to_coin(300))],
+        &[uosmo_info.to_coin(300)],
+    )
+    .unwrap();
+
+    // No debt, cannot be liquidated
+    assert_eq!(mock.query_liquidation_price(&account_id, &uosmo_info.denom), None);
+
+    mock.update_credit_account(&account_id, &user, vec![Borrow(uatom_info.to_coin(50))], &[])
+        .unwrap();
+
+    // Atom: 58 + 45 * p = 51 * p
+    let atom_price = mock.query_liquidation_price(&account_id, &uatom_info.denom).unwrap();
+    assert_eq!(atom_price, Decimal::from_ratio(58u128, 6u128));
+
+    let above = mock.query_health_under_scenario(
+        &account_id,
+        vec![RoverCoinPrice {
+            denom: uatom_info.denom.clone(),
+            price: Decimal::from_atomics(10u128, 0).unwrap(),
+        }],
+    );
+    assert!(above.liquidatable);
+    let below = mock.query_health_under_scenario(
+        &account_id,
+        vec![RoverCoinPrice {
+            denom: uatom_info.denom.clone(),
+            price: Decimal::from_atomics(95u128, 1).unwrap(),
+        }],
+    );
+    assert!(!below.liquidatable);
+
+    // Osmo: 234 * p + 45 = 51
+    let osmo_price = mock.query_liquidation_price(&account_id, &uosmo_info.denom).unwrap();
+    assert_eq!(osmo_price, Decimal::from_ratio(6u128, 234u128));
+
+    // Unrelated denom cannot trigger liquidation
+    assert_eq!(mock.query_liquidation_price(&account_id, "ujake"), None);
+}
+
+#[test]
+fn liquidation_price_includes_vault_base_tokens() {
+    let lp_token = lp_token_info();
+    let uatom_info = uatom_info();
+    let leverage_vault = unlocked_vault_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .allowed_coins(&[lp_token.clone(), uatom_info.clone()])
+        .vault_configs(&[leverage_vault.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![lp_token.to_coin(200)],
+        })
+        .build()
+        .unwrap();
+
+    let vault = mock.get_vault(&leverage_vault);
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(lp_token.to_coin(200)),
+            EnterVault {
+                vault,
+                coin: lp_token.to_action_coin(200),
+                min_receive: None,
+            },
+            Borrow(uatom_info.to_coin(1000)),
+        ],
+        &[lp_token.to_coin(200)],
+    )
+    .unwrap();
+
+    // Lp: 900 + 200 * 0.7 * p = 1001
+    let lp_price = mock.query_liquidation_price(&account_id, &lp_token.denom).unwrap();
+    assert_eq!(lp_price, Decimal::from_ratio(101u128, 140u128));
+}
+
+#[test]
+fn liquidation_price_of_18_decimal_denom() {
+    let weth_info = CoinInfo {
+        denom: "wei".to_string(),
+        price: Decimal::from_atomics(1u128, 12).unwrap(),
+        max_ltv: Decimal::from_atomics(7u128, 1).unwrap(),
+        liquidation_threshold: Decimal::from_atomics(78u128, 2).unwrap(),
+        liquidation_bonus: Decimal::from_atomics(12u128, 2).unwrap(),
+    };
+    let uatom_info = uatom_info();
+
+    // 1000 ETH, above the amounts `Decimal` can hold
+    let deposit_amount = 1_000_000_000_000_000_000_000u128;
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .allowed_coins(&[weth_info.clone(), uatom_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![weth_info.to_coin(deposit_amount)],
+        })
+        .build()
+        .unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![Deposit(weth_info.to_coin(deposit_amount)), Borrow(uatom_info.to_coin(100_000_000))],
+        &[weth_info.to_coin(deposit_amount)],
+    )
+    .unwrap();
+
+    // Wei: 90_000_000 + 10^21 * 0.78 * p = 100_000_001
+    let wei_price = mock.query_liquidation_price(&account_id, &weth_info.denom).unwrap();
+    assert_eq!(wei_price, Decimal::from_ratio(10_000_001u128, 780_000_000_000_000_000_000u128));
+}
+
 fn find_by_denom<'a>(denom: &'a str, shares: &'a [DebtAmount]) -> &'a DebtAmount {
     shares.iter().find(|item| item.denom == *denom).unwrap()
 }
@@ -1,4 +1,4 @@
-use cosmwasm_std::{coin, Decimal};
+use cosmwasm_std::{coin, Decimal, Uint128};
 use mars_rover::{
     adapters::vault::{VaultBase, VaultConfig},
     msg::instantiate::VaultInstantiateConfig,
@@ -89,6 +89,7 @@ fn raises_on_invalid_vaults_addr() {
                     max_ltv: Default::default(),
                     liquidation_threshold: Default::default(),
                     whitelisted: false,
+                    lp_underlying_denoms: None,
                 },
             }),
         )
@@ -262,3 +263,10 @@ fn max_close_factor_validated() {
         panic!("Should have thrown an error: Max close factor should be below 1");
     }
 }
+
+#[test]
+fn max_vault_positions_set_on_instantiate() {
+    let mock = MockEnv::new().max_vault_positions(3).build().unwrap();
+    let res = mock.query_config();
+    assert_eq!(Uint128::new(3), res.max_vault_positions);
+}
@@ -0,0 +1,90 @@
+use cosmwasm_std::{Addr, Decimal, Uint128};
+use mars_rover::msg::execute::Action::{Deposit, Leverage};
+
+use crate::helpers::{get_coin, get_debt, uatom_info, uosmo_info, AccountToFund, MockEnv};
+
+pub mod helpers;
+
+#[test]
+fn leverage_to_target() {
+    let uosmo_info = uosmo_info();
+    let uatom_info = uatom_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .allowed_coins(&[uosmo_info.clone(), uatom_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![uosmo_info.to_coin(400)],
+        })
+        .build()
+        .unwrap();
+
+    let account_id = mock.create_credit_account(&user).unwrap();
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(uosmo_info.to_coin(400)),
+            Leverage {
+                collateral_denom: uosmo_info.denom.clone(),
+                borrow_denom: uatom_info.denom.clone(),
+                target_leverage: Decimal::from_atomics(2u128, 0).unwrap(),
+                slippage: Decimal::percent(5),
+            },
+        ],
+        &[uosmo_info.to_coin(400)],
+    )
+    .unwrap();
+
+    // Collateral: 400 uosmo * 0.25 = 100
+    // Value to borrow: (2 * 100 - 100) / (1 - 0.05 + 2 * 0.05) = 95.23 -> 95 uatom
+    let positions = mock.query_positions(&account_id);
+    let debt = get_debt(&uatom_info.denom, &positions.debts);
+    assert_eq!(debt.amount, Uint128::new(95 + 1)); // +1 added by mock red bank
+
+    // Mock swapper always returns 1337 uosmo
+    let osmo_balance = get_coin(&uosmo_info.denom, &positions.deposits);
+    assert_eq!(osmo_balance.amount, Uint128::new(400 + 1337));
+    assert!(positions.deposits.iter().all(|c| c.denom != uatom_info.denom));
+}
+
+#[test]
+fn leverage_capped_by_max_ltv() {
+    let uosmo_info = uosmo_info();
+    let uatom_info = uatom_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .allowed_coins(&[uosmo_info.clone(), uatom_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![uosmo_info.to_coin(400)],
+        })
+        .build()
+        .unwrap();
+
+    let account_id = mock.create_credit_account(&user).unwrap();
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(uosmo_info.to_coin(400)),
+            Leverage {
+                collateral_denom: uosmo_info.denom.clone(),
+                borrow_denom: uatom_info.denom.clone(),
+                target_leverage: Decimal::from_atomics(10u128, 0).unwrap(),
+                slippage: Decimal::percent(5),
+            },
+        ],
+        &[uosmo_info.to_coin(400)],
+    )
+    .unwrap();
+
+    // Uncapped: (10 * 100 - 100) / (1 - 0.05 + 10 * 0.05) = 620.68
+    // Max LTV adjusted collateral: 100 * 0.7 = 70, less a rounding buffer of 3
+    // Capped: 67 / (1 - 0.7 * 0.95) = 200 uatom
+    let positions = mock.query_positions(&account_id);
+    let debt = get_debt(&uatom_info.denom, &positions.debts);
+    assert_eq!(debt.amount, Uint128::new(200 + 1)); // +1 added by mock red bank
+}
@@ -5,7 +5,10 @@ use mars_rover::{
         ContractError,
         ContractError::{AboveMaxLTV, LiquidationNotProfitable, NotLiquidatable},
     },
-    msg::execute::Action::{Borrow, Deposit, EnterVault, LiquidateCoin},
+    msg::{
+        execute::Action::{Borrow, Deposit, EnterVault, LiquidateCoin},
+        query::{LiquidationLimit, LiquidationRequest},
+    },
 };
 
 use crate::helpers::{
@@ -96,6 +99,7 @@ fn vault_positions_contribute_to_health() {
             EnterVault {
                 vault,
                 coin: lp_token.to_action_coin(200),
+                min_receive: None,
             },
             Borrow(atom_info.to_coin(14)),
         ],
@@ -468,6 +472,17 @@ fn debt_amount_adjusted_to_close_factor_max() {
 
     let liquidator_account_id = mock.create_credit_account(&liquidator).unwrap();
 
+    let preview = mock
+        .query_liquidation_preview(
+            &liquidatee_account_id,
+            uatom_info.to_coin(50),
+            LiquidationRequest::Coin(uosmo_info.denom.clone()),
+        )
+        .unwrap();
+    assert_eq!(preview.debt, uatom_info.to_coin(10));
+    assert_eq!(preview.request, uosmo_info.to_coin(264));
+    assert_eq!(preview.limit, LiquidationLimit::CloseFactor);
+
     mock.update_credit_account(
         &liquidator_account_id,
         &liquidator,
@@ -546,6 +561,17 @@ fn debt_amount_adjusted_to_total_debt_for_denom() {
 
     let liquidator_account_id = mock.create_credit_account(&liquidator).unwrap();
 
+    let preview = mock
+        .query_liquidation_preview(
+            &liquidatee_account_id,
+            ujake_info.to_coin(50),
+            LiquidationRequest::Coin(uosmo_info.denom.clone()),
+        )
+        .unwrap();
+    assert_eq!(preview.debt, ujake_info.to_coin(11));
+    assert_eq!(preview.request, uosmo_info.to_coin(116));
+    assert_eq!(preview.limit, LiquidationLimit::TotalDebt);
+
     mock.update_credit_account(
         &liquidator_account_id,
         &liquidator,
@@ -620,6 +646,17 @@ fn debt_amount_adjusted_to_max_allowed_by_request_coin() {
 
     let liquidator_account_id = mock.create_credit_account(&liquidator).unwrap();
 
+    let preview = mock
+        .query_liquidation_preview(
+            &liquidatee_account_id,
+            uatom_info.to_coin(50),
+            LiquidationRequest::Coin(uosmo_info.denom.clone()),
+        )
+        .unwrap();
+    assert_eq!(preview.debt, uatom_info.to_coin(3));
+    assert_eq!(preview.request, uosmo_info.to_coin(264));
+    assert_eq!(preview.limit, LiquidationLimit::CollateralBalance);
+
     mock.update_credit_account(
         &liquidator_account_id,
         &liquidator,
@@ -693,6 +730,17 @@ fn debt_amount_no_adjustment() {
 
     let liquidator_account_id = mock.create_credit_account(&liquidator).unwrap();
 
+    let preview = mock
+        .query_liquidation_preview(
+            &liquidatee_account_id,
+            uatom_info.to_coin(10),
+            LiquidationRequest::Coin(uosmo_info.denom.clone()),
+        )
+        .unwrap();
+    assert_eq!(preview.debt, uatom_info.to_coin(10));
+    assert_eq!(preview.request, uosmo_info.to_coin(240));
+    assert_eq!(preview.limit, LiquidationLimit::RequestedAmount);
+
     mock.update_credit_account(
         &liquidator_account_id,
         &liquidator,
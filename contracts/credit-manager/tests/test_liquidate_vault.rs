@@ -1,11 +1,14 @@
 use cosmwasm_std::{
-    Addr, Decimal, OverflowError, OverflowOperation::Sub, StdError::NotFound, Uint128,
+    coin, Addr, Decimal, OverflowError, OverflowOperation::Sub, StdError::NotFound, Uint128,
 };
 use mars_mock_oracle::msg::CoinPrice;
 use mars_rover::{
     adapters::vault::{VaultBase, VaultPositionType},
     error::ContractError,
-    msg::execute::Action::{Borrow, Deposit, EnterVault, LiquidateVault, RequestVaultUnlock},
+    msg::{
+        execute::Action::{Borrow, Deposit, EnterVault, LiquidateVault, RequestVaultUnlock},
+        query::{LiquidationLimit, LiquidationRequest},
+    },
 };
 
 use crate::helpers::{
@@ -95,6 +98,7 @@ fn liquidatee_is_not_liquidatable() {
             EnterVault {
                 vault,
                 coin: lp_token.to_action_coin(200),
+                min_receive: None,
             },
         ],
         &[lp_token.to_coin(200)],
@@ -153,6 +157,7 @@ fn liquidator_does_not_have_debt_coin_in_credit_account() {
             EnterVault {
                 vault,
                 coin: lp_token.to_action_coin(200),
+                min_receive: None,
             },
             Borrow(ujake.to_coin(175)),
         ],
@@ -217,6 +222,7 @@ fn wrong_position_type_sent_for_unlocked_vault() {
             EnterVault {
                 vault,
                 coin: lp_token.to_action_coin(200),
+                min_receive: None,
             },
         ],
         &[lp_token.to_coin(200)],
@@ -282,6 +288,7 @@ fn wrong_position_type_sent_for_locked_vault() {
             EnterVault {
                 vault,
                 coin: lp_token.to_action_coin(200),
+                min_receive: None,
             },
         ],
         &[lp_token.to_coin(200)],
@@ -340,6 +347,7 @@ fn liquidate_unlocked_vault() {
             EnterVault {
                 vault,
                 coin: lp_token.to_action_coin(200),
+                min_receive: None,
             },
             Borrow(ujake.to_coin(175)),
         ],
@@ -354,6 +362,20 @@ fn liquidate_unlocked_vault() {
 
     let liquidator_account_id = mock.create_credit_account(&liquidator).unwrap();
 
+    let preview = mock
+        .query_liquidation_preview(
+            &liquidatee_account_id,
+            ujake.to_coin(10),
+            LiquidationRequest::Vault {
+                request_vault: VaultBase::new(mock.get_vault(&leverage_vault).address),
+                position_type: VaultPositionType::UNLOCKED,
+            },
+        )
+        .unwrap();
+    assert_eq!(preview.debt, ujake.to_coin(10));
+    assert_eq!(preview.request, coin(115_000, leverage_vault.vault_token_denom.clone()));
+    assert_eq!(preview.limit, LiquidationLimit::RequestedAmount);
+
     mock.update_credit_account(
         &liquidator_account_id,
         &liquidator,
@@ -426,6 +448,7 @@ fn liquidate_locked_vault() {
             EnterVault {
                 vault,
                 coin: lp_token.to_action_coin(80),
+                min_receive: None,
             },
             Borrow(atom.to_coin(700)),
         ],
@@ -515,6 +538,7 @@ fn liquidate_unlocking_liquidation_order() {
             EnterVault {
                 vault: vault.clone(),
                 coin: lp_token.to_action_coin(200),
+                min_receive: None,
             },
             Borrow(ujake.to_coin(175)),
             RequestVaultUnlock {
@@ -629,6 +653,7 @@ fn liquidation_calculation_adjustment() {
             EnterVault {
                 vault,
                 coin: lp_token.to_action_coin(200),
+                min_receive: None,
             },
             Borrow(ujake.to_coin(175)),
         ],
@@ -0,0 +1,279 @@
+use cosmwasm_std::{coin, Addr, Decimal, Uint128};
+use mars_mock_oracle::msg::CoinPrice;
+use mars_rover::{
+    adapters::vault::{VaultBase, VaultPositionType},
+    error::ContractError,
+    msg::{
+        execute::Action::{Borrow, Deposit, EnterVault, LiquidateVault},
+        instantiate::ConfigUpdates,
+    },
+};
+
+use crate::helpers::{
+    assert_err, get_coin, get_debt, lp_token_info, ujake_info, unlocked_vault_info, AccountToFund,
+    CoinInfo, MockEnv, VaultTestInfo,
+};
+
+pub mod helpers;
+
+#[test]
+fn can_hold_multiple_vault_positions() {
+    let lp_token = lp_token_info();
+    let leverage_vault = unlocked_vault_info();
+    let (degen_vault_token, degen_vault) = degen_vault_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .allowed_coins(&[lp_token.clone(), degen_vault_token.clone()])
+        .vault_configs(&[leverage_vault.clone(), degen_vault.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![lp_token.to_coin(300), degen_vault_token.to_coin(300)],
+        })
+        .build()
+        .unwrap();
+
+    let lev_vault = mock.get_vault(&leverage_vault);
+    let degen_vault = mock.get_vault(&degen_vault);
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(lp_token.to_coin(200)),
+            EnterVault {
+                vault: lev_vault,
+                coin: lp_token.to_action_coin(200),
+                min_receive: None,
+            },
+            Deposit(degen_vault_token.to_coin(200)),
+            EnterVault {
+                vault: degen_vault,
+                coin: degen_vault_token.to_action_coin(200),
+                min_receive: None,
+            },
+        ],
+        &[lp_token.to_coin(200), degen_vault_token.to_coin(200)],
+    )
+    .unwrap();
+
+    let positions = mock.query_positions(&account_id);
+    assert_eq!(positions.vaults.len(), 2);
+}
+
+#[test]
+fn cannot_exceed_max_vault_positions() {
+    let lp_token = lp_token_info();
+    let leverage_vault = unlocked_vault_info();
+    let (degen_vault_token, degen_vault) = degen_vault_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .allowed_coins(&[lp_token.clone(), degen_vault_token.clone()])
+        .vault_configs(&[leverage_vault.clone(), degen_vault.clone()])
+        .max_vault_positions(1)
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![lp_token.to_coin(300), degen_vault_token.to_coin(300)],
+        })
+        .build()
+        .unwrap();
+
+    let lev_vault = mock.get_vault(&leverage_vault);
+    let degen_vault = mock.get_vault(&degen_vault);
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    let res = mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(lp_token.to_coin(200)),
+            EnterVault {
+                vault: lev_vault,
+                coin: lp_token.to_action_coin(200),
+                min_receive: None,
+            },
+            Deposit(degen_vault_token.to_coin(200)),
+            EnterVault {
+                vault: degen_vault,
+                coin: degen_vault_token.to_action_coin(200),
+                min_receive: None,
+            },
+        ],
+        &[lp_token.to_coin(200), degen_vault_token.to_coin(200)],
+    );
+    assert_err(
+        res,
+        ContractError::ExceedsMaxVaultPositions {
+            new_amount: Uint128::new(2),
+            maximum: Uint128::one(),
+        },
+    );
+}
+
+#[test]
+fn lowered_maximum_does_not_block_existing_positions() {
+    let lp_token = lp_token_info();
+    let leverage_vault = unlocked_vault_info();
+    let (degen_vault_token, degen_vault) = degen_vault_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .allowed_coins(&[lp_token.clone(), degen_vault_token.clone()])
+        .vault_configs(&[leverage_vault.clone(), degen_vault.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![lp_token.to_coin(300), degen_vault_token.to_coin(300)],
+        })
+        .build()
+        .unwrap();
+
+    let lev_vault = mock.get_vault(&leverage_vault);
+    let degen_vault = mock.get_vault(&degen_vault);
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(lp_token.to_coin(200)),
+            EnterVault {
+                vault: lev_vault,
+                coin: lp_token.to_action_coin(200),
+                min_receive: None,
+            },
+            Deposit(degen_vault_token.to_coin(200)),
+            EnterVault {
+                vault: degen_vault,
+                coin: degen_vault_token.to_action_coin(200),
+                min_receive: None,
+            },
+        ],
+        &[lp_token.to_coin(200), degen_vault_token.to_coin(200)],
+    )
+    .unwrap();
+
+    let owner = Addr::unchecked(mock.query_config().ownership.owner.unwrap());
+    mock.update_config(
+        &owner,
+        ConfigUpdates {
+            max_vault_positions: Some(Uint128::one()),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    // Account is above the new maximum, but does not add to its vault positions
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![Deposit(lp_token.to_coin(100))],
+        &[lp_token.to_coin(100)],
+    )
+    .unwrap();
+
+    let positions = mock.query_positions(&account_id);
+    assert_eq!(positions.vaults.len(), 2);
+}
+
+#[test]
+fn can_liquidate_account_at_max_vault_positions() {
+    let lp_token = lp_token_info();
+    let ujake = ujake_info();
+    let vault_infos = (0..4)
+        .map(|i| VaultTestInfo {
+            vault_token_denom: format!("uleverage-{i}"),
+            ..unlocked_vault_info()
+        })
+        .collect::<Vec<_>>();
+
+    let liquidatee = Addr::unchecked("liquidatee");
+    let liquidator = Addr::unchecked("liquidator");
+    let mut mock = MockEnv::new()
+        .allowed_coins(&[lp_token.clone(), ujake.clone()])
+        .vault_configs(&vault_infos)
+        .max_vault_positions(vault_infos.len() as u128)
+        .fund_account(AccountToFund {
+            addr: liquidatee.clone(),
+            funds: vec![lp_token.to_coin(200)],
+        })
+        .fund_account(AccountToFund {
+            addr: liquidator.clone(),
+            funds: vec![ujake.to_coin(10)],
+        })
+        .build()
+        .unwrap();
+
+    let liquidatee_account_id = mock.create_credit_account(&liquidatee).unwrap();
+    let mut actions = vec![Deposit(lp_token.to_coin(200))];
+    for info in &vault_infos {
+        actions.push(EnterVault {
+            vault: mock.get_vault(info),
+            coin: lp_token.to_action_coin(50),
+            min_receive: None,
+        });
+    }
+    actions.push(Borrow(ujake.to_coin(175)));
+    mock.update_credit_account(
+        &liquidatee_account_id,
+        &liquidatee,
+        actions,
+        &[lp_token.to_coin(200)],
+    )
+    .unwrap();
+    assert_eq!(mock.query_positions(&liquidatee_account_id).vaults.len(), vault_infos.len());
+
+    mock.price_change(CoinPrice {
+        denom: ujake.denom.clone(),
+        price: Decimal::from_atomics(20u128, 0).unwrap(),
+    });
+    assert!(mock.query_health(&liquidatee_account_id).liquidatable);
+
+    let liquidator_account_id = mock.create_credit_account(&liquidator).unwrap();
+    mock.update_credit_account(
+        &liquidator_account_id,
+        &liquidator,
+        vec![
+            Deposit(ujake.to_coin(10)),
+            LiquidateVault {
+                liquidatee_account_id: liquidatee_account_id.clone(),
+                debt_coin: ujake.to_coin(10),
+                request_vault: VaultBase::new(mock.get_vault(&vault_infos[0]).address),
+                position_type: VaultPositionType::UNLOCKED,
+            },
+        ],
+        &[ujake.to_coin(10)],
+    )
+    .unwrap();
+
+    // Liquidatee keeps a position in every vault, with the liquidated vault partially redeemed
+    let position = mock.query_positions(&liquidatee_account_id);
+    assert_eq!(position.vaults.len(), vault_infos.len());
+    let jake_debt = get_debt("ujake", &position.debts);
+    assert_eq!(jake_debt.amount, Uint128::new(166));
+
+    let position = mock.query_positions(&liquidator_account_id);
+    let lp = get_coin(&lp_token.denom, &position.deposits);
+    assert_eq!(lp.amount, Uint128::new(23));
+}
+
+fn degen_vault_info() -> (CoinInfo, VaultTestInfo) {
+    let degen_vault_token = CoinInfo {
+        denom: "udegen452".to_string(),
+        price: Decimal::from_atomics(121u128, 3).unwrap(),
+        max_ltv: Decimal::from_atomics(4u128, 1).unwrap(),
+        liquidation_threshold: Decimal::from_atomics(5u128, 1).unwrap(),
+        liquidation_bonus: Decimal::from_atomics(2u128, 1).unwrap(),
+    };
+    let degen_vault = VaultTestInfo {
+        vault_token_denom: "udegen".to_string(),
+        lockup: None,
+        base_token_denom: degen_vault_token.denom.clone(),
+        deposit_cap: coin(10_000_000, "uusdc"),
+        max_ltv: Decimal::from_atomics(6u128, 1).unwrap(),
+        liquidation_threshold: Decimal::from_atomics(7u128, 1).unwrap(),
+        whitelisted: true,
+    };
+    (degen_vault_token, degen_vault)
+}
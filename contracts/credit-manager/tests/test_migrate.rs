@@ -0,0 +1,44 @@
+use cosmwasm_std::{
+    testing::{mock_dependencies, mock_env},
+    Empty, Uint128,
+};
+use cw2::set_contract_version;
+use mars_credit_manager::{contract::migrate, state::MAX_VAULT_POSITIONS};
+use mars_rover::error::ContractError;
+
+const CONTRACT_NAME: &str = "crates.io:mars-credit-manager";
+
+#[test]
+fn migrate_seeds_max_vault_positions_with_previous_limit() {
+    let mut deps = mock_dependencies();
+    set_contract_version(deps.as_mut().storage, CONTRACT_NAME, "1.0.0").unwrap();
+
+    migrate(deps.as_mut(), mock_env(), Empty {}).unwrap();
+
+    let max_vault_positions = MAX_VAULT_POSITIONS.load(deps.as_ref().storage).unwrap();
+    assert_eq!(max_vault_positions, Uint128::one());
+}
+
+#[test]
+fn migrate_keeps_configured_max_vault_positions() {
+    let mut deps = mock_dependencies();
+    set_contract_version(deps.as_mut().storage, CONTRACT_NAME, "1.0.0").unwrap();
+    MAX_VAULT_POSITIONS.save(deps.as_mut().storage, &Uint128::new(4)).unwrap();
+
+    migrate(deps.as_mut(), mock_env(), Empty {}).unwrap();
+
+    let max_vault_positions = MAX_VAULT_POSITIONS.load(deps.as_ref().storage).unwrap();
+    assert_eq!(max_vault_positions, Uint128::new(4));
+}
+
+#[test]
+fn cannot_migrate_from_current_version() {
+    let mut deps = mock_dependencies();
+    set_contract_version(deps.as_mut().storage, CONTRACT_NAME, env!("CARGO_PKG_VERSION")).unwrap();
+
+    let err = migrate(deps.as_mut(), mock_env(), Empty {}).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Migration("Cannot upgrade from the current or newer version".to_string())
+    );
+}
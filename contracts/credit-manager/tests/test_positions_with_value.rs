@@ -0,0 +1,142 @@
+use cosmwasm_std::{Addr, Decimal, Uint128};
+use mars_rover::msg::{
+    execute::Action::{Borrow, Deposit, EnterVault},
+    instantiate::ConfigUpdates,
+    query::CollateralValue,
+};
+
+use crate::helpers::{
+    lp_token_info, uatom_info, unlocked_vault_info, uosmo_info, AccountToFund, MockEnv,
+};
+
+pub mod helpers;
+
+#[test]
+fn positions_are_valued_individually() {
+    let lp_token = lp_token_info();
+    let atom = uatom_info();
+    let osmo = uosmo_info();
+    let leverage_vault = unlocked_vault_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .allowed_coins(&[lp_token.clone(), atom.clone(), osmo.clone()])
+        .vault_configs(&[leverage_vault.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![lp_token.to_coin(200), osmo.to_coin(300)],
+        })
+        .build()
+        .unwrap();
+
+    let vault = mock.get_vault(&leverage_vault);
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(lp_token.to_coin(200)),
+            Deposit(osmo.to_coin(300)),
+            EnterVault {
+                vault,
+                coin: lp_token.to_action_coin(100),
+                min_receive: None,
+            },
+            Borrow(atom.to_coin(50)),
+        ],
+        &[lp_token.to_coin(200), osmo.to_coin(300)],
+    )
+    .unwrap();
+
+    let res = mock.query_positions_with_value(&account_id);
+    assert_eq!(res.account_id, account_id);
+
+    assert_eq!(res.deposits.len(), 3);
+    let atom_deposit = find_collateral(&atom.denom, &res.deposits);
+    assert_eq!(atom_deposit.coin.amount, Uint128::new(50));
+    assert_eq!(atom_deposit.coin.price, atom.price);
+    assert_eq!(atom_deposit.coin.value, Uint128::new(50));
+    assert_eq!(atom_deposit.max_ltv_adjusted_value, Uint128::new(41));
+    assert_eq!(atom_deposit.liquidation_threshold_adjusted_value, Uint128::new(45));
+
+    let lp_deposit = find_collateral(&lp_token.denom, &res.deposits);
+    assert_eq!(lp_deposit.coin.amount, Uint128::new(100));
+    assert_eq!(lp_deposit.coin.value, Uint128::new(987));
+    assert_eq!(lp_deposit.max_ltv, lp_token.max_ltv);
+    assert_eq!(lp_deposit.max_ltv_adjusted_value, Uint128::new(621));
+    assert_eq!(lp_deposit.liquidation_threshold_adjusted_value, Uint128::new(671));
+
+    let osmo_deposit = find_collateral(&osmo.denom, &res.deposits);
+    assert_eq!(osmo_deposit.coin.value, Uint128::new(75));
+    assert_eq!(osmo_deposit.max_ltv_adjusted_value, Uint128::new(52));
+    assert_eq!(osmo_deposit.liquidation_threshold_adjusted_value, Uint128::new(58));
+
+    assert_eq!(res.debts.len(), 1);
+    let atom_debt = res.debts.first().unwrap();
+    assert_eq!(atom_debt.denom, atom.denom);
+    assert_eq!(atom_debt.amount, Uint128::new(51));
+    assert_eq!(atom_debt.value, Uint128::new(51));
+
+    assert_eq!(res.vaults.len(), 1);
+    let vault_value = res.vaults.first().unwrap();
+    assert_eq!(vault_value.vault_coins.coin.denom, lp_token.denom);
+    assert_eq!(vault_value.vault_coins.coin.amount, Uint128::new(100));
+    assert_eq!(vault_value.vault_coins.coin.value, Uint128::new(987));
+    assert_eq!(vault_value.vault_coins.max_ltv, leverage_vault.max_ltv);
+    assert_eq!(vault_value.vault_coins.max_ltv_adjusted_value, Uint128::new(592));
+    assert_eq!(vault_value.vault_coins.liquidation_threshold_adjusted_value, Uint128::new(690));
+    assert!(vault_value.unlocking.is_empty());
+
+    let health = mock.query_health(&account_id);
+    assert_eq!(health.total_collateral_value, Uint128::new(2099));
+    assert_eq!(health.max_ltv_adjusted_collateral, Uint128::new(1306));
+    assert_eq!(health.liquidation_threshold_adjusted_collateral, Uint128::new(1464));
+    assert_eq!(health.total_debt_value, Uint128::new(51));
+}
+
+#[test]
+fn delisted_deposits_report_zero_max_ltv() {
+    let osmo = uosmo_info();
+    let atom = uatom_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .allowed_coins(&[osmo.clone(), atom.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![osmo.to_coin(300)],
+        })
+        .build()
+        .unwrap();
+
+    let account_id = mock.create_credit_account(&user).unwrap();
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![Deposit(osmo.to_coin(300))],
+        &[osmo.to_coin(300)],
+    )
+    .unwrap();
+
+    // Remove uosmo from the coin whitelist
+    let res = mock.query_config();
+    mock.update_config(
+        &Addr::unchecked(res.ownership.owner.unwrap()),
+        ConfigUpdates {
+            allowed_coins: Some(vec![atom.denom]),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    let res = mock.query_positions_with_value(&account_id);
+    let osmo_deposit = find_collateral(&osmo.denom, &res.deposits);
+    assert_eq!(osmo_deposit.max_ltv, Decimal::zero());
+    assert_eq!(osmo_deposit.max_ltv_adjusted_value, Uint128::zero());
+    assert_eq!(osmo_deposit.liquidation_threshold_adjusted_value, Uint128::new(58));
+}
+
+fn find_collateral<'a>(denom: &'a str, values: &'a [CollateralValue]) -> &'a CollateralValue {
+    values.iter().find(|v| v.coin.denom == denom).unwrap()
+}
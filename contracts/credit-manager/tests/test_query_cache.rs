@@ -0,0 +1,253 @@
+use std::collections::{HashMap, HashSet};
+
+use cosmwasm_std::{coins, Addr, Coin, Decimal, Uint128};
+use mars_health::{AssetParams, HealthComputer, HealthResponse, VaultCollateral, VaultParams};
+use mars_mock_oracle::msg::CoinPrice;
+use mars_rover::msg::{
+    execute::Action::{Borrow, Deposit, EnterVault, LiquidateCoin},
+    query::{CoinPrice as RoverCoinPrice, LiquidationRequest},
+};
+
+use crate::helpers::{
+    get_coin, get_debt, lp_token_info, uatom_info, unlocked_vault_info, uosmo_info, AccountToFund,
+    CoinInfo, MockEnv, VaultTestInfo,
+};
+
+pub mod helpers;
+
+/// Health computed by the contract reads each price & market once per query through a cache.
+/// Holding the same denom as a deposit, a debt and a vault base token must give the same result
+/// as computing health with every price & market looked up independently.
+#[test]
+fn cached_health_matches_uncached_computation() {
+    let uosmo_info = uosmo_info();
+    let mut uatom_info = uatom_info();
+    let lp_token = lp_token_info();
+    let leverage_vault = unlocked_vault_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .allowed_coins(&[uosmo_info.clone(), uatom_info.clone(), lp_token.clone()])
+        .vault_configs(&[leverage_vault.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![uosmo_info.to_coin(300), uatom_info.to_coin(50), lp_token.to_coin(200)],
+        })
+        .build()
+        .unwrap();
+    let vault = mock.get_vault(&leverage_vault);
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(uosmo_info.to_coin(300)),
+            Deposit(uatom_info.to_coin(50)),
+            Deposit(lp_token.to_coin(200)),
+            EnterVault {
+                vault,
+                coin: lp_token.to_action_coin(120),
+                min_receive: None,
+            },
+            Borrow(uatom_info.to_coin(40)),
+        ],
+        &[uosmo_info.to_coin(300), uatom_info.to_coin(50), lp_token.to_coin(200)],
+    )
+    .unwrap();
+
+    let coin_infos = [uosmo_info.clone(), uatom_info.clone(), lp_token.clone()];
+    let health = mock.query_health(&account_id);
+    assert_eq!(health, uncached_health(&mock, &account_id, &coin_infos, &leverage_vault));
+    assert!(health.max_ltv_health_factor.is_some());
+
+    // Overrides seed the cache & take precedence over the oracle
+    uatom_info.price = Decimal::from_atomics(25u128, 1).unwrap();
+    let coin_infos = [uosmo_info, uatom_info.clone(), lp_token];
+    let scenario_health = mock.query_health_under_scenario(
+        &account_id,
+        vec![RoverCoinPrice {
+            denom: uatom_info.denom.clone(),
+            price: uatom_info.price,
+        }],
+    );
+    let expected = uncached_health(&mock, &account_id, &coin_infos, &leverage_vault);
+    assert_eq!(scenario_health, expected);
+    assert_ne!(scenario_health, health);
+
+    mock.price_change(CoinPrice {
+        denom: uatom_info.denom.clone(),
+        price: uatom_info.price,
+    });
+    assert_eq!(mock.query_health(&account_id), expected);
+}
+
+/// Liquidation previews & executions price the liquidatee's positions through a cache. What the
+/// preview reports must be what the liquidation transfers, and the health that follows must
+/// match an independent computation.
+#[test]
+fn cached_liquidation_preview_matches_execution() {
+    let uosmo_info = uosmo_info();
+    let mut uatom_info = uatom_info();
+    let lp_token = lp_token_info();
+    let leverage_vault = unlocked_vault_info();
+
+    let liquidator = Addr::unchecked("liquidator");
+    let liquidatee = Addr::unchecked("liquidatee");
+    let mut mock = MockEnv::new()
+        .allowed_coins(&[uosmo_info.clone(), uatom_info.clone(), lp_token.clone()])
+        .vault_configs(&[leverage_vault.clone()])
+        .fund_account(AccountToFund {
+            addr: liquidatee.clone(),
+            funds: vec![uosmo_info.to_coin(300), lp_token.to_coin(20)],
+        })
+        .fund_account(AccountToFund {
+            addr: liquidator.clone(),
+            funds: coins(10, uatom_info.denom.clone()),
+        })
+        .build()
+        .unwrap();
+    let vault = mock.get_vault(&leverage_vault);
+    let liquidatee_account_id = mock.create_credit_account(&liquidatee).unwrap();
+
+    mock.update_credit_account(
+        &liquidatee_account_id,
+        &liquidatee,
+        vec![
+            Deposit(uosmo_info.to_coin(300)),
+            Deposit(lp_token.to_coin(20)),
+            EnterVault {
+                vault,
+                coin: lp_token.to_action_coin(20),
+                min_receive: None,
+            },
+            Borrow(uatom_info.to_coin(100)),
+        ],
+        &[uosmo_info.to_coin(300), lp_token.to_coin(20)],
+    )
+    .unwrap();
+
+    uatom_info.price = Decimal::from_atomics(20u128, 0).unwrap();
+    mock.price_change(CoinPrice {
+        denom: uatom_info.denom.clone(),
+        price: uatom_info.price,
+    });
+
+    let coin_infos = [uosmo_info.clone(), uatom_info.clone(), lp_token];
+    let health = mock.query_health(&liquidatee_account_id);
+    assert!(health.liquidatable);
+    assert_eq!(
+        health,
+        uncached_health(&mock, &liquidatee_account_id, &coin_infos, &leverage_vault)
+    );
+
+    let preview = mock
+        .query_liquidation_preview(
+            &liquidatee_account_id,
+            uatom_info.to_coin(10),
+            LiquidationRequest::Coin(uosmo_info.denom.clone()),
+        )
+        .unwrap();
+    assert!(!preview.debt.amount.is_zero());
+    assert!(!preview.request.amount.is_zero());
+
+    let debt_before = get_debt("uatom", &mock.query_positions(&liquidatee_account_id).debts);
+    let liquidator_account_id = mock.create_credit_account(&liquidator).unwrap();
+    mock.update_credit_account(
+        &liquidator_account_id,
+        &liquidator,
+        vec![
+            Deposit(uatom_info.to_coin(10)),
+            LiquidateCoin {
+                liquidatee_account_id: liquidatee_account_id.clone(),
+                debt_coin: uatom_info.to_coin(10),
+                request_coin_denom: uosmo_info.denom.clone(),
+            },
+        ],
+        &[uatom_info.to_coin(10)],
+    )
+    .unwrap();
+
+    let position = mock.query_positions(&liquidator_account_id);
+    let osmo_balance = get_coin("uosmo", &position.deposits);
+    assert_eq!(osmo_balance, preview.request);
+    let atom_balance = get_coin("uatom", &position.deposits);
+    assert_eq!(atom_balance.amount, Uint128::new(10) - preview.debt.amount);
+
+    let position = mock.query_positions(&liquidatee_account_id);
+    let atom_debt = get_debt("uatom", &position.debts);
+    assert_eq!(atom_debt.amount, debt_before.amount - preview.debt.amount);
+
+    assert_eq!(
+        mock.query_health(&liquidatee_account_id),
+        uncached_health(&mock, &liquidatee_account_id, &coin_infos, &leverage_vault)
+    );
+}
+
+/// Health computed from the account's positions, with prices & risk params taken straight from
+/// the coin and vault infos the oracle & red bank were configured with
+fn uncached_health(
+    mock: &MockEnv,
+    account_id: &str,
+    coin_infos: &[CoinInfo],
+    vault_info: &VaultTestInfo,
+) -> HealthResponse {
+    let positions = mock.query_positions(account_id);
+
+    let vaults = positions
+        .vaults
+        .iter()
+        .map(|v| {
+            let vault_coins = v.amount.unlocked() + v.amount.locked();
+            VaultCollateral {
+                vault_address: v.vault.address.to_string(),
+                base_amount: mock.query_preview_redeem(&v.vault.clone().into(), vault_coins),
+                unlocking: v.amount.unlocking().positions().iter().map(|u| u.coin.amount).collect(),
+            }
+        })
+        .collect::<Vec<_>>();
+    let vault_params = positions
+        .vaults
+        .iter()
+        .map(|v| {
+            (
+                v.vault.address.to_string(),
+                VaultParams {
+                    base_token: vault_info.base_token_denom.clone(),
+                    max_ltv: vault_info.max_ltv,
+                    liquidation_threshold: vault_info.liquidation_threshold,
+                    whitelisted: vault_info.whitelisted,
+                },
+            )
+        })
+        .collect();
+
+    let computer = HealthComputer {
+        deposits: positions.deposits,
+        debts: positions
+            .debts
+            .iter()
+            .map(|d| Coin {
+                denom: d.denom.clone(),
+                amount: d.amount,
+            })
+            .collect(),
+        vaults,
+        prices: coin_infos.iter().map(|c| (c.denom.clone(), c.price)).collect::<HashMap<_, _>>(),
+        asset_params: coin_infos
+            .iter()
+            .map(|c| {
+                (
+                    c.denom.clone(),
+                    AssetParams {
+                        max_ltv: c.max_ltv,
+                        liquidation_threshold: c.liquidation_threshold,
+                    },
+                )
+            })
+            .collect(),
+        vault_params,
+        allowed_coins: coin_infos.iter().map(|c| c.denom.clone()).collect::<HashSet<_>>(),
+    };
+    computer.compute_health().unwrap().into()
+}
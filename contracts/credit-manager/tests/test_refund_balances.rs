@@ -74,6 +74,7 @@ fn refund_coin_balances_when_no_balances() {
             EnterVault {
                 vault,
                 coin: lp_token.to_action_coin(200),
+                min_receive: None,
             },
             RefundAllCoinBalances {},
         ],
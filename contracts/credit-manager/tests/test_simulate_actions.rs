@@ -0,0 +1,838 @@
+use cosmwasm_std::{Addr, Decimal, Uint128};
+use cw_utils::Duration;
+use mars_mock_oracle::msg::CoinPrice;
+use mars_mock_vault::contract::STARTING_VAULT_SHARES;
+use mars_rover::{
+    adapters::vault::{VaultBase, VaultPositionType},
+    msg::{
+        execute::Action::{
+            self, Borrow, CancelVaultUnlock, CloseAccount, Deleverage, Deposit, EnterVault,
+            ExitVault, ExitVaultUnlocked, Leverage, LiquidateCoin, LiquidateVault,
+            ProvideLiquidity, RefundAllCoinBalances, Repay, RequestVaultUnlock, SwapExactIn,
+            SwapExactOut, Withdraw, WithdrawLiquidity,
+        },
+        query::SimulateActionsResponse,
+    },
+};
+use mars_swapper_mock::contract::{MOCK_SWAP_EXACT_OUT_COST, MOCK_SWAP_RESULT};
+use mars_zapper_mock::contract::STARTING_LP_POOL_TOKENS;
+
+use crate::helpers::{
+    get_debt, locked_vault_info, lp_token_info, uatom_info, ujake_info, unlocked_vault_info,
+    uosmo_info, AccountToFund, MockEnv,
+};
+
+pub mod helpers;
+
+#[test]
+fn simulation_does_not_alter_state() {
+    let coin_info = uosmo_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .allowed_coins(&[coin_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![coin_info.to_coin(300)],
+        })
+        .build()
+        .unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![Deposit(coin_info.to_coin(300))],
+        &[coin_info.to_coin(300)],
+    )
+    .unwrap();
+
+    let positions_before = mock.query_positions(&account_id);
+    let health_before = mock.query_health(&account_id);
+
+    let res = mock.simulate_actions(&account_id, vec![Borrow(coin_info.to_coin(50))]).unwrap();
+    assert_eq!(res.positions.deposits, vec![coin_info.to_coin(350)]);
+    assert_eq!(res.positions.debts.len(), 1);
+    assert_eq!(res.positions.debts.first().unwrap().amount, Uint128::new(50));
+    assert!(res.passes_max_ltv_check);
+
+    assert_eq!(mock.query_positions(&account_id), positions_before);
+    assert_eq!(mock.query_health(&account_id), health_before);
+}
+
+#[test]
+fn simulated_swap_matches_execution() {
+    let atom_info = uatom_info();
+    let osmo_info = uosmo_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .allowed_coins(&[atom_info.clone(), osmo_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![atom_info.to_coin(10_000)],
+        })
+        .build()
+        .unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![Deposit(atom_info.to_coin(10_000))],
+        &[atom_info.to_coin(10_000)],
+    )
+    .unwrap();
+
+    let swap = SwapExactIn {
+        coin_in: atom_info.to_action_coin(5_000),
+        denom_out: osmo_info.denom.clone(),
+        slippage: Decimal::from_atomics(6u128, 1).unwrap(),
+        min_receive: None,
+    };
+
+    let res = mock.simulate_actions(&account_id, vec![swap.clone()]).unwrap();
+    assert_eq!(
+        res.positions.deposits,
+        vec![atom_info.to_coin(5_000), osmo_info.to_coin(MOCK_SWAP_RESULT.u128())]
+    );
+
+    mock.update_credit_account(&account_id, &user, vec![swap], &[]).unwrap();
+
+    assert_eq!(mock.query_positions(&account_id), res.positions);
+    assert_eq!(mock.query_health(&account_id), res.health);
+}
+
+#[test]
+fn simulated_vault_entry_matches_execution() {
+    let lp_token = lp_token_info();
+    let leverage_vault = unlocked_vault_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .allowed_coins(&[lp_token.clone()])
+        .vault_configs(&[leverage_vault.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![lp_token.to_coin(300)],
+        })
+        .build()
+        .unwrap();
+
+    let vault = mock.get_vault(&leverage_vault);
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    let actions = vec![
+        Deposit(lp_token.to_coin(200)),
+        EnterVault {
+            vault,
+            coin: lp_token.to_action_coin(23),
+            min_receive: None,
+        },
+    ];
+
+    let res = mock.simulate_actions(&account_id, actions.clone()).unwrap();
+    assert!(res.passes_max_ltv_check);
+
+    mock.update_credit_account(&account_id, &user, actions, &[lp_token.to_coin(200)]).unwrap();
+
+    assert_eq!(mock.query_positions(&account_id), res.positions);
+    assert_eq!(mock.query_health(&account_id), res.health);
+}
+
+#[test]
+fn reports_failing_max_ltv_check() {
+    let coin_info = uosmo_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .allowed_coins(&[coin_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![coin_info.to_coin(300)],
+        })
+        .build()
+        .unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![Deposit(coin_info.to_coin(300))],
+        &[coin_info.to_coin(300)],
+    )
+    .unwrap();
+
+    let res = mock.simulate_actions(&account_id, vec![Borrow(coin_info.to_coin(1_000))]).unwrap();
+    assert!(!res.passes_max_ltv_check);
+    assert!(res.health.above_max_ltv);
+
+    let res =
+        mock.update_credit_account(&account_id, &user, vec![Borrow(coin_info.to_coin(1_000))], &[]);
+    assert!(res.is_err());
+}
+
+#[test]
+fn simulated_withdraw_repay_and_refund_match_execution() {
+    let coin_info = uosmo_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .allowed_coins(&[coin_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![coin_info.to_coin(300)],
+        })
+        .build()
+        .unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![Deposit(coin_info.to_coin(300)), Borrow(coin_info.to_coin(50))],
+        &[coin_info.to_coin(300)],
+    )
+    .unwrap();
+
+    let res = assert_simulation_matches_execution(
+        &mut mock,
+        &account_id,
+        &user,
+        vec![Repay(coin_info.to_action_coin(20)), Withdraw(coin_info.to_coin(100))],
+    );
+    assert_eq!(res.positions.deposits, vec![coin_info.to_coin(230)]);
+    assert_eq!(res.positions.debts.first().unwrap().amount, Uint128::new(31));
+
+    let res = assert_simulation_matches_execution(
+        &mut mock,
+        &account_id,
+        &user,
+        vec![Repay(coin_info.to_action_coin_full_balance()), RefundAllCoinBalances {}],
+    );
+    assert!(res.positions.deposits.is_empty());
+    assert!(res.positions.debts.is_empty());
+}
+
+#[test]
+fn simulated_swap_exact_out_matches_execution() {
+    let atom_info = uatom_info();
+    let osmo_info = uosmo_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .allowed_coins(&[atom_info.clone(), osmo_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![atom_info.to_coin(100)],
+        })
+        .build()
+        .unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![Deposit(atom_info.to_coin(100))],
+        &[atom_info.to_coin(100)],
+    )
+    .unwrap();
+
+    let res = assert_simulation_matches_execution(
+        &mut mock,
+        &account_id,
+        &user,
+        vec![SwapExactOut {
+            coin_out: osmo_info.to_coin(500),
+            denom_in: atom_info.denom.clone(),
+            max_in: Uint128::new(100),
+        }],
+    );
+    assert_eq!(
+        res.positions.deposits,
+        vec![atom_info.to_coin(100 - MOCK_SWAP_EXACT_OUT_COST.u128()), osmo_info.to_coin(500)]
+    );
+}
+
+#[test]
+fn simulated_liquidity_provision_and_withdrawal_match_execution() {
+    let atom = uatom_info();
+    let osmo = uosmo_info();
+    let lp_token = lp_token_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .allowed_coins(&[lp_token.clone(), atom.clone(), osmo.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![atom.to_coin(100), osmo.to_coin(50)],
+        })
+        .build()
+        .unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![Deposit(atom.to_coin(100)), Deposit(osmo.to_coin(50))],
+        &[atom.to_coin(100), osmo.to_coin(50)],
+    )
+    .unwrap();
+
+    let res = assert_simulation_matches_execution(
+        &mut mock,
+        &account_id,
+        &user,
+        vec![ProvideLiquidity {
+            coins_in: vec![atom.to_action_coin(100), osmo.to_action_coin(50)],
+            lp_token_out: lp_token.denom.clone(),
+            minimum_receive: Uint128::zero(),
+        }],
+    );
+    assert_eq!(res.positions.deposits, vec![lp_token.to_coin(STARTING_LP_POOL_TOKENS.u128())]);
+
+    let res = assert_simulation_matches_execution(
+        &mut mock,
+        &account_id,
+        &user,
+        vec![WithdrawLiquidity {
+            lp_token: lp_token.to_action_coin_full_balance(),
+            min_receive: None,
+        }],
+    );
+    assert_eq!(res.positions.deposits, vec![atom.to_coin(100), osmo.to_coin(50)]);
+}
+
+#[test]
+fn simulated_vault_exit_matches_execution() {
+    let lp_token = lp_token_info();
+    let leverage_vault = unlocked_vault_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .allowed_coins(&[lp_token.clone()])
+        .vault_configs(&[leverage_vault.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![lp_token.to_coin(200)],
+        })
+        .build()
+        .unwrap();
+
+    let vault = mock.get_vault(&leverage_vault);
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(lp_token.to_coin(200)),
+            EnterVault {
+                vault: vault.clone(),
+                coin: lp_token.to_action_coin(200),
+                min_receive: None,
+            },
+        ],
+        &[lp_token.to_coin(200)],
+    )
+    .unwrap();
+
+    let res = assert_simulation_matches_execution(
+        &mut mock,
+        &account_id,
+        &user,
+        vec![ExitVault {
+            vault,
+            amount: STARTING_VAULT_SHARES.multiply_ratio(1u128, 4u128),
+            min_receive: None,
+        }],
+    );
+    assert_eq!(res.positions.deposits, vec![lp_token.to_coin(50)]);
+}
+
+#[test]
+fn simulated_vault_unlocks_match_execution() {
+    let lp_token = lp_token_info();
+    let leverage_vault = locked_vault_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .allowed_coins(&[lp_token.clone()])
+        .vault_configs(&[leverage_vault.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![lp_token.to_coin(200)],
+        })
+        .build()
+        .unwrap();
+
+    let vault = mock.get_vault(&leverage_vault);
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(lp_token.to_coin(200)),
+            EnterVault {
+                vault: vault.clone(),
+                coin: lp_token.to_action_coin(200),
+                min_receive: None,
+            },
+        ],
+        &[lp_token.to_coin(200)],
+    )
+    .unwrap();
+
+    // The vault assigns unlocking position ids on execution, so only the amounts are compared
+    let request_unlock = vec![RequestVaultUnlock {
+        vault: vault.clone(),
+        amount: STARTING_VAULT_SHARES,
+    }];
+    let res = mock.simulate_actions(&account_id, request_unlock.clone()).unwrap();
+    mock.update_credit_account(&account_id, &user, request_unlock, &[]).unwrap();
+
+    let positions = mock.query_positions(&account_id);
+    let simulated = res.positions.vaults.first().unwrap().amount.clone();
+    let executed = positions.vaults.first().unwrap().amount.clone();
+    assert_eq!(simulated.locked(), executed.locked());
+    assert_eq!(simulated.unlocking().total(), executed.unlocking().total());
+    assert_eq!(mock.query_health(&account_id), res.health);
+
+    let id = executed.unlocking().positions().first().unwrap().id;
+    let res = assert_simulation_matches_execution(
+        &mut mock,
+        &account_id,
+        &user,
+        vec![CancelVaultUnlock {
+            vault: vault.clone(),
+            id,
+        }],
+    );
+    assert_eq!(res.positions.vaults.first().unwrap().amount.locked(), STARTING_VAULT_SHARES);
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![RequestVaultUnlock {
+            vault: vault.clone(),
+            amount: STARTING_VAULT_SHARES,
+        }],
+        &[],
+    )
+    .unwrap();
+
+    mock.app.update_block(|block| {
+        if let Duration::Time(s) = leverage_vault.lockup.unwrap() {
+            block.time = block.time.plus_seconds(s);
+            block.height += 1;
+        }
+    });
+
+    let positions = mock.query_positions(&account_id);
+    let id = positions.vaults.first().unwrap().amount.unlocking().positions().first().unwrap().id;
+    let res = assert_simulation_matches_execution(
+        &mut mock,
+        &account_id,
+        &user,
+        vec![ExitVaultUnlocked {
+            id,
+            vault,
+            min_receive: None,
+        }],
+    );
+    assert!(res.positions.vaults.is_empty());
+    assert_eq!(res.positions.deposits, vec![lp_token.to_coin(200)]);
+}
+
+#[test]
+fn simulated_deleverage_matches_execution() {
+    let uosmo_info = uosmo_info();
+    let uatom_info = uatom_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .allowed_coins(&[uosmo_info.clone(), uatom_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![uatom_info.to_coin(400)],
+        })
+        .build()
+        .unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(uatom_info.to_coin(400)),
+            Borrow(uosmo_info.to_coin(1300)),
+            Withdraw(uosmo_info.to_coin(1300)),
+        ],
+        &[uatom_info.to_coin(400)],
+    )
+    .unwrap();
+
+    let res = assert_simulation_matches_execution(
+        &mut mock,
+        &account_id,
+        &user,
+        vec![Deleverage {
+            target_max_ltv_health_factor: Decimal::from_atomics(11u128, 1).unwrap(),
+            collateral_denom: uatom_info.denom.clone(),
+            debt_denom: uosmo_info.denom.clone(),
+            slippage: Decimal::percent(5),
+        }],
+    );
+    assert_eq!(
+        res.positions.deposits,
+        vec![uatom_info.to_coin(400 - 134), uosmo_info.to_coin(1337 - 1270)]
+    );
+}
+
+#[test]
+fn simulated_leverage_matches_execution() {
+    let osmo_info = uosmo_info();
+    let atom_info = uatom_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .allowed_coins(&[osmo_info.clone(), atom_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![osmo_info.to_coin(400)],
+        })
+        .build()
+        .unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    let actions = vec![
+        Deposit(osmo_info.to_coin(400)),
+        Leverage {
+            collateral_denom: osmo_info.denom.clone(),
+            borrow_denom: atom_info.denom.clone(),
+            target_leverage: Decimal::from_atomics(2u128, 0).unwrap(),
+            slippage: Decimal::percent(5),
+        },
+    ];
+
+    let res = mock.simulate_actions(&account_id, actions.clone()).unwrap();
+    assert!(res.passes_max_ltv_check);
+    assert_eq!(res.positions.deposits, vec![osmo_info.to_coin(400 + MOCK_SWAP_RESULT.u128())]);
+    let debt = get_debt(&atom_info.denom, &res.positions.debts);
+    assert_eq!(debt.amount, Uint128::new(95));
+
+    mock.update_credit_account(&account_id, &user, actions, &[osmo_info.to_coin(400)]).unwrap();
+
+    let positions = mock.query_positions(&account_id);
+    assert_eq!(positions.deposits, res.positions.deposits);
+    let debt = get_debt(&atom_info.denom, &positions.debts);
+    assert_eq!(debt.amount, Uint128::new(95 + 1)); // +1 added by mock red bank
+}
+
+#[test]
+fn simulated_close_account_matches_execution() {
+    let uosmo_info = uosmo_info();
+    let uatom_info = uatom_info();
+    let lp_token = lp_token_info();
+    let leverage_vault = unlocked_vault_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .allowed_coins(&[lp_token.clone(), uosmo_info.clone(), uatom_info.clone()])
+        .vault_configs(&[leverage_vault.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![lp_token.to_coin(200), uatom_info.to_coin(300)],
+        })
+        .build()
+        .unwrap();
+
+    let vault = mock.get_vault(&leverage_vault);
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(lp_token.to_coin(200)),
+            Deposit(uatom_info.to_coin(300)),
+            EnterVault {
+                vault,
+                coin: lp_token.to_action_coin(200),
+                min_receive: None,
+            },
+            Borrow(uosmo_info.to_coin(100)),
+        ],
+        &[lp_token.to_coin(200), uatom_info.to_coin(300)],
+    )
+    .unwrap();
+
+    let res = assert_simulation_matches_execution(
+        &mut mock,
+        &account_id,
+        &user,
+        vec![CloseAccount {
+            target_denom: uosmo_info.denom.clone(),
+            slippage: Decimal::percent(5),
+        }],
+    );
+    assert!(res.positions.deposits.is_empty());
+    assert!(res.positions.debts.is_empty());
+    assert!(res.positions.vaults.is_empty());
+}
+
+#[test]
+fn simulated_vault_zap_matches_execution() {
+    let atom = uatom_info();
+    let osmo = uosmo_info();
+    let lp_token = lp_token_info();
+    let leverage_vault = unlocked_vault_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .allowed_coins(&[lp_token.clone(), atom.clone(), osmo.clone()])
+        .vault_configs(&[leverage_vault.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![atom.to_coin(300), osmo.to_coin(300)],
+        })
+        .build()
+        .unwrap();
+
+    let vault = mock.get_vault(&leverage_vault);
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(atom.to_coin(300)),
+            Deposit(osmo.to_coin(300)),
+            ProvideLiquidity {
+                coins_in: vec![atom.to_action_coin(100), osmo.to_action_coin(100)],
+                lp_token_out: lp_token.denom.clone(),
+                minimum_receive: Uint128::zero(),
+            },
+        ],
+        &[atom.to_coin(300), osmo.to_coin(300)],
+    )
+    .unwrap();
+
+    let res = assert_simulation_matches_execution(
+        &mut mock,
+        &account_id,
+        &user,
+        vec![EnterVaultWithZap {
+            vault,
+            coins_in: vec![
+                lp_token.to_action_coin(10_000),
+                atom.to_action_coin(10),
+                osmo.to_action_coin(10),
+            ],
+            slippage: Decimal::from_atomics(6u128, 1).unwrap(),
+            min_vault_tokens: STARTING_VAULT_SHARES,
+        }],
+    );
+    assert_eq!(res.positions.vaults.first().unwrap().amount.unlocked(), STARTING_VAULT_SHARES);
+}
+
+#[test]
+fn simulated_vault_exit_into_matches_execution() {
+    let atom = uatom_info();
+    let osmo = uosmo_info();
+    let lp_token = lp_token_info();
+    let leverage_vault = unlocked_vault_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .allowed_coins(&[lp_token.clone(), atom.clone(), osmo.clone()])
+        .vault_configs(&[leverage_vault.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![atom.to_coin(300), osmo.to_coin(300)],
+        })
+        .build()
+        .unwrap();
+
+    let vault = mock.get_vault(&leverage_vault);
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(atom.to_coin(300)),
+            Deposit(osmo.to_coin(300)),
+            ProvideLiquidity {
+                coins_in: vec![atom.to_action_coin(100), osmo.to_action_coin(100)],
+                lp_token_out: lp_token.denom.clone(),
+                minimum_receive: Uint128::zero(),
+            },
+            EnterVault {
+                vault: vault.clone(),
+                coin: lp_token.to_action_coin(100_000),
+                min_receive: None,
+            },
+        ],
+        &[atom.to_coin(300), osmo.to_coin(300)],
+    )
+    .unwrap();
+
+    let res = assert_simulation_matches_execution(
+        &mut mock,
+        &account_id,
+        &user,
+        vec![ExitVaultInto {
+            vault,
+            amount: STARTING_VAULT_SHARES,
+            denom_out: osmo.denom.clone(),
+            slippage: Decimal::from_atomics(6u128, 1).unwrap(),
+        }],
+    );
+    assert!(res.positions.vaults.is_empty());
+}
+
+#[test]
+fn simulated_coin_liquidation_matches_execution() {
+    let uosmo_info = uosmo_info();
+    let uatom_info = uatom_info();
+    let liquidator = Addr::unchecked("liquidator");
+    let liquidatee = Addr::unchecked("liquidatee");
+    let mut mock = MockEnv::new()
+        .max_close_factor(Decimal::from_atomics(1u128, 1).unwrap())
+        .allowed_coins(&[uosmo_info.clone(), uatom_info.clone()])
+        .fund_account(AccountToFund {
+            addr: liquidatee.clone(),
+            funds: vec![uosmo_info.to_coin(300)],
+        })
+        .fund_account(AccountToFund {
+            addr: liquidator.clone(),
+            funds: vec![uatom_info.to_coin(10)],
+        })
+        .build()
+        .unwrap();
+    let liquidatee_account_id = mock.create_credit_account(&liquidatee).unwrap();
+
+    mock.update_credit_account(
+        &liquidatee_account_id,
+        &liquidatee,
+        vec![Deposit(uosmo_info.to_coin(300)), Borrow(uatom_info.to_coin(100))],
+        &[uosmo_info.to_coin(300)],
+    )
+    .unwrap();
+
+    mock.price_change(CoinPrice {
+        denom: uatom_info.denom.clone(),
+        price: Decimal::from_atomics(55u128, 1).unwrap(),
+    });
+
+    let liquidator_account_id = mock.create_credit_account(&liquidator).unwrap();
+
+    let actions = vec![
+        Deposit(uatom_info.to_coin(10)),
+        LiquidateCoin {
+            liquidatee_account_id,
+            debt_coin: uatom_info.to_coin(10),
+            request_coin_denom: uosmo_info.denom.clone(),
+        },
+    ];
+
+    let res = mock.simulate_actions(&liquidator_account_id, actions.clone()).unwrap();
+    assert_eq!(res.positions.deposits, vec![uosmo_info.to_coin(240)]);
+
+    mock.update_credit_account(
+        &liquidator_account_id,
+        &liquidator,
+        actions,
+        &[uatom_info.to_coin(10)],
+    )
+    .unwrap();
+
+    assert_eq!(mock.query_positions(&liquidator_account_id), res.positions);
+    assert_eq!(mock.query_health(&liquidator_account_id), res.health);
+}
+
+#[test]
+fn simulated_vault_liquidation_matches_execution() {
+    let lp_token = lp_token_info();
+    let ujake = ujake_info();
+    let leverage_vault = unlocked_vault_info();
+
+    let liquidatee = Addr::unchecked("liquidatee");
+    let liquidator = Addr::unchecked("liquidator");
+
+    let mut mock = MockEnv::new()
+        .allowed_coins(&[lp_token.clone(), ujake.clone()])
+        .vault_configs(&[leverage_vault.clone()])
+        .fund_account(AccountToFund {
+            addr: liquidatee.clone(),
+            funds: vec![lp_token.to_coin(200)],
+        })
+        .fund_account(AccountToFund {
+            addr: liquidator.clone(),
+            funds: vec![ujake.to_coin(10)],
+        })
+        .build()
+        .unwrap();
+
+    let vault = mock.get_vault(&leverage_vault);
+    let liquidatee_account_id = mock.create_credit_account(&liquidatee).unwrap();
+
+    mock.update_credit_account(
+        &liquidatee_account_id,
+        &liquidatee,
+        vec![
+            Deposit(lp_token.to_coin(200)),
+            EnterVault {
+                vault: vault.clone(),
+                coin: lp_token.to_action_coin(200),
+                min_receive: None,
+            },
+            Borrow(ujake.to_coin(175)),
+        ],
+        &[lp_token.to_coin(200)],
+    )
+    .unwrap();
+
+    mock.price_change(CoinPrice {
+        denom: ujake.denom.clone(),
+        price: Decimal::from_atomics(20u128, 0).unwrap(),
+    });
+
+    let liquidator_account_id = mock.create_credit_account(&liquidator).unwrap();
+
+    let actions = vec![
+        Deposit(ujake.to_coin(10)),
+        LiquidateVault {
+            liquidatee_account_id,
+            debt_coin: ujake.to_coin(10),
+            request_vault: VaultBase::new(vault.address),
+            position_type: VaultPositionType::UNLOCKED,
+        },
+    ];
+
+    let res = mock.simulate_actions(&liquidator_account_id, actions.clone()).unwrap();
+    assert_eq!(res.positions.deposits, vec![lp_token.to_coin(23)]);
+
+    mock.update_credit_account(&liquidator_account_id, &liquidator, actions, &[ujake.to_coin(10)])
+        .unwrap();
+
+    assert_eq!(mock.query_positions(&liquidator_account_id), res.positions);
+    assert_eq!(mock.query_health(&liquidator_account_id), res.health);
+}
+
+/// Simulates actions that need no funds sent, executes them and asserts both end in the same state
+fn assert_simulation_matches_execution(
+    mock: &mut MockEnv,
+    account_id: &str,
+    user: &Addr,
+    actions: Vec<Action>,
+) -> SimulateActionsResponse {
+    let res = mock.simulate_actions(account_id, actions.clone()).unwrap();
+    mock.update_credit_account(account_id, user, actions, &[]).unwrap();
+
+    assert_eq!(mock.query_positions(account_id), res.positions);
+    assert_eq!(mock.query_health(account_id), res.health);
+    res
+}
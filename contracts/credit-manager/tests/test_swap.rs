@@ -2,11 +2,11 @@ use cosmwasm_std::{coins, Addr, Coin, Decimal, OverflowError, OverflowOperation:
 use mars_rover::{
     error::ContractError,
     msg::execute::{
-        Action::{Deposit, SwapExactIn},
+        Action::{Deposit, SwapExactIn, SwapExactOut},
         ActionAmount, ActionCoin,
     },
 };
-use mars_swapper_mock::contract::MOCK_SWAP_RESULT;
+use mars_swapper_mock::contract::{MOCK_SWAP_EXACT_OUT_COST, MOCK_SWAP_RESULT};
 
 use crate::helpers::{assert_err, uatom_info, uosmo_info, AccountToFund, MockEnv};
 
@@ -29,6 +29,7 @@ fn only_token_owner_can_swap_for_account() {
             },
             denom_out: "osmo".to_string(),
             slippage: Decimal::from_atomics(6u128, 1).unwrap(),
+            min_receive: None,
         }],
         &[],
     );
@@ -57,6 +58,7 @@ fn denom_out_must_be_whitelisted() {
             coin_in: osmo_info.to_action_coin(10_000),
             denom_out: "ujake".to_string(),
             slippage: Decimal::from_atomics(6u128, 1).unwrap(),
+            min_receive: None,
         }],
         &[],
     );
@@ -81,6 +83,7 @@ fn no_amount_sent() {
             coin_in: osmo_info.to_action_coin(0),
             denom_out: atom_info.denom,
             slippage: Decimal::from_atomics(6u128, 1).unwrap(),
+            min_receive: None,
         }],
         &[],
     );
@@ -105,6 +108,7 @@ fn user_has_zero_balance_for_swap_req() {
             coin_in: osmo_info.to_action_coin(10_000),
             denom_out: atom_info.denom,
             slippage: Decimal::from_atomics(6u128, 1).unwrap(),
+            min_receive: None,
         }],
         &[],
     );
@@ -144,6 +148,7 @@ fn user_does_not_have_enough_balance_for_swap_req() {
                 coin_in: osmo_info.to_action_coin(10_000),
                 denom_out: atom_info.denom,
                 slippage: Decimal::from_atomics(6u128, 1).unwrap(),
+                min_receive: None,
             },
         ],
         &[osmo_info.to_coin(100)],
@@ -159,6 +164,85 @@ fn user_does_not_have_enough_balance_for_swap_req() {
     )
 }
 
+#[test]
+fn swap_fails_below_min_receive() {
+    let atom_info = uatom_info();
+    let osmo_info = uosmo_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .allowed_coins(&[osmo_info.clone(), atom_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![Coin::new(10_000u128, atom_info.denom.clone())],
+        })
+        .build()
+        .unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    let res = mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(atom_info.to_coin(10_000)),
+            SwapExactIn {
+                coin_in: atom_info.to_action_coin(10_000),
+                denom_out: osmo_info.denom.clone(),
+                slippage: Decimal::from_atomics(6u128, 1).unwrap(),
+                min_receive: Some(MOCK_SWAP_RESULT + Uint128::one()),
+            },
+        ],
+        &[atom_info.to_coin(10_000)],
+    );
+
+    assert_err(
+        res,
+        ContractError::ReceivedBelowMinimum {
+            denom: osmo_info.denom,
+            received: MOCK_SWAP_RESULT,
+            min_receive: MOCK_SWAP_RESULT + Uint128::one(),
+        },
+    )
+}
+
+#[test]
+fn swap_success_at_min_receive() {
+    let atom_info = uatom_info();
+    let osmo_info = uosmo_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .allowed_coins(&[osmo_info.clone(), atom_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![Coin::new(10_000u128, atom_info.denom.clone())],
+        })
+        .build()
+        .unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(atom_info.to_coin(10_000)),
+            SwapExactIn {
+                coin_in: atom_info.to_action_coin(10_000),
+                denom_out: osmo_info.denom.clone(),
+                slippage: Decimal::from_atomics(6u128, 1).unwrap(),
+                min_receive: Some(MOCK_SWAP_RESULT),
+            },
+        ],
+        &[atom_info.to_coin(10_000)],
+    )
+    .unwrap();
+
+    let position = mock.query_positions(&account_id);
+    assert_eq!(position.deposits.len(), 1);
+    assert_eq!(position.deposits.first().unwrap().denom, osmo_info.denom);
+    assert_eq!(position.deposits.first().unwrap().amount, MOCK_SWAP_RESULT);
+}
+
 #[test]
 fn swap_success_with_specified_amount() {
     let atom_info = uatom_info();
@@ -187,6 +271,7 @@ fn swap_success_with_specified_amount() {
                 coin_in: atom_info.to_action_coin(10_000),
                 denom_out: osmo_info.denom.clone(),
                 slippage: Decimal::from_atomics(6u128, 1).unwrap(),
+                min_receive: None,
             },
         ],
         &[atom_info.to_coin(10_000)],
@@ -234,6 +319,7 @@ fn swap_success_with_amount_none() {
                 coin_in: atom_info.to_action_coin_full_balance(),
                 denom_out: osmo_info.denom.clone(),
                 slippage: Decimal::from_atomics(6u128, 1).unwrap(),
+                min_receive: None,
             },
         ],
         &[atom_info.to_coin(10_000)],
@@ -252,3 +338,89 @@ fn swap_success_with_amount_none() {
     assert_eq!(position.deposits.first().unwrap().denom, osmo_info.denom);
     assert_eq!(position.deposits.first().unwrap().amount, MOCK_SWAP_RESULT);
 }
+
+#[test]
+fn swap_exact_out_requires_max_in_balance() {
+    let atom_info = uatom_info();
+    let osmo_info = uosmo_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .allowed_coins(&[osmo_info.clone(), atom_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![Coin::new(100u128, atom_info.denom.clone())],
+        })
+        .build()
+        .unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    let res = mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(atom_info.to_coin(100)),
+            SwapExactOut {
+                coin_out: osmo_info.to_coin(500),
+                denom_in: atom_info.denom.clone(),
+                max_in: Uint128::new(101),
+            },
+        ],
+        &[atom_info.to_coin(100)],
+    );
+
+    assert_err(
+        res,
+        ContractError::Overflow(OverflowError {
+            operation: Sub,
+            operand1: "100".to_string(),
+            operand2: "101".to_string(),
+        }),
+    )
+}
+
+#[test]
+fn swap_exact_out_success() {
+    let atom_info = uatom_info();
+    let osmo_info = uosmo_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .allowed_coins(&[osmo_info.clone(), atom_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![Coin::new(100u128, atom_info.denom.clone())],
+        })
+        .build()
+        .unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(atom_info.to_coin(100)),
+            SwapExactOut {
+                coin_out: osmo_info.to_coin(500),
+                denom_in: atom_info.denom.clone(),
+                max_in: Uint128::new(100),
+            },
+        ],
+        &[atom_info.to_coin(100)],
+    )
+    .unwrap();
+
+    // assert rover balance, with the unspent input refunded by the swapper
+    let atom_balance = mock.query_balance(&mock.rover, &atom_info.denom).amount;
+    let osmo_balance = mock.query_balance(&mock.rover, &osmo_info.denom).amount;
+    assert_eq!(atom_balance, Uint128::new(100) - MOCK_SWAP_EXACT_OUT_COST);
+    assert_eq!(osmo_balance, Uint128::new(500));
+
+    // assert account position
+    let position = mock.query_positions(&account_id);
+    assert_eq!(position.deposits.len(), 2);
+    let atom_deposit = position.deposits.iter().find(|c| c.denom == atom_info.denom).unwrap();
+    assert_eq!(atom_deposit.amount, Uint128::new(100) - MOCK_SWAP_EXACT_OUT_COST);
+    let osmo_deposit = position.deposits.iter().find(|c| c.denom == osmo_info.denom).unwrap();
+    assert_eq!(osmo_deposit.amount, Uint128::new(500));
+}
@@ -39,6 +39,7 @@ fn only_owner_can_update_config() {
             red_bank: None,
             max_close_factor: None,
             max_unlocking_positions: None,
+            max_vault_positions: None,
             swapper: None,
             vault_configs: None,
             zapper: None,
@@ -70,6 +71,7 @@ fn raises_on_invalid_vaults_config() {
             red_bank: None,
             max_close_factor: None,
             max_unlocking_positions: None,
+            max_vault_positions: None,
             swapper: None,
             vault_configs: Some(vec![vault_config]),
             zapper: None,
@@ -97,6 +99,7 @@ fn raises_on_invalid_vaults_config() {
             red_bank: None,
             max_close_factor: None,
             max_unlocking_positions: None,
+            max_vault_positions: None,
             swapper: None,
             vault_configs: Some(vec![vault_config]),
             zapper: None,
@@ -123,6 +126,7 @@ fn raises_on_invalid_vaults_config() {
             red_bank: None,
             max_close_factor: None,
             max_unlocking_positions: None,
+            max_vault_positions: None,
             swapper: None,
             vault_configs: Some(vec![vault_a, vault_b]),
             zapper: None,
@@ -152,6 +156,7 @@ fn update_config_works_with_full_config() {
     let new_zapper = ZapperBase::new("new_zapper".to_string());
     let new_close_factor = Decimal::from_atomics(32u128, 2).unwrap();
     let new_unlocking_max = Uint128::new(321);
+    let new_vault_max = Uint128::new(4);
     let new_swapper = SwapperBase::new("new_swapper".to_string());
 
     mock.update_config(
@@ -163,6 +168,7 @@ fn update_config_works_with_full_config() {
             red_bank: Some(new_red_bank.clone()),
             max_close_factor: Some(new_close_factor),
             max_unlocking_positions: Some(new_unlocking_max),
+            max_vault_positions: Some(new_vault_max),
             swapper: Some(new_swapper.clone()),
             vault_configs: Some(new_vault_configs.clone()),
             zapper: Some(new_zapper.clone()),
@@ -213,6 +219,9 @@ fn update_config_works_with_full_config() {
     assert_eq!(new_config.max_unlocking_positions, new_unlocking_max);
     assert_ne!(new_config.max_unlocking_positions, original_config.max_unlocking_positions);
 
+    assert_eq!(new_config.max_vault_positions, new_vault_max);
+    assert_ne!(new_config.max_vault_positions, original_config.max_vault_positions);
+
     assert_eq!(&new_config.swapper, new_swapper.address());
     assert_ne!(new_config.swapper, original_config.swapper);
 }
@@ -253,6 +262,7 @@ fn update_config_works_with_some_config() {
     assert_eq!(new_config.red_bank, original_config.red_bank);
     assert_eq!(new_config.oracle, original_config.oracle);
     assert_eq!(new_config.max_close_factor, original_config.max_close_factor);
+    assert_eq!(new_config.max_vault_positions, original_config.max_vault_positions);
     assert_eq!(new_config.swapper, original_config.swapper);
     assert_eq!(new_config.zapper, original_config.zapper);
     assert_eq!(original_allowed_coins, new_queried_allowed_coins);
@@ -356,6 +366,7 @@ fn raises_on_duplicate_vault_configs() {
             red_bank: None,
             max_close_factor: None,
             max_unlocking_positions: None,
+            max_vault_positions: None,
             swapper: None,
             vault_configs: Some(vec![
                 VaultInstantiateConfig {
@@ -365,6 +376,7 @@ fn raises_on_duplicate_vault_configs() {
                         max_ltv: Default::default(),
                         liquidation_threshold: Default::default(),
                         whitelisted: true,
+                        lp_underlying_denoms: None,
                     },
                 },
                 VaultInstantiateConfig {
@@ -374,6 +386,7 @@ fn raises_on_duplicate_vault_configs() {
                         max_ltv: Default::default(),
                         liquidation_threshold: Default::default(),
                         whitelisted: false,
+                        lp_underlying_denoms: None,
                     },
                 },
             ]),
@@ -406,6 +419,7 @@ fn raises_on_duplicate_coin_configs() {
             red_bank: None,
             max_close_factor: None,
             max_unlocking_positions: None,
+            max_vault_positions: None,
             swapper: None,
             vault_configs: None,
             zapper: None,
@@ -487,6 +501,7 @@ fn deploy_vault(app: &mut BasicApp) -> VaultInstantiateConfig {
             max_ltv: Decimal::from_atomics(3u128, 1).unwrap(),
             liquidation_threshold: Decimal::from_atomics(5u128, 1).unwrap(),
             whitelisted: false,
+            lp_underlying_denoms: None,
         },
     }
 }
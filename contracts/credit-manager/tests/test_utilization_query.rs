@@ -66,6 +66,7 @@ fn utilization_if_cap_is_base_denom() {
                     denom: base_info.denom.clone(),
                     amount: Exact(Uint128::new(50)),
                 },
+                min_receive: None,
             },
         ],
         &[base_info.to_coin(50)],
@@ -125,6 +126,7 @@ fn utilization_in_other_denom() {
                     denom: jake_info.denom.clone(),
                     amount: Exact(Uint128::new(1_000_000)),
                 },
+                min_receive: None,
             },
         ],
         &[jake_info.to_coin(1_000_000)],
@@ -0,0 +1,148 @@
+use cosmwasm_std::{Addr, Uint128};
+use mars_mock_vault::contract::STARTING_VAULT_SHARES;
+use mars_rover::{
+    error::ContractError,
+    msg::execute::Action::{CancelVaultUnlock, Deposit, EnterVault, RequestVaultUnlock},
+};
+
+use crate::helpers::{assert_err, locked_vault_info, lp_token_info, AccountToFund, MockEnv};
+
+pub mod helpers;
+
+#[test]
+fn only_owner_can_cancel_unlock() {
+    let leverage_vault = locked_vault_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new().vault_configs(&[leverage_vault.clone()]).build().unwrap();
+    let vault = mock.get_vault(&leverage_vault);
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    let bad_guy = Addr::unchecked("bad_guy");
+    let res = mock.update_credit_account(
+        &account_id,
+        &bad_guy,
+        vec![CancelVaultUnlock {
+            vault,
+            id: 1,
+        }],
+        &[],
+    );
+
+    assert_err(
+        res,
+        ContractError::NotTokenOwner {
+            user: bad_guy.into(),
+            account_id,
+        },
+    )
+}
+
+#[test]
+fn unlocking_position_must_exist() {
+    let lp_token = lp_token_info();
+    let leverage_vault = locked_vault_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .allowed_coins(&[lp_token.clone()])
+        .vault_configs(&[leverage_vault.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![lp_token.to_coin(200)],
+        })
+        .build()
+        .unwrap();
+
+    let vault = mock.get_vault(&leverage_vault);
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    let res = mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(lp_token.to_coin(200)),
+            EnterVault {
+                vault: vault.clone(),
+                coin: lp_token.to_action_coin(23),
+                min_receive: None,
+            },
+            CancelVaultUnlock {
+                vault,
+                id: 1,
+            },
+        ],
+        &[lp_token.to_coin(200)],
+    );
+
+    assert_err(res, ContractError::NoPositionMatch("1".to_string()))
+}
+
+#[test]
+fn cancelled_unlock_is_locked_again() {
+    let lp_token = lp_token_info();
+    let leverage_vault = locked_vault_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .allowed_coins(&[lp_token.clone()])
+        .vault_configs(&[leverage_vault.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![lp_token.to_coin(200)],
+        })
+        .build()
+        .unwrap();
+
+    let vault = mock.get_vault(&leverage_vault);
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(lp_token.to_coin(200)),
+            EnterVault {
+                vault: vault.clone(),
+                coin: lp_token.to_action_coin(23),
+                min_receive: None,
+            },
+            RequestVaultUnlock {
+                vault: vault.clone(),
+                amount: STARTING_VAULT_SHARES,
+            },
+        ],
+        &[lp_token.to_coin(200)],
+    )
+    .unwrap();
+
+    let res = mock.query_positions(&account_id);
+    let unlocking = res.vaults.first().unwrap().amount.unlocking();
+    let position_id = unlocking.positions().first().unwrap().id;
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![CancelVaultUnlock {
+            vault: vault.clone(),
+            id: position_id,
+        }],
+        &[],
+    )
+    .unwrap();
+
+    // Assert token's position with Rover
+    let res = mock.query_positions(&account_id);
+    assert_eq!(res.vaults.len(), 1);
+    let amount = &res.vaults.first().unwrap().amount;
+    assert_eq!(amount.locked(), STARTING_VAULT_SHARES);
+    assert!(amount.unlocking().positions().is_empty());
+
+    // Assert Rover's position w/ Vault
+    let res = mock.query_unlocking_positions(&vault, &mock.rover);
+    assert!(res.is_empty());
+    let vault_token_balance = mock.query_balance(&mock.rover, &leverage_vault.vault_token_denom);
+    assert_eq!(vault_token_balance.amount, STARTING_VAULT_SHARES);
+    let redeemable = mock.query_preview_redeem(&vault, STARTING_VAULT_SHARES);
+    assert_eq!(redeemable, Uint128::new(23));
+}
@@ -35,6 +35,7 @@ fn only_account_owner_can_take_action() {
                 denom: "uosmo".to_string(),
                 amount: ActionAmount::Exact(Uint128::new(1)),
             },
+            min_receive: None,
         }],
         &[],
     );
@@ -65,6 +66,7 @@ fn deposit_denom_is_whitelisted() {
         vec![EnterVault {
             vault,
             coin: lp_token.to_action_coin(200),
+            min_receive: None,
         }],
         &[],
     );
@@ -103,6 +105,7 @@ fn vault_is_whitelisted() {
         vec![EnterVault {
             vault: vault.clone(),
             coin: uatom.to_action_coin(200),
+            min_receive: None,
         }],
         &[],
     );
@@ -130,6 +133,7 @@ fn deposited_coin_matches_vault_requirements() {
         vec![EnterVault {
             vault: mock.get_vault(&leverage_vault),
             coin: uatom.to_action_coin(200),
+            min_receive: None,
         }],
         &[],
     );
@@ -166,6 +170,7 @@ fn fails_if_not_enough_funds_for_implied_deposit() {
         vec![EnterVault {
             vault: mock.get_vault(&leverage_vault),
             coin: lp_token.to_action_coin_full_balance(),
+            min_receive: None,
         }],
         &[],
     );
@@ -202,6 +207,7 @@ fn fails_if_not_enough_funds_for_enumerated_deposit() {
         vec![EnterVault {
             vault: mock.get_vault(&leverage_vault),
             coin: lp_token.to_action_coin(200),
+            min_receive: None,
         }],
         &[],
     );
@@ -245,6 +251,7 @@ fn successful_deposit_into_locked_vault() {
             EnterVault {
                 vault: vault.clone(),
                 coin: lp_token.to_action_coin(23),
+                min_receive: None,
             },
         ],
         &[lp_token.to_coin(200)],
@@ -296,6 +303,7 @@ fn successful_deposit_into_unlocked_vault() {
             EnterVault {
                 vault: vault.clone(),
                 coin: lp_token.to_action_coin(23),
+                min_receive: None,
             },
         ],
         &[lp_token.to_coin(200)],
@@ -350,6 +358,7 @@ fn vault_deposit_must_be_under_cap() {
             EnterVault {
                 vault: vault.clone(),
                 coin: lp_token.to_action_coin(700_000),
+                min_receive: None,
             },
         ],
         &[lp_token.to_coin(700_000)],
@@ -367,6 +376,7 @@ fn vault_deposit_must_be_under_cap() {
             EnterVault {
                 vault: vault.clone(),
                 coin: lp_token.to_action_coin(100_000),
+                min_receive: None,
             },
         ],
         &[lp_token.to_coin(100_000)],
@@ -384,6 +394,7 @@ fn vault_deposit_must_be_under_cap() {
             EnterVault {
                 vault,
                 coin: lp_token.to_action_coin(2_500_000),
+                min_receive: None,
             },
         ],
         &[lp_token.to_coin(2_500_000)],
@@ -425,6 +436,7 @@ fn successful_deposit_with_implied_full_balance_amount() {
             EnterVault {
                 vault: vault.clone(),
                 coin: lp_token.to_action_coin_full_balance(),
+                min_receive: None,
             },
         ],
         &[lp_token.to_coin(200)],
@@ -442,3 +454,46 @@ fn successful_deposit_with_implied_full_balance_amount() {
         mock.query_balance(&Addr::unchecked(vault.address), &leverage_vault.base_token_denom);
     assert_eq!(base_denom.amount, Uint128::new(200))
 }
+
+#[test]
+fn enter_vault_below_min_receive() {
+    let lp_token = lp_token_info();
+    let leverage_vault = unlocked_vault_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .allowed_coins(&[lp_token.clone()])
+        .vault_configs(&[leverage_vault.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![lp_token.to_coin(300)],
+        })
+        .build()
+        .unwrap();
+
+    let vault = mock.get_vault(&leverage_vault);
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    let res = mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(lp_token.to_coin(200)),
+            EnterVault {
+                vault,
+                coin: lp_token.to_action_coin(200),
+                min_receive: Some(STARTING_VAULT_SHARES + Uint128::one()),
+            },
+        ],
+        &[lp_token.to_coin(200)],
+    );
+
+    assert_err(
+        res,
+        ContractError::ReceivedBelowMinimum {
+            denom: leverage_vault.vault_token_denom,
+            received: STARTING_VAULT_SHARES,
+            min_receive: STARTING_VAULT_SHARES + Uint128::one(),
+        },
+    );
+}
@@ -0,0 +1,284 @@
+use cosmwasm_std::{coin, Addr, Decimal, Uint128};
+use mars_mock_vault::contract::STARTING_VAULT_SHARES;
+use mars_rover::{
+    error::ContractError,
+    msg::execute::Action::{Deposit, EnterVaultWithZap, ProvideLiquidity},
+};
+use mars_swapper_mock::contract::MOCK_SWAP_RESULT;
+use mars_zapper_mock::contract::STARTING_LP_POOL_TOKENS;
+
+use crate::helpers::{
+    assert_err, get_coin, lp_token_info, uatom_info, ujake_info, unlocked_vault_info, uosmo_info,
+    AccountToFund, MockEnv, VaultTestInfo,
+};
+
+pub mod helpers;
+
+#[test]
+fn coins_in_must_have_an_amount() {
+    let atom = uatom_info();
+    let lp_token = lp_token_info();
+    let leverage_vault = unlocked_vault_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .allowed_coins(&[lp_token, atom.clone()])
+        .vault_configs(&[leverage_vault.clone()])
+        .build()
+        .unwrap();
+
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    let res = mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![EnterVaultWithZap {
+            vault: mock.get_vault(&leverage_vault),
+            coins_in: vec![atom.to_action_coin(0)],
+            slippage: Decimal::from_atomics(6u128, 1).unwrap(),
+            min_vault_tokens: Uint128::zero(),
+        }],
+        &[],
+    );
+
+    assert_err(res, ContractError::NoAmount);
+}
+
+#[test]
+fn provides_liquidity_for_lp_base_token() {
+    let atom = uatom_info();
+    let osmo = uosmo_info();
+    let lp_token = lp_token_info();
+    let leverage_vault = unlocked_vault_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .allowed_coins(&[lp_token.clone(), atom.clone(), osmo.clone()])
+        .vault_configs(&[leverage_vault.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![atom.to_coin(300), osmo.to_coin(300)],
+        })
+        .build()
+        .unwrap();
+
+    let vault = mock.get_vault(&leverage_vault);
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(atom.to_coin(300)),
+            Deposit(osmo.to_coin(300)),
+            ProvideLiquidity {
+                coins_in: vec![atom.to_action_coin(100), osmo.to_action_coin(100)],
+                lp_token_out: lp_token.denom.clone(),
+                minimum_receive: Uint128::zero(),
+            },
+        ],
+        &[atom.to_coin(300), osmo.to_coin(300)],
+    )
+    .unwrap();
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![EnterVaultWithZap {
+            vault,
+            coins_in: vec![atom.to_action_coin(10), osmo.to_action_coin(10)],
+            slippage: Decimal::from_atomics(6u128, 1).unwrap(),
+            min_vault_tokens: STARTING_VAULT_SHARES,
+        }],
+        &[],
+    )
+    .unwrap();
+
+    let positions = mock.query_positions(&account_id);
+    assert_eq!(positions.vaults.len(), 1);
+    assert_eq!(STARTING_VAULT_SHARES, positions.vaults.first().unwrap().amount.unlocked());
+
+    // LP tokens held before the zap are not deposited
+    assert_eq!(positions.deposits.len(), 3);
+    let lp_balance = get_coin(&lp_token.denom, &positions.deposits);
+    assert_eq!(lp_balance.amount, STARTING_LP_POOL_TOKENS);
+    let atom_balance = get_coin(&atom.denom, &positions.deposits);
+    assert_eq!(atom_balance.amount, Uint128::new(190));
+    let osmo_balance = get_coin(&osmo.denom, &positions.deposits);
+    assert_eq!(osmo_balance.amount, Uint128::new(190));
+}
+
+#[test]
+fn deposits_lp_base_token_directly() {
+    let atom = uatom_info();
+    let osmo = uosmo_info();
+    let lp_token = lp_token_info();
+    let leverage_vault = unlocked_vault_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .allowed_coins(&[lp_token.clone(), atom.clone(), osmo.clone()])
+        .vault_configs(&[leverage_vault.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![atom.to_coin(300), osmo.to_coin(300)],
+        })
+        .build()
+        .unwrap();
+
+    let vault = mock.get_vault(&leverage_vault);
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(atom.to_coin(300)),
+            Deposit(osmo.to_coin(300)),
+            ProvideLiquidity {
+                coins_in: vec![atom.to_action_coin(100), osmo.to_action_coin(100)],
+                lp_token_out: lp_token.denom.clone(),
+                minimum_receive: Uint128::zero(),
+            },
+        ],
+        &[atom.to_coin(300), osmo.to_coin(300)],
+    )
+    .unwrap();
+
+    // LP tokens in coins_in are deposited with those received for the underlying, not swapped
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![EnterVaultWithZap {
+            vault,
+            coins_in: vec![
+                lp_token.to_action_coin(10_000),
+                atom.to_action_coin(10),
+                osmo.to_action_coin(10),
+            ],
+            slippage: Decimal::from_atomics(6u128, 1).unwrap(),
+            min_vault_tokens: STARTING_VAULT_SHARES,
+        }],
+        &[],
+    )
+    .unwrap();
+
+    let positions = mock.query_positions(&account_id);
+    assert_eq!(positions.vaults.len(), 1);
+    assert_eq!(STARTING_VAULT_SHARES, positions.vaults.first().unwrap().amount.unlocked());
+
+    assert_eq!(positions.deposits.len(), 3);
+    let lp_balance = get_coin(&lp_token.denom, &positions.deposits);
+    assert_eq!(lp_balance.amount, STARTING_LP_POOL_TOKENS - Uint128::new(10_000));
+    let atom_balance = get_coin(&atom.denom, &positions.deposits);
+    assert_eq!(atom_balance.amount, Uint128::new(190));
+    let osmo_balance = get_coin(&osmo.denom, &positions.deposits);
+    assert_eq!(osmo_balance.amount, Uint128::new(190));
+}
+
+#[test]
+fn swaps_coins_into_base_token() {
+    let jake = ujake_info();
+    let osmo = uosmo_info();
+    let osmo_vault = osmo_vault_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .allowed_coins(&[jake.clone(), osmo.clone()])
+        .vault_configs(&[osmo_vault.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![jake.to_coin(300), osmo.to_coin(300)],
+        })
+        .build()
+        .unwrap();
+
+    let vault = mock.get_vault(&osmo_vault);
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(jake.to_coin(100)),
+            Deposit(osmo.to_coin(50)),
+            EnterVaultWithZap {
+                vault: vault.clone(),
+                coins_in: vec![jake.to_action_coin(100), osmo.to_action_coin(20)],
+                slippage: Decimal::from_atomics(6u128, 1).unwrap(),
+                min_vault_tokens: Uint128::zero(),
+            },
+        ],
+        &[jake.to_coin(100), osmo.to_coin(50)],
+    )
+    .unwrap();
+
+    let positions = mock.query_positions(&account_id);
+    assert_eq!(positions.vaults.len(), 1);
+    let vault_amount = positions.vaults.first().unwrap().amount.unlocked();
+    assert_eq!(STARTING_VAULT_SHARES, vault_amount);
+
+    // Swap result and the given osmo are deposited, the rest of the osmo stays in the account
+    let amount = mock.query_preview_redeem(&vault, vault_amount);
+    assert_eq!(amount, MOCK_SWAP_RESULT + Uint128::new(20));
+    assert_eq!(positions.deposits.len(), 1);
+    let osmo_balance = get_coin(&osmo.denom, &positions.deposits);
+    assert_eq!(osmo_balance.amount, Uint128::new(30));
+}
+
+#[test]
+fn errors_if_below_min_vault_tokens() {
+    let jake = ujake_info();
+    let osmo = uosmo_info();
+    let osmo_vault = osmo_vault_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .allowed_coins(&[jake.clone(), osmo])
+        .vault_configs(&[osmo_vault.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![jake.to_coin(300)],
+        })
+        .build()
+        .unwrap();
+
+    let vault = mock.get_vault(&osmo_vault);
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    let res = mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(jake.to_coin(100)),
+            EnterVaultWithZap {
+                vault,
+                coins_in: vec![jake.to_action_coin(100)],
+                slippage: Decimal::from_atomics(6u128, 1).unwrap(),
+                min_vault_tokens: STARTING_VAULT_SHARES + Uint128::one(),
+            },
+        ],
+        &[jake.to_coin(100)],
+    );
+
+    assert_err(
+        res,
+        ContractError::ReceivedBelowMinimum {
+            denom: osmo_vault.vault_token_denom,
+            received: STARTING_VAULT_SHARES,
+            min_receive: STARTING_VAULT_SHARES + Uint128::one(),
+        },
+    );
+}
+
+fn osmo_vault_info() -> VaultTestInfo {
+    VaultTestInfo {
+        vault_token_denom: "uosmovault".to_string(),
+        lockup: None,
+        base_token_denom: "uosmo".to_string(),
+        deposit_cap: coin(10_000_000, "uusdc"),
+        max_ltv: Decimal::from_atomics(6u128, 1).unwrap(),
+        liquidation_threshold: Decimal::from_atomics(7u128, 1).unwrap(),
+        whitelisted: true,
+    }
+}
@@ -30,6 +30,7 @@ fn only_owner_of_token_can_withdraw_from_vault() {
         vec![ExitVault {
             vault: VaultBase::new("some_vault".to_string()),
             amount: STARTING_VAULT_SHARES,
+            min_receive: None,
         }],
         &[],
     );
@@ -56,6 +57,7 @@ fn can_only_take_action_on_whitelisted_vaults() {
         vec![ExitVault {
             vault: VaultBase::new("not_allowed_vault".to_string()),
             amount: STARTING_VAULT_SHARES,
+            min_receive: None,
         }],
         &[],
     );
@@ -93,6 +95,7 @@ fn no_unlocked_vault_coins_to_withdraw() {
             ExitVault {
                 vault,
                 amount: STARTING_VAULT_SHARES,
+                min_receive: None,
             },
         ],
         &[coin(200, "uatom"), coin(200, "uosmo")],
@@ -135,6 +138,7 @@ fn withdraw_with_unlocked_vault_coins() {
             EnterVault {
                 vault: vault.clone(),
                 coin: lp_token.to_action_coin(100),
+                min_receive: None,
             },
         ],
         &[lp_token.to_coin(200)],
@@ -163,6 +167,7 @@ fn withdraw_with_unlocked_vault_coins() {
         vec![ExitVault {
             vault,
             amount: STARTING_VAULT_SHARES,
+            min_receive: None,
         }],
         &[],
     )
@@ -186,3 +191,51 @@ fn withdraw_with_unlocked_vault_coins() {
 fn get_coin(denom: &str, coins: &[Coin]) -> Coin {
     coins.iter().find(|cv| cv.denom == denom).unwrap().clone()
 }
+
+#[test]
+fn exit_vault_below_min_receive() {
+    let lp_token = lp_token_info();
+    let leverage_vault = unlocked_vault_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .allowed_coins(&[lp_token.clone()])
+        .vault_configs(&[leverage_vault.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![lp_token.to_coin(300)],
+        })
+        .build()
+        .unwrap();
+
+    let vault = mock.get_vault(&leverage_vault);
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    let res = mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(lp_token.to_coin(200)),
+            EnterVault {
+                vault: vault.clone(),
+                coin: lp_token.to_action_coin(100),
+                min_receive: None,
+            },
+            ExitVault {
+                vault,
+                amount: STARTING_VAULT_SHARES,
+                min_receive: Some(Uint128::new(101)),
+            },
+        ],
+        &[lp_token.to_coin(200)],
+    );
+
+    assert_err(
+        res,
+        ContractError::ReceivedBelowMinimum {
+            denom: lp_token.denom,
+            received: Uint128::new(100),
+            min_receive: Uint128::new(101),
+        },
+    );
+}
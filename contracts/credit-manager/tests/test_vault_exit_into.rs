@@ -0,0 +1,176 @@
+use cosmwasm_std::{coin, Addr, Decimal, Uint128};
+use mars_mock_vault::contract::STARTING_VAULT_SHARES;
+use mars_rover::{
+    error::ContractError,
+    msg::execute::Action::{Deposit, EnterVault, ExitVaultInto, ProvideLiquidity},
+};
+use mars_swapper_mock::contract::MOCK_SWAP_RESULT;
+use mars_zapper_mock::contract::STARTING_LP_POOL_TOKENS;
+
+use crate::helpers::{
+    assert_err, get_coin, lp_token_info, uatom_info, unlocked_vault_info, uosmo_info,
+    AccountToFund, MockEnv, VaultTestInfo,
+};
+
+pub mod helpers;
+
+#[test]
+fn denom_out_must_be_whitelisted() {
+    let lp_token = lp_token_info();
+    let leverage_vault = unlocked_vault_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .allowed_coins(&[lp_token])
+        .vault_configs(&[leverage_vault.clone()])
+        .build()
+        .unwrap();
+
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    let res = mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![ExitVaultInto {
+            vault: mock.get_vault(&leverage_vault),
+            amount: STARTING_VAULT_SHARES,
+            denom_out: "ujake".to_string(),
+            slippage: Decimal::from_atomics(6u128, 1).unwrap(),
+        }],
+        &[],
+    );
+
+    assert_err(res, ContractError::NotWhitelisted("ujake".to_string()));
+}
+
+#[test]
+fn swaps_base_token_into_denom_out() {
+    let atom = uatom_info();
+    let osmo = uosmo_info();
+    let atom_vault = atom_vault_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .allowed_coins(&[atom.clone(), osmo.clone()])
+        .vault_configs(&[atom_vault.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![atom.to_coin(300)],
+        })
+        .build()
+        .unwrap();
+
+    let vault = mock.get_vault(&atom_vault);
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(atom.to_coin(300)),
+            EnterVault {
+                vault: vault.clone(),
+                coin: atom.to_action_coin(200),
+                min_receive: None,
+            },
+            ExitVaultInto {
+                vault,
+                amount: STARTING_VAULT_SHARES,
+                denom_out: osmo.denom.clone(),
+                slippage: Decimal::from_atomics(6u128, 1).unwrap(),
+            },
+        ],
+        &[atom.to_coin(300)],
+    )
+    .unwrap();
+
+    let positions = mock.query_positions(&account_id);
+    assert_eq!(positions.vaults.len(), 0);
+
+    // Atom held before exiting is not swapped
+    assert_eq!(positions.deposits.len(), 2);
+    let atom_balance = get_coin(&atom.denom, &positions.deposits);
+    assert_eq!(atom_balance.amount, Uint128::new(100));
+    let osmo_balance = get_coin(&osmo.denom, &positions.deposits);
+    assert_eq!(osmo_balance.amount, MOCK_SWAP_RESULT);
+}
+
+#[test]
+fn withdraws_liquidity_and_swaps_underlying() {
+    let atom = uatom_info();
+    let osmo = uosmo_info();
+    let lp_token = lp_token_info();
+    let leverage_vault = unlocked_vault_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .allowed_coins(&[lp_token.clone(), atom.clone(), osmo.clone()])
+        .vault_configs(&[leverage_vault.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![atom.to_coin(300), osmo.to_coin(300)],
+        })
+        .build()
+        .unwrap();
+
+    let vault = mock.get_vault(&leverage_vault);
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(atom.to_coin(300)),
+            Deposit(osmo.to_coin(300)),
+            ProvideLiquidity {
+                coins_in: vec![atom.to_action_coin(100), osmo.to_action_coin(100)],
+                lp_token_out: lp_token.denom.clone(),
+                minimum_receive: Uint128::zero(),
+            },
+            EnterVault {
+                vault: vault.clone(),
+                coin: lp_token.to_action_coin(100_000),
+                min_receive: None,
+            },
+        ],
+        &[atom.to_coin(300), osmo.to_coin(300)],
+    )
+    .unwrap();
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![ExitVaultInto {
+            vault,
+            amount: STARTING_VAULT_SHARES,
+            denom_out: osmo.denom.clone(),
+            slippage: Decimal::from_atomics(6u128, 1).unwrap(),
+        }],
+        &[],
+    )
+    .unwrap();
+
+    let positions = mock.query_positions(&account_id);
+    assert_eq!(positions.vaults.len(), 0);
+
+    // 100_000 LP tokens withdraw into 10 atom and 10 osmo. The atom is swapped into osmo.
+    assert_eq!(positions.deposits.len(), 3);
+    let lp_balance = get_coin(&lp_token.denom, &positions.deposits);
+    assert_eq!(lp_balance.amount, STARTING_LP_POOL_TOKENS - Uint128::new(100_000));
+    let atom_balance = get_coin(&atom.denom, &positions.deposits);
+    assert_eq!(atom_balance.amount, Uint128::new(200));
+    let osmo_balance = get_coin(&osmo.denom, &positions.deposits);
+    assert_eq!(osmo_balance.amount, Uint128::new(210) + MOCK_SWAP_RESULT);
+}
+
+fn atom_vault_info() -> VaultTestInfo {
+    VaultTestInfo {
+        vault_token_denom: "uatomvault".to_string(),
+        lockup: None,
+        base_token_denom: "uatom".to_string(),
+        deposit_cap: coin(10_000_000, "uusdc"),
+        max_ltv: Decimal::from_atomics(6u128, 1).unwrap(),
+        liquidation_threshold: Decimal::from_atomics(7u128, 1).unwrap(),
+        whitelisted: true,
+    }
+}
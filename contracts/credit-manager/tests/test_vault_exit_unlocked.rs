@@ -34,6 +34,7 @@ fn only_owner_can_withdraw_unlocked_for_account() {
         vec![ExitVaultUnlocked {
             id: 423,
             vault,
+            min_receive: None,
         }],
         &[],
     );
@@ -61,6 +62,7 @@ fn can_only_take_action_on_whitelisted_vaults() {
         vec![ExitVaultUnlocked {
             id: 234,
             vault,
+            min_receive: None,
         }],
         &[],
     );
@@ -100,6 +102,7 @@ fn not_owner_of_unlocking_position() {
             EnterVault {
                 vault: vault.clone(),
                 coin: lp_token.to_action_coin(23),
+                min_receive: None,
             },
             RequestVaultUnlock {
                 vault: vault.clone(),
@@ -124,6 +127,7 @@ fn not_owner_of_unlocking_position() {
             EnterVault {
                 vault: vault.clone(),
                 coin: lp_token.to_action_coin(2),
+                min_receive: None,
             },
             RequestVaultUnlock {
                 vault: vault.clone(),
@@ -132,6 +136,7 @@ fn not_owner_of_unlocking_position() {
             ExitVaultUnlocked {
                 id: lockup_id, // ID from user_a not from user_b
                 vault,
+                min_receive: None,
             },
         ],
         &[lp_token.to_coin(2)],
@@ -167,6 +172,7 @@ fn unlocking_position_not_ready_time() {
             EnterVault {
                 vault: vault.clone(),
                 coin: lp_token.to_action_coin(23),
+                min_receive: None,
             },
             RequestVaultUnlock {
                 vault: vault.clone(),
@@ -186,6 +192,7 @@ fn unlocking_position_not_ready_time() {
         vec![ExitVaultUnlocked {
             id: lockup_id,
             vault,
+            min_receive: None,
         }],
         &[],
     );
@@ -220,6 +227,7 @@ fn unlocking_position_not_ready_blocks() {
             EnterVault {
                 vault: vault.clone(),
                 coin: lp_token.to_action_coin(23),
+                min_receive: None,
             },
             RequestVaultUnlock {
                 vault: vault.clone(),
@@ -239,6 +247,7 @@ fn unlocking_position_not_ready_blocks() {
         vec![ExitVaultUnlocked {
             id: lockup_id,
             vault,
+            min_receive: None,
         }],
         &[],
     );
@@ -273,6 +282,7 @@ fn withdraw_unlock_success_time_expiring() {
             EnterVault {
                 vault: vault.clone(),
                 coin: lp_token.to_action_coin(200),
+                min_receive: None,
             },
             RequestVaultUnlock {
                 vault: vault.clone(),
@@ -305,6 +315,7 @@ fn withdraw_unlock_success_time_expiring() {
         vec![ExitVaultUnlocked {
             id: lockup_id,
             vault,
+            min_receive: None,
         }],
         &[],
     )
@@ -355,6 +366,7 @@ fn withdraw_unlock_success_block_expiring() {
             EnterVault {
                 vault: vault.clone(),
                 coin: lp_token.to_action_coin(200),
+                min_receive: None,
             },
             RequestVaultUnlock {
                 vault: vault.clone(),
@@ -387,6 +399,7 @@ fn withdraw_unlock_success_block_expiring() {
         vec![ExitVaultUnlocked {
             id: lockup_id,
             vault,
+            min_receive: None,
         }],
         &[],
     )
@@ -166,6 +166,7 @@ fn not_enough_vault_tokens_for_request() {
             EnterVault {
                 vault: vault.clone(),
                 coin: lp_token.to_action_coin(23),
+                min_receive: None,
             },
             RequestVaultUnlock {
                 vault,
@@ -212,6 +213,7 @@ fn request_unlocked() {
             EnterVault {
                 vault: vault.clone(),
                 coin: lp_token.to_action_coin(23),
+                min_receive: None,
             },
             RequestVaultUnlock {
                 vault: vault.clone(),
@@ -287,6 +289,7 @@ fn cannot_request_more_than_max() {
             EnterVault {
                 vault: vault.clone(),
                 coin: lp_token.to_action_coin(23),
+                min_receive: None,
             },
         ],
         &[lp_token.to_coin(200)],
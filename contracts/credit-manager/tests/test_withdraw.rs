@@ -276,3 +276,62 @@ fn multiple_withdraw_actions() {
     let coin = mock.query_balance(&user, &uatom_info.denom);
     assert_eq!(coin.amount, uatom_amount);
 }
+
+#[test]
+fn max_withdraw_amount_is_exact() {
+    let coin_info = uatom_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .allowed_coins(&[coin_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: coins(300, coin_info.denom.clone()),
+        })
+        .build()
+        .unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![Action::Deposit(coin_info.to_coin(300))],
+        &[coin(300, coin_info.denom.clone())],
+    )
+    .unwrap();
+
+    let max_withdraw = mock.query_max_withdraw_amount(&account_id, &coin_info.denom);
+    assert_eq!(max_withdraw, Uint128::new(300));
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![Action::Borrow(coin_info.to_coin(100))],
+        &[],
+    )
+    .unwrap();
+
+    // 400 uatom deposited, 101 uatom of debt (incl. simulated interest)
+    // Remaining 124 uatom -> floor(124 * 0.82) = 101 covers the debt, 123 uatom would not
+    let max_withdraw = mock.query_max_withdraw_amount(&account_id, &coin_info.denom);
+    assert_eq!(max_withdraw, Uint128::new(276));
+
+    let res = mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![Action::Withdraw(coin_info.to_coin(max_withdraw.u128() + 1))],
+        &[],
+    );
+    assert!(res.is_err());
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![Action::Withdraw(coin_info.to_coin(max_withdraw.u128()))],
+        &[],
+    )
+    .unwrap();
+
+    let max_withdraw = mock.query_max_withdraw_amount(&account_id, &coin_info.denom);
+    assert_eq!(max_withdraw, Uint128::zero());
+}
@@ -12,7 +12,7 @@ use mars_rover::{
 use mars_zapper_mock::contract::STARTING_LP_POOL_TOKENS;
 
 use crate::helpers::{
-    assert_err, get_coin, lp_token_info, uatom_info, uosmo_info, AccountToFund, MockEnv,
+    assert_err, get_coin, lp_token_info, uatom_info, ujake_info, uosmo_info, AccountToFund, MockEnv,
 };
 
 pub mod helpers;
@@ -32,6 +32,7 @@ fn only_token_owner_can_unzap_for_account() {
                 denom: "xyz".to_string(),
                 amount: ActionAmount::AccountBalance,
             },
+            min_receive: None,
         }],
         &[],
     );
@@ -57,6 +58,7 @@ fn lp_token_in_must_be_whitelisted() {
         &user,
         vec![WithdrawLiquidity {
             lp_token: lp_token.to_action_coin(100),
+            min_receive: None,
         }],
         &[],
     );
@@ -114,6 +116,7 @@ fn coins_out_must_be_whitelisted() {
         &user,
         vec![WithdrawLiquidity {
             lp_token: lp_token.to_action_coin(100_000),
+            min_receive: None,
         }],
         &[],
     );
@@ -153,6 +156,7 @@ fn does_not_have_the_tokens_to_withdraw_liq() {
             },
             WithdrawLiquidity {
                 lp_token: lp_token.to_action_coin(attempted_unzap_amount),
+                min_receive: None,
             },
         ],
         &[atom.to_coin(100), osmo.to_coin(50)],
@@ -206,6 +210,7 @@ fn amount_zero_passed() {
         &user,
         vec![WithdrawLiquidity {
             lp_token: lp_token.to_action_coin(0),
+            min_receive: None,
         }],
         &[],
     );
@@ -236,6 +241,7 @@ fn amount_none_passed_with_no_balance() {
         &user,
         vec![WithdrawLiquidity {
             lp_token: lp_token.to_action_coin_full_balance(),
+            min_receive: None,
         }],
         &[],
     );
@@ -274,6 +280,7 @@ fn successful_unzap_specified_amount() {
             },
             WithdrawLiquidity {
                 lp_token: lp_token.to_action_coin(STARTING_LP_POOL_TOKENS.u128()),
+                min_receive: None,
             },
         ],
         &[atom.to_coin(100), osmo.to_coin(50)],
@@ -337,6 +344,7 @@ fn successful_unzap_unspecified_amount() {
             },
             WithdrawLiquidity {
                 lp_token: lp_token.to_action_coin_full_balance(),
+                min_receive: None,
             },
         ],
         &[atom.to_coin(100), osmo.to_coin(50)],
@@ -368,3 +376,96 @@ fn successful_unzap_unspecified_amount() {
     let osmo_balance = mock.query_balance(&Addr::unchecked(config.zapper), &osmo.denom);
     assert_eq!(osmo_balance.amount, Uint128::zero());
 }
+
+#[test]
+fn withdraw_below_min_receive() {
+    let atom = uatom_info();
+    let osmo = uosmo_info();
+    let lp_token = lp_token_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .allowed_coins(&[lp_token.clone(), atom.clone(), osmo.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![atom.to_coin(300), osmo.to_coin(300)],
+        })
+        .build()
+        .unwrap();
+
+    let account_id = mock.create_credit_account(&user).unwrap();
+    let res = mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(atom.to_coin(100)),
+            Deposit(osmo.to_coin(50)),
+            ProvideLiquidity {
+                coins_in: vec![atom.to_action_coin(100), osmo.to_action_coin(50)],
+                lp_token_out: lp_token.denom.clone(),
+                minimum_receive: Uint128::zero(),
+            },
+            WithdrawLiquidity {
+                lp_token: lp_token.to_action_coin_full_balance(),
+                min_receive: Some(vec![atom.to_coin(100), osmo.to_coin(51)]),
+            },
+        ],
+        &[atom.to_coin(100), osmo.to_coin(50)],
+    );
+
+    assert_err(
+        res,
+        RoverError::ReceivedBelowMinimum {
+            denom: osmo.denom,
+            received: Uint128::new(50),
+            min_receive: Uint128::new(51),
+        },
+    );
+}
+
+#[test]
+fn min_receive_for_coin_not_withdrawn() {
+    let atom = uatom_info();
+    let osmo = uosmo_info();
+    let jake = ujake_info();
+    let lp_token = lp_token_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .allowed_coins(&[lp_token.clone(), atom.clone(), osmo.clone(), jake.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![atom.to_coin(300), osmo.to_coin(300)],
+        })
+        .build()
+        .unwrap();
+
+    let account_id = mock.create_credit_account(&user).unwrap();
+    let res = mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(atom.to_coin(100)),
+            Deposit(osmo.to_coin(50)),
+            ProvideLiquidity {
+                coins_in: vec![atom.to_action_coin(100), osmo.to_action_coin(50)],
+                lp_token_out: lp_token.denom.clone(),
+                minimum_receive: Uint128::zero(),
+            },
+            WithdrawLiquidity {
+                lp_token: lp_token.to_action_coin_full_balance(),
+                min_receive: Some(vec![jake.to_coin(1)]),
+            },
+        ],
+        &[atom.to_coin(100), osmo.to_coin(50)],
+    );
+
+    assert_err(
+        res,
+        RoverError::ReceivedBelowMinimum {
+            denom: jake.denom,
+            received: Uint128::zero(),
+            min_receive: Uint128::one(),
+        },
+    );
+}
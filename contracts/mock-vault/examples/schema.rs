@@ -1,6 +1,6 @@
 use cosmwasm_schema::write_api;
-use mars_mock_vault::msg::InstantiateMsg;
-use mars_rover::adapters::vault::{ExecuteMsg, QueryMsg};
+use mars_mock_vault::msg::{ExecuteMsg, InstantiateMsg};
+use mars_rover::adapters::vault::QueryMsg;
 
 fn main() {
     write_api! {
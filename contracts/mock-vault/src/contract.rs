@@ -2,27 +2,25 @@
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{coin, to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, Uint128};
 use cw_vault_standard::{
-    extensions::{
-        force_unlock::ForceUnlockExecuteMsg,
-        lockup::{LockupExecuteMsg, LockupQueryMsg},
-    },
-    msg::{ExtensionExecuteMsg, ExtensionQueryMsg},
+    extensions::{force_unlock::ForceUnlockExecuteMsg, lockup::LockupQueryMsg},
+    msg::ExtensionQueryMsg,
 };
-use mars_rover::adapters::vault::{ExecuteMsg, QueryMsg};
+use mars_rover::adapters::vault::QueryMsg;
 
 use crate::{
     deposit::deposit,
     error::ContractResult,
-    msg::InstantiateMsg,
+    msg::{ExecuteMsg, ExtensionExecuteMsg, InstantiateMsg, LockupExecuteMsg},
     query::{
-        query_lockup_duration, query_unlocking_position, query_unlocking_positions,
-        query_vault_info, query_vault_token_supply, shares_to_base_denom_amount,
+        base_denom_amount_to_shares, query_lockup_duration, query_unlocking_position,
+        query_unlocking_positions, query_vault_info, query_vault_token_supply,
+        shares_to_base_denom_amount,
     },
     state::{
         CHAIN_BANK, COIN_BALANCE, LOCKUP_TIME, NEXT_LOCKUP_ID, ORACLE, TOTAL_VAULT_SHARES,
         VAULT_TOKEN_DENOM,
     },
-    unlock::{request_unlock, withdraw_unlocked, withdraw_unlocking_force},
+    unlock::{cancel_unlock, request_unlock, withdraw_unlocked, withdraw_unlocking_force},
     withdraw::{redeem_force, withdraw},
 };
 
@@ -72,6 +70,9 @@ pub fn execute(
                 LockupExecuteMsg::Unlock {
                     ..
                 } => request_unlock(deps, env, info),
+                LockupExecuteMsg::CancelUnlock {
+                    lockup_id,
+                } => cancel_unlock(deps, &info.sender, lockup_id),
             },
             ExtensionExecuteMsg::ForceUnlock(force_msg) => match force_msg {
                 ForceUnlockExecuteMsg::ForceRedeem {
@@ -93,6 +94,9 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> ContractResult<Binary> {
     let res = match msg {
         QueryMsg::TotalVaultTokenSupply {} => to_binary(&query_vault_token_supply(deps.storage)?),
         QueryMsg::Info {} => to_binary(&query_vault_info(deps)?),
+        QueryMsg::PreviewDeposit {
+            amount,
+        } => to_binary(&base_denom_amount_to_shares(deps, amount)?),
         QueryMsg::PreviewRedeem {
             amount,
         } => to_binary(&shares_to_base_denom_amount(deps.storage, amount)?),
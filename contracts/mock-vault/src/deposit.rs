@@ -45,7 +45,7 @@ pub fn deposit(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractErr
     Ok(Response::new().add_message(transfer_msg))
 }
 
-fn mock_lp_token_mint(deps: DepsMut, amount: Uint128) -> StdResult<Coin> {
+pub fn mock_lp_token_mint(deps: DepsMut, amount: Uint128) -> StdResult<Coin> {
     let denom = VAULT_TOKEN_DENOM.load(deps.storage)?;
 
     CHAIN_BANK.update(deps.storage, |bank_amount| -> StdResult<_> { Ok(bank_amount - amount) })?;
@@ -1,5 +1,9 @@
 use cosmwasm_schema::cw_serde;
+use cosmwasm_std::Uint128;
 use cw_utils::Duration;
+use cw_vault_standard::{
+    extensions::force_unlock::ForceUnlockExecuteMsg, msg::VaultStandardExecuteMsg,
+};
 use mars_rover::adapters::oracle::OracleUnchecked;
 
 // Remaining messages in cw-vault-standard
@@ -13,3 +17,26 @@ pub struct InstantiateMsg {
     pub lockup: Option<Duration>,
     pub oracle: OracleUnchecked,
 }
+
+pub type ExecuteMsg = VaultStandardExecuteMsg<ExtensionExecuteMsg>;
+
+#[cw_serde]
+pub enum ExtensionExecuteMsg {
+    Lockup(LockupExecuteMsg),
+    ForceUnlock(ForceUnlockExecuteMsg),
+}
+
+/// cw-vault-standard lockup messages, plus Rover's `RelockExecuteMsg::CancelUnlock`
+#[cw_serde]
+pub enum LockupExecuteMsg {
+    WithdrawUnlocked {
+        recipient: Option<String>,
+        lockup_id: u64,
+    },
+    Unlock {
+        amount: Uint128,
+    },
+    CancelUnlock {
+        lockup_id: u64,
+    },
+}
@@ -1,11 +1,13 @@
-use cosmwasm_std::{Deps, Order, StdError, StdResult, Storage, Uint128};
+use cosmwasm_std::{Coin, Deps, Order, StdError, StdResult, Storage, Uint128};
 use cw_utils::Duration;
 use cw_vault_standard::{extensions::lockup::UnlockingPosition, msg::VaultInfoResponse};
 
 use crate::{
+    contract::STARTING_VAULT_SHARES,
     error::{ContractError::NotLockingVault, ContractResult},
     state::{
-        COIN_BALANCE, LOCKUP_TIME, TOTAL_VAULT_SHARES, UNLOCKING_POSITIONS, VAULT_TOKEN_DENOM,
+        COIN_BALANCE, LOCKUP_TIME, ORACLE, TOTAL_VAULT_SHARES, UNLOCKING_POSITIONS,
+        VAULT_TOKEN_DENOM,
     },
 };
 
@@ -23,6 +25,24 @@ pub fn shares_to_base_denom_amount(
     }
 }
 
+/// Mirrors the share calculation done on deposit
+pub fn base_denom_amount_to_shares(deps: Deps, amount: Uint128) -> ContractResult<Uint128> {
+    let total_shares = TOTAL_VAULT_SHARES.load(deps.storage)?;
+    if total_shares.is_zero() {
+        return Ok(STARTING_VAULT_SHARES);
+    }
+
+    let oracle = ORACLE.load(deps.storage)?;
+    let balance = COIN_BALANCE.load(deps.storage)?;
+    let assets = Coin {
+        denom: balance.denom.clone(),
+        amount,
+    };
+    let total_vault_value = oracle.query_total_value(&deps.querier, &[balance])?;
+    let assets_value = oracle.query_total_value(&deps.querier, &[assets])?;
+    Ok(total_shares.checked_multiply_ratio(assets_value, total_vault_value)?)
+}
+
 pub fn query_vault_info(deps: Deps) -> ContractResult<VaultInfoResponse> {
     let base_token = COIN_BALANCE.load(deps.storage)?.denom;
     let vault_token = VAULT_TOKEN_DENOM.load(deps.storage)?;
@@ -7,8 +7,10 @@ use cw_vault_standard::extensions::lockup::{
 };
 
 use crate::{
+    deposit::mock_lp_token_mint,
     error::ContractError,
-    state::{COIN_BALANCE, LOCKUP_TIME, NEXT_LOCKUP_ID, UNLOCKING_POSITIONS},
+    query::base_denom_amount_to_shares,
+    state::{COIN_BALANCE, LOCKUP_TIME, NEXT_LOCKUP_ID, TOTAL_VAULT_SHARES, UNLOCKING_POSITIONS},
     withdraw::{get_vault_token, withdraw_state_update},
 };
 
@@ -119,3 +121,37 @@ pub fn withdraw_unlocking_force(
     });
     Ok(Response::new().add_message(transfer_msg))
 }
+
+/// Returns the base tokens of an unlocking position to the vault, minting vault tokens for them
+pub fn cancel_unlock(
+    deps: DepsMut,
+    sender: &Addr,
+    lockup_id: u64,
+) -> Result<Response, ContractError> {
+    let mut lockups =
+        UNLOCKING_POSITIONS.may_load(deps.storage, sender.clone())?.unwrap_or_default();
+    let lockup = lockups
+        .iter()
+        .find(|p| p.id == lockup_id)
+        .cloned()
+        .ok_or(ContractError::LockupPositionNotFound(lockup_id))?;
+
+    lockups.retain(|p| p.id != lockup_id);
+    UNLOCKING_POSITIONS.save(deps.storage, sender.clone(), &lockups)?;
+
+    let shares = base_denom_amount_to_shares(deps.as_ref(), lockup.base_token_amount)?;
+    COIN_BALANCE.update(deps.storage, |total| -> StdResult<_> {
+        Ok(Coin {
+            denom: total.denom,
+            amount: total.amount + lockup.base_token_amount,
+        })
+    })?;
+    TOTAL_VAULT_SHARES.update(deps.storage, |total| -> StdResult<_> { Ok(total + shares) })?;
+
+    let minted = mock_lp_token_mint(deps, shares)?;
+    let transfer_msg = CosmosMsg::Bank(BankMsg::Send {
+        to_address: sender.to_string(),
+        amount: vec![minted],
+    });
+    Ok(Response::new().add_message(transfer_msg))
+}
@@ -0,0 +1,106 @@
+//! Subset of the Astroport pair API used by the swapper
+
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Addr, Coin, Decimal, Uint128};
+
+/// Max spread pairs apply to swaps that do not set one
+pub const DEFAULT_SLIPPAGE: Decimal = Decimal::permille(5);
+
+/// Pairs reject swaps with a max spread above this
+pub const MAX_ALLOWED_SLIPPAGE: Decimal = Decimal::percent(50);
+
+#[cw_serde]
+pub enum AssetInfo {
+    Token {
+        contract_addr: Addr,
+    },
+    NativeToken {
+        denom: String,
+    },
+}
+
+impl AssetInfo {
+    pub fn native(denom: &str) -> Self {
+        AssetInfo::NativeToken {
+            denom: denom.to_string(),
+        }
+    }
+
+    pub fn is_native(&self, denom: &str) -> bool {
+        matches!(self, AssetInfo::NativeToken { denom: d } if d == denom)
+    }
+}
+
+#[cw_serde]
+pub struct Asset {
+    pub info: AssetInfo,
+    pub amount: Uint128,
+}
+
+impl From<&Coin> for Asset {
+    fn from(coin: &Coin) -> Self {
+        Asset {
+            info: AssetInfo::native(&coin.denom),
+            amount: coin.amount,
+        }
+    }
+}
+
+#[cw_serde]
+pub enum PairType {
+    Xyk {},
+    Stable {},
+    Custom(String),
+}
+
+#[cw_serde]
+pub struct PairInfo {
+    pub asset_infos: Vec<AssetInfo>,
+    pub contract_addr: Addr,
+    pub liquidity_token: Addr,
+    pub pair_type: PairType,
+}
+
+#[cw_serde]
+pub enum PairExecuteMsg {
+    /// Swap an offer asset for the ask asset. Fails if the spread exceeds `max_spread`, measured
+    /// from `belief_price` (offer per ask) if set, else from the pair's spot price.
+    Swap {
+        offer_asset: Asset,
+        ask_asset_info: Option<AssetInfo>,
+        belief_price: Option<Decimal>,
+        max_spread: Option<Decimal>,
+        to: Option<String>,
+    },
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum PairQueryMsg {
+    #[returns(PairInfo)]
+    Pair {},
+    #[returns(SimulationResponse)]
+    Simulation {
+        offer_asset: Asset,
+        ask_asset_info: Option<AssetInfo>,
+    },
+    #[returns(ReverseSimulationResponse)]
+    ReverseSimulation {
+        ask_asset: Asset,
+        offer_asset_info: Option<AssetInfo>,
+    },
+}
+
+#[cw_serde]
+pub struct SimulationResponse {
+    pub return_amount: Uint128,
+    pub spread_amount: Uint128,
+    pub commission_amount: Uint128,
+}
+
+#[cw_serde]
+pub struct ReverseSimulationResponse {
+    pub offer_amount: Uint128,
+    pub spread_amount: Uint128,
+    pub commission_amount: Uint128,
+}
@@ -0,0 +1,3 @@
+pub mod astroport;
+pub mod contract;
+pub mod route;
@@ -0,0 +1,191 @@
+use std::fmt;
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    to_binary, Coin, CosmosMsg, Decimal, Empty, Env, QuerierWrapper, Uint128, WasmMsg,
+};
+use mars_rover::adapters::swap::{EstimateExactInSwapResponse, EstimateExactOutSwapResponse};
+use mars_swapper_base::{ContractError, ContractResult, Route};
+
+use crate::astroport::{
+    Asset, AssetInfo, PairExecuteMsg, PairInfo, PairQueryMsg, ReverseSimulationResponse,
+    SimulationResponse, MAX_ALLOWED_SLIPPAGE,
+};
+
+/// Max spread of exact-out swaps from the price of the reverse simulation. The pair cannot move
+/// between simulation and swap, so this only absorbs rounding.
+const EXACT_OUT_MAX_SPREAD: Decimal = Decimal::permille(5);
+
+/// Swaps through a single Astroport pair. Astroport has no exact-in multi-hop message on the
+/// pairs themselves, so multi-hop routes are not supported.
+#[cw_serde]
+pub struct AstroportRoute {
+    /// Address of the pair contract
+    pub pair_addr: String,
+    /// Denom received from the pair
+    pub denom_out: String,
+}
+
+impl fmt::Display for AstroportRoute {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}", self.pair_addr, self.denom_out)
+    }
+}
+
+impl Route<Empty, Empty> for AstroportRoute {
+    fn validate(
+        &self,
+        querier: &QuerierWrapper,
+        denom_in: &str,
+        denom_out: &str,
+    ) -> ContractResult<()> {
+        if denom_in == denom_out {
+            return Err(ContractError::InvalidRoute {
+                reason: format!("denom in and denom out are both {denom_in}"),
+            });
+        }
+
+        if self.denom_out != denom_out {
+            return Err(ContractError::InvalidRoute {
+                reason: format!(
+                    "the route's output denom {} does not match the desired output {denom_out}",
+                    self.denom_out
+                ),
+            });
+        }
+
+        let pair: PairInfo = querier.query_wasm_smart(&self.pair_addr, &PairQueryMsg::Pair {})?;
+        for denom in [denom_in, denom_out] {
+            if !pair.asset_infos.iter().any(|info| info.is_native(denom)) {
+                return Err(ContractError::InvalidRoute {
+                    reason: format!("pair {} does not contain denom {denom}", self.pair_addr),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    fn from_candidate_path(path: &[(String, String)]) -> ContractResult<Self> {
+        match path {
+            [(pair_addr, denom_out)] => Ok(AstroportRoute {
+                pair_addr: pair_addr.clone(),
+                denom_out: denom_out.clone(),
+            }),
+            _ => Err(ContractError::InvalidRoute {
+                reason: "astroport routes must contain exactly one pair".to_string(),
+            }),
+        }
+    }
+
+    /// Build a swap message with the slippage allowance as the pair's max spread, capped at the
+    /// most pairs accept. Astroport prices with the pair's reserves, so the TWAP window does not
+    /// apply.
+    fn build_exact_in_swap_msgs(
+        &self,
+        _querier: &QuerierWrapper,
+        _env: &Env,
+        coin_in: &Coin,
+        slippage: Decimal,
+        _twap_window: Option<u64>,
+    ) -> ContractResult<Vec<CosmosMsg>> {
+        let swap_msg = CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: self.pair_addr.clone(),
+            msg: to_binary(&PairExecuteMsg::Swap {
+                offer_asset: Asset::from(coin_in),
+                ask_asset_info: Some(AssetInfo::native(&self.denom_out)),
+                belief_price: None,
+                max_spread: Some(slippage.min(MAX_ALLOWED_SLIPPAGE)),
+                to: None,
+            })?,
+            funds: vec![coin_in.clone()],
+        });
+        Ok(vec![swap_msg])
+    }
+
+    fn estimate_exact_in_swap(
+        &self,
+        querier: &QuerierWrapper,
+        _env: &Env,
+        coin_in: &Coin,
+        _twap_window: Option<u64>,
+    ) -> ContractResult<EstimateExactInSwapResponse> {
+        let res: SimulationResponse = querier.query_wasm_smart(
+            &self.pair_addr,
+            &PairQueryMsg::Simulation {
+                offer_asset: Asset::from(coin_in),
+                ask_asset_info: Some(AssetInfo::native(&self.denom_out)),
+            },
+        )?;
+        Ok(EstimateExactInSwapResponse {
+            amount: res.return_amount,
+        })
+    }
+
+    /// Pairs only swap exact-in, so the input is sized with a reverse simulation. Any of `max_in`
+    /// not offered to the pair is refunded by the swapper. The simulated price is passed as the
+    /// belief price, as the spread from the pair's spot price is the price impact `max_in` already
+    /// accounts for.
+    fn build_exact_out_swap_msg(
+        &self,
+        querier: &QuerierWrapper,
+        _env: &Env,
+        coin_out: &Coin,
+        max_in: &Coin,
+    ) -> ContractResult<CosmosMsg> {
+        let offer_amount = self.reverse_simulate(querier, coin_out, &max_in.denom)?;
+        if offer_amount > max_in.amount {
+            return Err(ContractError::MaxInExceeded {
+                required: offer_amount,
+                max_in: max_in.amount,
+            });
+        }
+
+        let coin_in = Coin {
+            denom: max_in.denom.clone(),
+            amount: offer_amount,
+        };
+        Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: self.pair_addr.clone(),
+            msg: to_binary(&PairExecuteMsg::Swap {
+                offer_asset: Asset::from(&coin_in),
+                ask_asset_info: Some(AssetInfo::native(&coin_out.denom)),
+                belief_price: Some(Decimal::from_ratio(offer_amount, coin_out.amount)),
+                max_spread: Some(EXACT_OUT_MAX_SPREAD),
+                to: None,
+            })?,
+            funds: vec![coin_in],
+        }))
+    }
+
+    fn estimate_exact_out_swap(
+        &self,
+        querier: &QuerierWrapper,
+        _env: &Env,
+        coin_out: &Coin,
+        denom_in: &str,
+        _twap_window: Option<u64>,
+    ) -> ContractResult<EstimateExactOutSwapResponse> {
+        Ok(EstimateExactOutSwapResponse {
+            amount: self.reverse_simulate(querier, coin_out, denom_in)?,
+        })
+    }
+}
+
+impl AstroportRoute {
+    fn reverse_simulate(
+        &self,
+        querier: &QuerierWrapper,
+        coin_out: &Coin,
+        denom_in: &str,
+    ) -> ContractResult<Uint128> {
+        let res: ReverseSimulationResponse = querier.query_wasm_smart(
+            &self.pair_addr,
+            &PairQueryMsg::ReverseSimulation {
+                ask_asset: Asset::from(coin_out),
+                offer_asset_info: Some(AssetInfo::native(denom_in)),
+            },
+        )?;
+        Ok(res.offer_amount)
+    }
+}
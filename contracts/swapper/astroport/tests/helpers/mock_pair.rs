@@ -0,0 +1,194 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    to_binary, Addr, BankMsg, Binary, Coin, Decimal, Deps, DepsMut, Empty, Env, MessageInfo,
+    Response, StdError, StdResult, Uint128,
+};
+use cw_multi_test::{Contract, ContractWrapper};
+use cw_storage_plus::Item;
+use mars_swapper_astroport::astroport::{
+    Asset, AssetInfo, PairExecuteMsg, PairInfo, PairQueryMsg, PairType, ReverseSimulationResponse,
+    SimulationResponse, DEFAULT_SLIPPAGE, MAX_ALLOWED_SLIPPAGE,
+};
+
+const DENOMS: Item<[String; 2]> = Item::new("denoms");
+
+#[cw_serde]
+pub struct MockPairInstantiateMsg {
+    pub denoms: [String; 2],
+}
+
+/// A constant product pair without commission, using its bank balances as reserves
+pub fn mock_pair_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(execute, instantiate, query);
+    Box::new(contract)
+}
+
+fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: MockPairInstantiateMsg,
+) -> StdResult<Response> {
+    DENOMS.save(deps.storage, &msg.denoms)?;
+    Ok(Response::new())
+}
+
+fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: PairExecuteMsg) -> StdResult<Response> {
+    match msg {
+        PairExecuteMsg::Swap {
+            offer_asset,
+            ask_asset_info,
+            belief_price,
+            max_spread,
+            ..
+        } => {
+            let (offer_denom, ask_denom) =
+                swap_denoms(deps.as_ref(), &offer_asset, ask_asset_info)?;
+            if info.funds != vec![Coin::new(offer_asset.amount.u128(), &offer_denom)] {
+                return Err(StdError::generic_err("Native token balance mismatch"));
+            }
+
+            // Funds are transferred before execution, so exclude them from the reserves
+            let offer_pool = query_reserve(deps.as_ref(), &env, &offer_denom)? - offer_asset.amount;
+            let ask_pool = query_reserve(deps.as_ref(), &env, &ask_denom)?;
+            let (return_amount, spread_amount) =
+                compute_swap(offer_pool, ask_pool, offer_asset.amount)?;
+
+            assert_max_spread(
+                belief_price,
+                max_spread,
+                offer_asset.amount,
+                return_amount,
+                spread_amount,
+            )?;
+
+            Ok(Response::new().add_message(BankMsg::Send {
+                to_address: info.sender.to_string(),
+                amount: vec![Coin::new(return_amount.u128(), ask_denom)],
+            }))
+        }
+    }
+}
+
+fn query(deps: Deps, env: Env, msg: PairQueryMsg) -> StdResult<Binary> {
+    match msg {
+        PairQueryMsg::Pair {} => {
+            let denoms = DENOMS.load(deps.storage)?;
+            to_binary(&PairInfo {
+                asset_infos: denoms.iter().map(|denom| AssetInfo::native(denom)).collect(),
+                contract_addr: env.contract.address,
+                liquidity_token: Addr::unchecked("lp_token"),
+                pair_type: PairType::Xyk {},
+            })
+        }
+        PairQueryMsg::Simulation {
+            offer_asset,
+            ask_asset_info,
+        } => {
+            let (offer_denom, ask_denom) = swap_denoms(deps, &offer_asset, ask_asset_info)?;
+            let offer_pool = query_reserve(deps, &env, &offer_denom)?;
+            let ask_pool = query_reserve(deps, &env, &ask_denom)?;
+            let (return_amount, spread_amount) =
+                compute_swap(offer_pool, ask_pool, offer_asset.amount)?;
+            to_binary(&SimulationResponse {
+                return_amount,
+                spread_amount,
+                commission_amount: Uint128::zero(),
+            })
+        }
+        PairQueryMsg::ReverseSimulation {
+            ask_asset,
+            offer_asset_info,
+        } => {
+            let (ask_denom, offer_denom) = swap_denoms(deps, &ask_asset, offer_asset_info)?;
+            let offer_pool = query_reserve(deps, &env, &offer_denom)?;
+            let ask_pool = query_reserve(deps, &env, &ask_denom)?;
+            if ask_asset.amount >= ask_pool {
+                return Err(StdError::generic_err("Not enough liquidity"));
+            }
+            // offer = offer_pool * ask / (ask_pool - ask), rounded up
+            let offer_amount = Uint128::one()
+                + offer_pool.multiply_ratio(ask_asset.amount, ask_pool - ask_asset.amount);
+            let spread_amount =
+                offer_amount.multiply_ratio(ask_pool, offer_pool) - ask_asset.amount;
+            to_binary(&ReverseSimulationResponse {
+                offer_amount,
+                spread_amount,
+                commission_amount: Uint128::zero(),
+            })
+        }
+    }
+}
+
+/// Returns the native denoms of the given asset and the other side of the swap
+fn swap_denoms(
+    deps: Deps,
+    asset: &Asset,
+    other_info: Option<AssetInfo>,
+) -> StdResult<(String, String)> {
+    let denoms = DENOMS.load(deps.storage)?;
+    let native_denom = |info: &AssetInfo| match info {
+        AssetInfo::NativeToken {
+            denom,
+        } if denoms.contains(denom) => Ok(denom.clone()),
+        _ => Err(StdError::generic_err(
+            "Asset mismatch between the requested and the stored asset in contract",
+        )),
+    };
+
+    let denom = native_denom(&asset.info)?;
+    let other_denom = match other_info {
+        Some(info) => native_denom(&info)?,
+        None => denoms.iter().find(|d| **d != denom).cloned().unwrap(),
+    };
+    if denom == other_denom {
+        return Err(StdError::generic_err("Doubling assets in asset infos"));
+    }
+    Ok((denom, other_denom))
+}
+
+/// Mirrors the pair's spread check: the spread is measured from the belief price if set, else from
+/// the spot price, against the default max spread if none is set
+fn assert_max_spread(
+    belief_price: Option<Decimal>,
+    max_spread: Option<Decimal>,
+    offer_amount: Uint128,
+    return_amount: Uint128,
+    spread_amount: Uint128,
+) -> StdResult<()> {
+    let max_spread = max_spread.unwrap_or(DEFAULT_SLIPPAGE);
+    if max_spread > MAX_ALLOWED_SLIPPAGE {
+        return Err(StdError::generic_err("Provided spread amount exceeds allowed limit"));
+    }
+
+    let exceeded = match belief_price {
+        Some(belief_price) => {
+            let inverse =
+                belief_price.inv().ok_or_else(|| StdError::generic_err("Invalid belief price"))?;
+            let expected_return = offer_amount * inverse;
+            return_amount < expected_return
+                && Decimal::from_ratio(expected_return - return_amount, expected_return)
+                    > max_spread
+        }
+        None => Decimal::from_ratio(spread_amount, return_amount + spread_amount) > max_spread,
+    };
+    if exceeded {
+        return Err(StdError::generic_err("Operation exceeds max spread limit"));
+    }
+    Ok(())
+}
+
+fn query_reserve(deps: Deps, env: &Env, denom: &str) -> StdResult<Uint128> {
+    Ok(deps.querier.query_balance(&env.contract.address, denom)?.amount)
+}
+
+/// Returns the constant product output and its spread from the pool's spot price
+fn compute_swap(
+    offer_pool: Uint128,
+    ask_pool: Uint128,
+    offer_amount: Uint128,
+) -> StdResult<(Uint128, Uint128)> {
+    let return_amount = ask_pool.multiply_ratio(offer_amount, offer_pool + offer_amount);
+    let spread_amount = offer_amount.multiply_ratio(ask_pool, offer_pool) - return_amount;
+    Ok((return_amount, spread_amount))
+}
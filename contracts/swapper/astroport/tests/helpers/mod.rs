@@ -0,0 +1,107 @@
+use std::fmt::Display;
+
+use anyhow::Result as AnyResult;
+use cosmwasm_std::{coin, Addr, Coin, Empty, Uint128};
+use cw_multi_test::{App, AppResponse, BasicApp, Contract, ContractWrapper, Executor};
+use mars_rover::adapters::swap::{ExecuteMsg, InstantiateMsg};
+use mars_swapper_astroport::route::AstroportRoute;
+
+pub use self::mock_pair::*;
+
+mod mock_pair;
+
+pub const OWNER: &str = "owner";
+pub const USER: &str = "user";
+
+pub fn mock_app() -> BasicApp {
+    App::new(|router, _, storage| {
+        for addr in [OWNER, USER] {
+            router
+                .bank
+                .init_balance(
+                    storage,
+                    &Addr::unchecked(addr),
+                    vec![
+                        coin(1_000_000_000_000, "uatom"),
+                        coin(1_000_000_000_000, "umars"),
+                        coin(1_000_000_000_000, "uosmo"),
+                    ],
+                )
+                .unwrap();
+        }
+    })
+}
+
+fn mock_swapper_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        mars_swapper_astroport::contract::execute,
+        mars_swapper_astroport::contract::instantiate,
+        mars_swapper_astroport::contract::query,
+    );
+    Box::new(contract)
+}
+
+pub fn instantiate_swapper(app: &mut BasicApp) -> Addr {
+    let code_id = app.store_code(mock_swapper_contract());
+    app.instantiate_contract(
+        code_id,
+        Addr::unchecked(OWNER),
+        &InstantiateMsg {
+            owner: OWNER.to_string(),
+            oracle_check: None,
+        },
+        &[],
+        "swapper-astroport-contract",
+        None,
+    )
+    .unwrap()
+}
+
+/// Instantiates a mock xyk pair holding `reserves` as its liquidity
+pub fn instantiate_pair(app: &mut BasicApp, reserves: [Coin; 2]) -> Addr {
+    let code_id = app.store_code(mock_pair_contract());
+    let mut funds = reserves.to_vec();
+    funds.sort_by(|a, b| a.denom.cmp(&b.denom));
+    app.instantiate_contract(
+        code_id,
+        Addr::unchecked(OWNER),
+        &MockPairInstantiateMsg {
+            denoms: [reserves[0].denom.clone(), reserves[1].denom.clone()],
+        },
+        &funds,
+        "mock-astroport-pair",
+        None,
+    )
+    .unwrap()
+}
+
+pub fn set_route(
+    app: &mut BasicApp,
+    swapper: &Addr,
+    pair: &Addr,
+    denom_in: &str,
+    denom_out: &str,
+) -> AnyResult<AppResponse> {
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        swapper.clone(),
+        &ExecuteMsg::SetRoute {
+            denom_in: denom_in.to_string(),
+            denom_out: denom_out.to_string(),
+            route: AstroportRoute {
+                pair_addr: pair.to_string(),
+                denom_out: denom_out.to_string(),
+            },
+            twap_window: None,
+        },
+        &[],
+    )
+}
+
+pub fn query_balance(app: &BasicApp, addr: &Addr, denom: &str) -> Uint128 {
+    app.wrap().query_balance(addr, denom).unwrap().amount
+}
+
+pub fn assert_err(res: anyhow::Error, err: impl Display) {
+    assert!(res.root_cause().to_string().contains(&err.to_string()));
+}
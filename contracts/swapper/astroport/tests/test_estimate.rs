@@ -0,0 +1,52 @@
+use cosmwasm_std::{coin, Uint128};
+use mars_rover::adapters::swap::{
+    EstimateExactInSwapResponse, EstimateExactOutSwapResponse, QueryMsg,
+};
+
+use crate::helpers::{instantiate_pair, instantiate_swapper, mock_app, set_route};
+
+pub mod helpers;
+
+#[test]
+fn estimate_exact_in_uses_pair_simulation() {
+    let mut app = mock_app();
+    let swapper = instantiate_swapper(&mut app);
+    let pair = instantiate_pair(&mut app, [coin(1_000_000, "umars"), coin(250_000, "uosmo")]);
+    set_route(&mut app, &swapper, &pair, "umars", "uosmo").unwrap();
+
+    let res: EstimateExactInSwapResponse = app
+        .wrap()
+        .query_wasm_smart(
+            swapper,
+            &QueryMsg::EstimateExactInSwap {
+                coin_in: coin(10_000, "umars"),
+                denom_out: "uosmo".to_string(),
+            },
+        )
+        .unwrap();
+
+    // 250_000 * 10_000 / (1_000_000 + 10_000)
+    assert_eq!(res.amount, Uint128::new(2475));
+}
+
+#[test]
+fn estimate_exact_out_uses_reverse_simulation() {
+    let mut app = mock_app();
+    let swapper = instantiate_swapper(&mut app);
+    let pair = instantiate_pair(&mut app, [coin(1_000_000, "umars"), coin(250_000, "uosmo")]);
+    set_route(&mut app, &swapper, &pair, "umars", "uosmo").unwrap();
+
+    let res: EstimateExactOutSwapResponse = app
+        .wrap()
+        .query_wasm_smart(
+            swapper,
+            &QueryMsg::EstimateExactOutSwap {
+                coin_out: coin(2475, "uosmo"),
+                denom_in: "umars".to_string(),
+            },
+        )
+        .unwrap();
+
+    // 1_000_000 * 2475 / (250_000 - 2475), rounded up
+    assert_eq!(res.amount, Uint128::new(9999));
+}
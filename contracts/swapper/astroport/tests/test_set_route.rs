@@ -0,0 +1,87 @@
+use cosmwasm_std::{coin, Addr};
+use cw_multi_test::Executor;
+use mars_rover::adapters::swap::{ExecuteMsg, QueryMsg, RouteResponse};
+use mars_swapper_astroport::route::AstroportRoute;
+use mars_swapper_base::ContractError;
+
+use crate::helpers::{
+    assert_err, instantiate_pair, instantiate_swapper, mock_app, set_route, OWNER,
+};
+
+pub mod helpers;
+
+#[test]
+fn denom_missing_from_pair() {
+    let mut app = mock_app();
+    let swapper = instantiate_swapper(&mut app);
+    let pair = instantiate_pair(&mut app, [coin(1_000_000, "umars"), coin(250_000, "uosmo")]);
+
+    let res_err = set_route(&mut app, &swapper, &pair, "uatom", "uosmo").unwrap_err();
+
+    assert_err(
+        res_err,
+        ContractError::InvalidRoute {
+            reason: format!("pair {pair} does not contain denom uatom"),
+        },
+    );
+}
+
+#[test]
+fn route_output_must_match_denom_out() {
+    let mut app = mock_app();
+    let swapper = instantiate_swapper(&mut app);
+    let pair = instantiate_pair(&mut app, [coin(1_000_000, "umars"), coin(250_000, "uosmo")]);
+
+    let res_err = app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            swapper,
+            &ExecuteMsg::SetRoute {
+                denom_in: "umars".to_string(),
+                denom_out: "uosmo".to_string(),
+                route: AstroportRoute {
+                    pair_addr: pair.to_string(),
+                    denom_out: "umars".to_string(),
+                },
+                twap_window: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+
+    assert_err(
+        res_err,
+        ContractError::InvalidRoute {
+            reason: "the route's output denom umars does not match the desired output uosmo"
+                .to_string(),
+        },
+    );
+}
+
+#[test]
+fn set_route_success() {
+    let mut app = mock_app();
+    let swapper = instantiate_swapper(&mut app);
+    let pair = instantiate_pair(&mut app, [coin(1_000_000, "umars"), coin(250_000, "uosmo")]);
+
+    set_route(&mut app, &swapper, &pair, "umars", "uosmo").unwrap();
+
+    let res: RouteResponse<AstroportRoute> = app
+        .wrap()
+        .query_wasm_smart(
+            swapper,
+            &QueryMsg::Route {
+                denom_in: "umars".to_string(),
+                denom_out: "uosmo".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        res.route,
+        AstroportRoute {
+            pair_addr: pair.to_string(),
+            denom_out: "uosmo".to_string(),
+        }
+    );
+    assert_eq!(res.route.to_string(), format!("{pair}:uosmo"));
+}
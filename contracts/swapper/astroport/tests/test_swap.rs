@@ -0,0 +1,152 @@
+use cosmwasm_std::{coin, Addr, Decimal, Uint128};
+use cw_multi_test::Executor;
+use mars_rover::adapters::swap::ExecuteMsg;
+use mars_swapper_astroport::route::AstroportRoute;
+use mars_swapper_base::ContractError;
+
+use crate::helpers::{
+    assert_err, instantiate_pair, instantiate_swapper, mock_app, query_balance, set_route, USER,
+};
+
+pub mod helpers;
+
+#[test]
+fn swap_exact_in_exceeding_max_spread() {
+    let mut app = mock_app();
+    let swapper = instantiate_swapper(&mut app);
+    let pair = instantiate_pair(&mut app, [coin(1_000_000, "umars"), coin(250_000, "uosmo")]);
+    set_route(&mut app, &swapper, &pair, "umars", "uosmo").unwrap();
+
+    // Swapping 10_000 umars has a 1% spread
+    let res_err = app
+        .execute_contract(
+            Addr::unchecked(USER),
+            swapper,
+            &ExecuteMsg::<AstroportRoute>::SwapExactIn {
+                coin_in: coin(10_000, "umars"),
+                denom_out: "uosmo".to_string(),
+                slippage: Decimal::permille(5),
+            },
+            &[coin(10_000, "umars")],
+        )
+        .unwrap_err();
+
+    assert_err(res_err, "Operation exceeds max spread limit");
+}
+
+#[test]
+fn swap_exact_in_success() {
+    let mut app = mock_app();
+    let swapper = instantiate_swapper(&mut app);
+    let pair = instantiate_pair(&mut app, [coin(1_000_000, "umars"), coin(250_000, "uosmo")]);
+    set_route(&mut app, &swapper, &pair, "umars", "uosmo").unwrap();
+
+    let user = Addr::unchecked(USER);
+    let mars_balance_before = query_balance(&app, &user, "umars");
+    let osmo_balance_before = query_balance(&app, &user, "uosmo");
+
+    app.execute_contract(
+        user.clone(),
+        swapper.clone(),
+        &ExecuteMsg::<AstroportRoute>::SwapExactIn {
+            coin_in: coin(10_000, "umars"),
+            denom_out: "uosmo".to_string(),
+            slippage: Decimal::percent(2),
+        },
+        &[coin(10_000, "umars")],
+    )
+    .unwrap();
+
+    assert_eq!(query_balance(&app, &user, "umars"), mars_balance_before - Uint128::new(10_000));
+    assert_eq!(query_balance(&app, &user, "uosmo"), osmo_balance_before + Uint128::new(2475));
+
+    // Assert no tokens in contract left over
+    assert_eq!(query_balance(&app, &swapper, "umars"), Uint128::zero());
+    assert_eq!(query_balance(&app, &swapper, "uosmo"), Uint128::zero());
+}
+
+#[test]
+fn swap_exact_in_caps_slippage_at_pair_maximum() {
+    let mut app = mock_app();
+    let swapper = instantiate_swapper(&mut app);
+    let pair = instantiate_pair(&mut app, [coin(1_000_000, "umars"), coin(250_000, "uosmo")]);
+    set_route(&mut app, &swapper, &pair, "umars", "uosmo").unwrap();
+
+    let user = Addr::unchecked(USER);
+    let osmo_balance_before = query_balance(&app, &user, "uosmo");
+
+    // Pairs reject a max spread above 50%, so a larger slippage allowance is capped
+    app.execute_contract(
+        user.clone(),
+        swapper,
+        &ExecuteMsg::<AstroportRoute>::SwapExactIn {
+            coin_in: coin(10_000, "umars"),
+            denom_out: "uosmo".to_string(),
+            slippage: Decimal::percent(60),
+        },
+        &[coin(10_000, "umars")],
+    )
+    .unwrap();
+
+    assert_eq!(query_balance(&app, &user, "uosmo"), osmo_balance_before + Uint128::new(2475));
+}
+
+#[test]
+fn swap_exact_out_above_max_in() {
+    let mut app = mock_app();
+    let swapper = instantiate_swapper(&mut app);
+    let pair = instantiate_pair(&mut app, [coin(1_000_000, "umars"), coin(250_000, "uosmo")]);
+    set_route(&mut app, &swapper, &pair, "umars", "uosmo").unwrap();
+
+    let res_err = app
+        .execute_contract(
+            Addr::unchecked(USER),
+            swapper,
+            &ExecuteMsg::<AstroportRoute>::SwapExactOut {
+                coin_out: coin(2475, "uosmo"),
+                denom_in: "umars".to_string(),
+                max_in: Uint128::new(9_000),
+            },
+            &[coin(9_000, "umars")],
+        )
+        .unwrap_err();
+
+    assert_err(
+        res_err,
+        ContractError::MaxInExceeded {
+            required: Uint128::new(9999),
+            max_in: Uint128::new(9_000),
+        },
+    );
+}
+
+#[test]
+fn swap_exact_out_refunds_unspent_input() {
+    let mut app = mock_app();
+    let swapper = instantiate_swapper(&mut app);
+    let pair = instantiate_pair(&mut app, [coin(1_000_000, "umars"), coin(250_000, "uosmo")]);
+    set_route(&mut app, &swapper, &pair, "umars", "uosmo").unwrap();
+
+    let user = Addr::unchecked(USER);
+    let mars_balance_before = query_balance(&app, &user, "umars");
+    let osmo_balance_before = query_balance(&app, &user, "uosmo");
+
+    app.execute_contract(
+        user.clone(),
+        swapper.clone(),
+        &ExecuteMsg::<AstroportRoute>::SwapExactOut {
+            coin_out: coin(2475, "uosmo"),
+            denom_in: "umars".to_string(),
+            max_in: Uint128::new(10_000),
+        },
+        &[coin(10_000, "umars")],
+    )
+    .unwrap();
+
+    assert_eq!(query_balance(&app, &user, "umars"), mars_balance_before - Uint128::new(9999));
+    assert_eq!(query_balance(&app, &user, "uosmo"), osmo_balance_before + Uint128::new(2475));
+
+    // Assert no tokens in contract left over
+    assert_eq!(query_balance(&app, &swapper, "umars"), Uint128::zero());
+    assert_eq!(query_balance(&app, &swapper, "uosmo"), Uint128::zero());
+}
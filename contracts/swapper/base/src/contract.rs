@@ -1,24 +1,34 @@
 use std::marker::PhantomData;
 
 use cosmwasm_std::{
-    to_binary, Addr, BankMsg, Binary, Coin, CosmosMsg, CustomMsg, CustomQuery, Decimal, Deps,
-    DepsMut, Env, MessageInfo, Order, Response, WasmMsg,
+    to_binary, Addr, Api, BankMsg, Binary, Coin, CosmosMsg, CustomMsg, CustomQuery, Decimal, Deps,
+    DepsMut, Empty, Env, Fraction, MessageInfo, Order, QuerierWrapper, Response, StdResult,
+    Uint128, WasmMsg,
 };
-use cw_storage_plus::{Bound, Map};
+use cw_storage_plus::{Bound, Item, Map};
 use mars_owner::{Owner, OwnerInit::SetInitialOwner, OwnerUpdate};
 use mars_rover::{
     adapters::swap::{
-        EstimateExactInSwapResponse, ExecuteMsg, InstantiateMsg, QueryMsg, RouteResponse,
-        RoutesResponse,
+        BestRouteResponse, CandidatePool, CandidatePoolsResponse, ConfigResponse, ConfigUpdate,
+        EstimateExactInSwapResponse, EstimateExactOutSwapResponse, ExecuteMsg, InstantiateMsg,
+        NewRoute, OracleCheck, OracleCheckUnchecked, QueryMsg, RouteResponse,
+        RouteValidityResponse, RoutesResponse, ValidateRoutesResponse,
     },
     error::ContractError as RoverError,
 };
 
-use crate::{ContractResult, Route};
+use crate::{ContractError, ContractResult, Route};
 
 const DEFAULT_LIMIT: u32 = 5;
 const MAX_LIMIT: u32 = 10;
 
+/// Upper bound on the hops searched for a best route, keeping the search's gas cost bounded
+pub const MAX_HOPS: u8 = 3;
+
+/// Upper bound on the pools in the registry. Every path through them may be estimated when
+/// searching for a best route, so the number of pools bounds the search's gas cost.
+pub const MAX_CANDIDATE_POOLS: usize = 10;
+
 pub struct SwapBase<'a, Q, M, R>
 where
     Q: CustomQuery,
@@ -29,11 +39,28 @@ where
     pub owner: Owner<'a>,
     /// The trade route for each pair of input/output assets
     pub routes: Map<'a, (String, String), R>,
+    /// TWAP window in seconds used to price each pair's route. The route's default is used if not
+    /// set.
+    pub route_twap_windows: Map<'a, (String, String), u64>,
+    /// Cross-check of route estimates against oracle prices. Skipped if not set.
+    pub oracle_check: Item<'a, OracleCheck>,
+    /// Denoms of each pool searched when discovering routes, keyed by pool id
+    pub candidate_pools: Map<'a, String, Vec<String>>,
+    /// Max hops of the best route used for exact-in swaps without an explicit route. No fallback
+    /// if not set.
+    pub fallback_max_hops: Item<'a, u8>,
     /// Phantom data holds generics
     pub custom_query: PhantomData<Q>,
     pub custom_message: PhantomData<M>,
 }
 
+/// A route together with the TWAP window used to price it
+pub struct StoredRoute<R> {
+    pub route: R,
+    /// TWAP window in seconds. The route's default is used if not set.
+    pub twap_window: Option<u64>,
+}
+
 impl<'a, Q, M, R> Default for SwapBase<'a, Q, M, R>
 where
     Q: CustomQuery,
@@ -44,6 +71,10 @@ where
         Self {
             owner: Owner::new("owner"),
             routes: Map::new("routes"),
+            route_twap_windows: Map::new("route_twap_windows"),
+            oracle_check: Item::new("oracle_check"),
+            candidate_pools: Map::new("candidate_pools"),
+            fallback_max_hops: Item::new("fallback_max_hops"),
             custom_query: PhantomData,
             custom_message: PhantomData,
         }
@@ -68,6 +99,12 @@ where
                 owner: msg.owner,
             },
         )?;
+
+        if let Some(oracle_check) = msg.oracle_check {
+            self.oracle_check
+                .save(deps.storage, &validate_oracle_check(deps.api, &oracle_check)?)?;
+        }
+
         Ok(Response::default())
     }
 
@@ -84,27 +121,54 @@ where
                 denom_in,
                 denom_out,
                 route,
-            } => self.set_route(deps, info.sender, denom_in, denom_out, route),
+                twap_window,
+            } => self.set_route(deps, info.sender, denom_in, denom_out, route, twap_window),
+            ExecuteMsg::SetRoutes {
+                routes,
+            } => self.set_routes(deps, info.sender, routes),
+            ExecuteMsg::DeleteRoute {
+                denom_in,
+                denom_out,
+            } => self.delete_route(deps, info.sender, denom_in, denom_out),
+            ExecuteMsg::UpdateConfig {
+                oracle_check,
+                fallback_max_hops,
+            } => self.update_config(deps, info.sender, oracle_check, fallback_max_hops),
+            ExecuteMsg::UpdateCandidatePools {
+                add,
+                remove,
+            } => self.update_candidate_pools(deps, info.sender, add, remove),
             ExecuteMsg::SwapExactIn {
                 coin_in,
                 denom_out,
                 slippage,
             } => self.swap_exact_in(deps, env, info, coin_in, denom_out, slippage),
+            ExecuteMsg::SwapExactOut {
+                coin_out,
+                denom_in,
+                max_in,
+            } => self.swap_exact_out(deps, env, info, coin_out, denom_in, max_in),
             ExecuteMsg::TransferResult {
                 recipient,
                 denom_in,
                 denom_out,
-            } => self.transfer_result(deps, env, info, recipient, denom_in, denom_out),
+                min_receive,
+            } => self.transfer_result(deps, env, info, recipient, denom_in, denom_out, min_receive),
         }
     }
 
     pub fn query(&self, deps: Deps<Q>, env: Env, msg: QueryMsg) -> ContractResult<Binary> {
         let res = match msg {
             QueryMsg::Owner {} => to_binary(&self.owner.query(deps.storage)?),
+            QueryMsg::Config {} => to_binary(&self.query_config(deps)?),
             QueryMsg::EstimateExactInSwap {
                 coin_in,
                 denom_out,
             } => to_binary(&self.estimate_exact_in_swap(deps, env, coin_in, denom_out)?),
+            QueryMsg::EstimateExactOutSwap {
+                coin_out,
+                denom_in,
+            } => to_binary(&self.estimate_exact_out_swap(deps, env, coin_out, denom_in)?),
             QueryMsg::Route {
                 denom_in,
                 denom_out,
@@ -113,20 +177,42 @@ where
                 start_after,
                 limit,
             } => to_binary(&self.query_routes(deps, start_after, limit)?),
+            QueryMsg::ValidateRoutes {
+                start_after,
+                limit,
+            } => to_binary(&self.query_validate_routes(deps, start_after, limit)?),
+            QueryMsg::CandidatePools {
+                start_after,
+                limit,
+            } => to_binary(&self.query_candidate_pools(deps, start_after, limit)?),
+            QueryMsg::BestRoute {
+                coin_in,
+                denom_out,
+                max_hops,
+            } => to_binary(&self.query_best_route(deps, env, coin_in, denom_out, max_hops)?),
         };
         res.map_err(Into::into)
     }
 
+    fn query_config(&self, deps: Deps<Q>) -> ContractResult<ConfigResponse> {
+        Ok(ConfigResponse {
+            oracle_check: self.oracle_check.may_load(deps.storage)?.map(Into::into),
+            fallback_max_hops: self.fallback_max_hops.may_load(deps.storage)?,
+        })
+    }
+
     fn query_route(
         &self,
         deps: Deps<Q>,
         denom_in: String,
         denom_out: String,
     ) -> ContractResult<RouteResponse<R>> {
+        let stored = self.load_route(deps, (denom_in.clone(), denom_out.clone()))?;
         Ok(RouteResponse {
-            denom_in: denom_in.clone(),
-            denom_out: denom_out.clone(),
-            route: self.routes.load(deps.storage, (denom_in, denom_out))?,
+            denom_in,
+            denom_out,
+            route: stored.route,
+            twap_window: stored.twap_window,
         })
     }
 
@@ -144,15 +230,183 @@ where
             .take(limit)
             .map(|item| {
                 let ((denom_in, denom_out), route) = item?;
+                let twap_window = self
+                    .route_twap_windows
+                    .may_load(deps.storage, (denom_in.clone(), denom_out.clone()))?;
                 Ok(RouteResponse {
                     denom_in,
                     denom_out,
                     route,
+                    twap_window,
+                })
+            })
+            .collect()
+    }
+
+    fn query_validate_routes(
+        &self,
+        deps: Deps<Q>,
+        start_after: Option<(String, String)>,
+        limit: Option<u32>,
+    ) -> ContractResult<ValidateRoutesResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.map(Bound::exclusive);
+
+        self.routes
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| {
+                let ((denom_in, denom_out), route) = item?;
+                let error = route
+                    .validate(&deps.querier, &denom_in, &denom_out)
+                    .err()
+                    .map(|err| err.to_string());
+                Ok(RouteValidityResponse {
+                    denom_in,
+                    denom_out,
+                    error,
                 })
             })
             .collect()
     }
 
+    fn query_candidate_pools(
+        &self,
+        deps: Deps<Q>,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> ContractResult<CandidatePoolsResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.map(Bound::exclusive);
+
+        self.candidate_pools
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| {
+                let (id, denoms) = item?;
+                Ok(CandidatePool {
+                    id,
+                    denoms,
+                })
+            })
+            .collect()
+    }
+
+    fn query_best_route(
+        &self,
+        deps: Deps<Q>,
+        env: Env,
+        coin_in: Coin,
+        denom_out: String,
+        max_hops: u8,
+    ) -> ContractResult<BestRouteResponse<R>> {
+        let (route, amount) = self.best_route(deps, &env, &coin_in, &denom_out, max_hops)?;
+        Ok(BestRouteResponse {
+            route,
+            amount,
+        })
+    }
+
+    /// Searches paths of up to `max_hops` candidate pools from the input to the output denom.
+    /// Paths that fail validation or cannot be estimated (e.g. missing TWAP records) are skipped.
+    /// Returns the route with the highest estimated output.
+    fn best_route(
+        &self,
+        deps: Deps<Q>,
+        env: &Env,
+        coin_in: &Coin,
+        denom_out: &str,
+        max_hops: u8,
+    ) -> ContractResult<(R, Uint128)> {
+        let max_hops = max_hops.min(MAX_HOPS) as usize;
+        let pools = self
+            .candidate_pools
+            .range(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?;
+
+        let mut best: Option<(R, Uint128)> = None;
+        let mut paths: Vec<(Vec<(String, String)>, &str)> = vec![(vec![], coin_in.denom.as_str())];
+        while let Some((path, denom)) = paths.pop() {
+            if denom == denom_out {
+                let route = match R::from_candidate_path(&path) {
+                    Ok(route) => route,
+                    Err(_) => continue,
+                };
+                if route.validate(&deps.querier, &coin_in.denom, denom_out).is_err() {
+                    continue;
+                }
+                let amount = match route.estimate_exact_in_swap(&deps.querier, env, coin_in, None) {
+                    Ok(res) => res.amount,
+                    Err(_) => continue,
+                };
+                if best.as_ref().map_or(true, |(_, best_amount)| amount > *best_amount) {
+                    best = Some((route, amount));
+                }
+                continue;
+            }
+
+            if path.len() >= max_hops {
+                continue;
+            }
+
+            for (pool_id, denoms) in &pools {
+                if !denoms.iter().any(|d| *d == denom) || path.iter().any(|(id, _)| id == pool_id) {
+                    continue;
+                }
+                // Skip denoms already seen, a route must not contain a loop
+                for next in denoms {
+                    if *next == denom
+                        || *next == coin_in.denom
+                        || path.iter().any(|(_, seen)| seen == next)
+                    {
+                        continue;
+                    }
+                    let mut next_path = path.clone();
+                    next_path.push((pool_id.clone(), next.clone()));
+                    paths.push((next_path, next.as_str()));
+                }
+            }
+        }
+
+        best.ok_or_else(|| ContractError::NoRouteFound {
+            denom_in: coin_in.denom.clone(),
+            denom_out: denom_out.to_string(),
+        })
+    }
+
+    /// Loads the route for the pair. If none was set and the fallback is enabled, the best route
+    /// through the candidate pools is used instead.
+    fn load_exact_in_route(
+        &self,
+        deps: Deps<Q>,
+        env: &Env,
+        coin_in: &Coin,
+        denom_out: &str,
+    ) -> ContractResult<StoredRoute<R>> {
+        let key = (coin_in.denom.clone(), denom_out.to_string());
+        if self.routes.has(deps.storage, key.clone()) {
+            return self.load_route(deps, key);
+        }
+
+        match self.fallback_max_hops.may_load(deps.storage)? {
+            Some(max_hops) => {
+                let (route, _) = self.best_route(deps, env, coin_in, denom_out, max_hops)?;
+                Ok(StoredRoute {
+                    route,
+                    twap_window: None,
+                })
+            }
+            None => self.load_route(deps, key),
+        }
+    }
+
+    fn load_route(&self, deps: Deps<Q>, key: (String, String)) -> ContractResult<StoredRoute<R>> {
+        Ok(StoredRoute {
+            route: self.routes.load(deps.storage, key.clone())?,
+            twap_window: self.route_twap_windows.may_load(deps.storage, key)?,
+        })
+    }
+
     fn estimate_exact_in_swap(
         &self,
         deps: Deps<Q>,
@@ -160,8 +414,35 @@ where
         coin_in: Coin,
         denom_out: String,
     ) -> ContractResult<EstimateExactInSwapResponse> {
-        let route = self.routes.load(deps.storage, (coin_in.denom.clone(), denom_out))?;
-        route.estimate_exact_in_swap(&deps.querier, &env, &coin_in)
+        let stored = self.load_exact_in_route(deps, &env, &coin_in, &denom_out)?;
+        let estimated = stored.route.estimate_exact_in_swap(
+            &deps.querier,
+            &env,
+            &coin_in,
+            stored.twap_window,
+        )?;
+        self.assert_within_oracle_tolerance(deps, estimated.amount, &coin_in, &denom_out)?;
+        Ok(estimated)
+    }
+
+    /// Exact-out swaps only use explicit routes. The fallback is ranked by exact-in output, which
+    /// says nothing about the input a given output requires.
+    fn estimate_exact_out_swap(
+        &self,
+        deps: Deps<Q>,
+        env: Env,
+        coin_out: Coin,
+        denom_in: String,
+    ) -> ContractResult<EstimateExactOutSwapResponse> {
+        let stored = self.load_route(deps, (denom_in.clone(), coin_out.denom.clone()))?;
+        self.assert_exact_out_within_oracle_tolerance(deps, &env, &stored, &coin_out, &denom_in)?;
+        stored.route.estimate_exact_out_swap(
+            &deps.querier,
+            &env,
+            &coin_out,
+            &denom_in,
+            stored.twap_window,
+        )
     }
 
     fn swap_exact_in(
@@ -173,10 +454,25 @@ where
         denom_out: String,
         slippage: Decimal,
     ) -> ContractResult<Response<M>> {
-        let swap_msg = self
-            .routes
-            .load(deps.storage, (coin_in.denom.clone(), denom_out.clone()))?
-            .build_exact_in_swap_msg(&deps.querier, &env, &coin_in, slippage)?;
+        let stored = self.load_exact_in_route(deps.as_ref(), &env, &coin_in, &denom_out)?;
+        let estimated = stored
+            .route
+            .estimate_exact_in_swap(&deps.querier, &env, &coin_in, stored.twap_window)?
+            .amount;
+        self.assert_within_oracle_tolerance(deps.as_ref(), estimated, &coin_in, &denom_out)?;
+        let swap_msgs = stored.route.build_exact_in_swap_msgs(
+            &deps.querier,
+            &env,
+            &coin_in,
+            slippage,
+            stored.twap_window,
+        )?;
+
+        // The minimum applies to the combined output of every swap message, so routes split
+        // across several paths are not held to a minimum per path
+        let min_share = Decimal::one().checked_sub(slippage)?;
+        let min_receive =
+            estimated.checked_multiply_ratio(min_share.numerator(), min_share.denominator())?;
 
         // Check balance of result of swapper and send back result to sender
         let transfer_msg = CosmosMsg::Wasm(WasmMsg::Execute {
@@ -186,11 +482,12 @@ where
                 recipient: info.sender,
                 denom_in: coin_in.denom.clone(),
                 denom_out: denom_out.clone(),
+                min_receive: Some(min_receive),
             })?,
         });
 
         Ok(Response::new()
-            .add_message(swap_msg)
+            .add_messages(swap_msgs)
             .add_message(transfer_msg)
             .add_attribute("action", "swap_fn")
             .add_attribute("denom_in", coin_in.denom)
@@ -199,6 +496,98 @@ where
             .add_attribute("slippage", slippage.to_string()))
     }
 
+    /// Refuses the swap if the route's estimated output differs from the output implied by oracle
+    /// prices by more than the configured tolerance. Guards against routes through thin or
+    /// manipulated pools.
+    fn assert_within_oracle_tolerance(
+        &self,
+        deps: Deps<Q>,
+        estimated: Uint128,
+        coin_in: &Coin,
+        denom_out: &str,
+    ) -> ContractResult<()> {
+        let oracle_check = match self.oracle_check.may_load(deps.storage)? {
+            Some(oracle_check) => oracle_check,
+            None => return Ok(()),
+        };
+
+        let oracle_estimated =
+            oracle_amount(deps, &oracle_check, coin_in.amount, &coin_in.denom, denom_out)?;
+
+        assert_deviation_within(estimated, oracle_estimated, oracle_check.tolerance)
+    }
+
+    /// Exact-out counterpart of `assert_within_oracle_tolerance`, comparing the route's estimated
+    /// input against the input implied by oracle prices
+    fn assert_exact_out_within_oracle_tolerance(
+        &self,
+        deps: Deps<Q>,
+        env: &Env,
+        stored: &StoredRoute<R>,
+        coin_out: &Coin,
+        denom_in: &str,
+    ) -> ContractResult<()> {
+        let oracle_check = match self.oracle_check.may_load(deps.storage)? {
+            Some(oracle_check) => oracle_check,
+            None => return Ok(()),
+        };
+
+        let estimated = stored
+            .route
+            .estimate_exact_out_swap(&deps.querier, env, coin_out, denom_in, stored.twap_window)?
+            .amount;
+        let oracle_estimated =
+            oracle_amount(deps, &oracle_check, coin_out.amount, &coin_out.denom, denom_in)?;
+
+        assert_deviation_within(estimated, oracle_estimated, oracle_check.tolerance)
+    }
+
+    fn swap_exact_out(
+        &self,
+        deps: DepsMut<Q>,
+        env: Env,
+        info: MessageInfo,
+        coin_out: Coin,
+        denom_in: String,
+        max_in: Uint128,
+    ) -> ContractResult<Response<M>> {
+        let max_coin_in = Coin {
+            denom: denom_in.clone(),
+            amount: max_in,
+        };
+        let stored = self.load_route(deps.as_ref(), (denom_in.clone(), coin_out.denom.clone()))?;
+        self.assert_exact_out_within_oracle_tolerance(
+            deps.as_ref(),
+            &env,
+            &stored,
+            &coin_out,
+            &denom_in,
+        )?;
+        let swap_msg =
+            stored.route.build_exact_out_swap_msg(&deps.querier, &env, &coin_out, &max_coin_in)?;
+
+        // Send back result of swapper to sender, along with any unspent input
+        let transfer_msg = CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: env.contract.address.to_string(),
+            funds: vec![],
+            msg: to_binary(&ExecuteMsg::<R>::TransferResult {
+                recipient: info.sender,
+                denom_in: denom_in.clone(),
+                denom_out: coin_out.denom.clone(),
+                min_receive: None,
+            })?,
+        });
+
+        Ok(Response::new()
+            .add_message(swap_msg)
+            .add_message(transfer_msg)
+            .add_attribute("action", "swap_exact_out")
+            .add_attribute("denom_in", denom_in)
+            .add_attribute("max_in", max_in)
+            .add_attribute("coin_out", coin_out.to_string()))
+    }
+
+    #[allow(clippy::too_many_arguments)]
     fn transfer_result(
         &self,
         deps: DepsMut<Q>,
@@ -207,6 +596,7 @@ where
         recipient: Addr,
         denom_in: String,
         denom_out: String,
+        min_receive: Option<Uint128>,
     ) -> ContractResult<Response<M>> {
         // Internal callback only
         if info.sender != env.contract.address {
@@ -221,6 +611,16 @@ where
             deps.querier.query_balance(env.contract.address.clone(), denom_in)?;
         let denom_out_balance = deps.querier.query_balance(env.contract.address, denom_out)?;
 
+        if let Some(min_receive) = min_receive {
+            if denom_out_balance.amount < min_receive {
+                return Err(ContractError::MinReceiveNotMet {
+                    denom: denom_out_balance.denom,
+                    received: denom_out_balance.amount,
+                    min_receive,
+                });
+            }
+        }
+
         let transfer_msg = CosmosMsg::Bank(BankMsg::Send {
             to_address: recipient.to_string(),
             amount: vec![denom_in_balance, denom_out_balance]
@@ -240,18 +640,175 @@ where
         denom_in: String,
         denom_out: String,
         route: R,
+        twap_window: Option<u64>,
     ) -> ContractResult<Response<M>> {
         self.owner.assert_owner(deps.storage, &sender)?;
 
-        route.validate(&deps.querier, &denom_in, &denom_out)?;
-
-        self.routes.save(deps.storage, (denom_in.clone(), denom_out.clone()), &route)?;
+        let stored = self.save_route(deps, &denom_in, &denom_out, route, twap_window)?;
 
         Ok(Response::new()
             .add_attribute("action", "rover/base/set_route")
             .add_attribute("denom_in", denom_in)
             .add_attribute("denom_out", denom_out)
-            .add_attribute("route", route.to_string()))
+            .add_attribute("route", stored.route.to_string())
+            .add_attribute(
+                "twap_window",
+                stored.twap_window.map(|w| w.to_string()).unwrap_or_else(|| "default".to_string()),
+            ))
+    }
+
+    fn set_routes(
+        &self,
+        mut deps: DepsMut<Q>,
+        sender: Addr,
+        routes: Vec<NewRoute<R>>,
+    ) -> ContractResult<Response<M>> {
+        self.owner.assert_owner(deps.storage, &sender)?;
+
+        let count = routes.len();
+        for new_route in routes {
+            self.save_route(
+                deps.branch(),
+                &new_route.denom_in,
+                &new_route.denom_out,
+                new_route.route,
+                new_route.twap_window,
+            )?;
+        }
+
+        Ok(Response::new()
+            .add_attribute("action", "rover/base/set_routes")
+            .add_attribute("count", count.to_string()))
+    }
+
+    fn save_route(
+        &self,
+        deps: DepsMut<Q>,
+        denom_in: &str,
+        denom_out: &str,
+        route: R,
+        twap_window: Option<u64>,
+    ) -> ContractResult<StoredRoute<R>> {
+        route.validate(&deps.querier, denom_in, denom_out)?;
+
+        if twap_window == Some(0) {
+            return Err(ContractError::InvalidRoute {
+                reason: "TWAP window must be greater than zero".to_string(),
+            });
+        }
+
+        let key = (denom_in.to_string(), denom_out.to_string());
+        self.routes.save(deps.storage, key.clone(), &route)?;
+        match twap_window {
+            Some(twap_window) => self.route_twap_windows.save(deps.storage, key, &twap_window)?,
+            None => self.route_twap_windows.remove(deps.storage, key),
+        }
+
+        Ok(StoredRoute {
+            route,
+            twap_window,
+        })
+    }
+
+    fn delete_route(
+        &self,
+        deps: DepsMut<Q>,
+        sender: Addr,
+        denom_in: String,
+        denom_out: String,
+    ) -> ContractResult<Response<M>> {
+        self.owner.assert_owner(deps.storage, &sender)?;
+
+        let key = (denom_in.clone(), denom_out.clone());
+        // Errors if there is no route for the pair
+        self.routes.load(deps.storage, key.clone())?;
+        self.routes.remove(deps.storage, key.clone());
+        self.route_twap_windows.remove(deps.storage, key);
+
+        Ok(Response::new()
+            .add_attribute("action", "rover/base/delete_route")
+            .add_attribute("denom_in", denom_in)
+            .add_attribute("denom_out", denom_out))
+    }
+
+    fn update_config(
+        &self,
+        deps: DepsMut<Q>,
+        sender: Addr,
+        oracle_check: Option<ConfigUpdate<OracleCheckUnchecked>>,
+        fallback_max_hops: Option<ConfigUpdate<u8>>,
+    ) -> ContractResult<Response<M>> {
+        self.owner.assert_owner(deps.storage, &sender)?;
+
+        let mut response = Response::new().add_attribute("action", "rover/base/update_config");
+        match oracle_check {
+            Some(ConfigUpdate::Set(unchecked)) => {
+                let oracle_check = validate_oracle_check(deps.api, &unchecked)?;
+                self.oracle_check.save(deps.storage, &oracle_check)?;
+                response = response
+                    .add_attribute("oracle", oracle_check.oracle.address())
+                    .add_attribute("oracle_tolerance", oracle_check.tolerance.to_string());
+            }
+            Some(ConfigUpdate::Clear) => {
+                self.oracle_check.remove(deps.storage);
+                response = response.add_attribute("oracle", "none");
+            }
+            None => {}
+        }
+
+        match fallback_max_hops {
+            Some(ConfigUpdate::Set(max_hops)) => {
+                let max_hops = max_hops.min(MAX_HOPS);
+                self.fallback_max_hops.save(deps.storage, &max_hops)?;
+                response = response.add_attribute("fallback_max_hops", max_hops.to_string());
+            }
+            Some(ConfigUpdate::Clear) => {
+                self.fallback_max_hops.remove(deps.storage);
+                response = response.add_attribute("fallback_max_hops", "none");
+            }
+            None => {}
+        }
+
+        Ok(response)
+    }
+
+    fn update_candidate_pools(
+        &self,
+        deps: DepsMut<Q>,
+        sender: Addr,
+        add: Vec<CandidatePool>,
+        remove: Vec<String>,
+    ) -> ContractResult<Response<M>> {
+        self.owner.assert_owner(deps.storage, &sender)?;
+
+        for pool in &add {
+            if pool.denoms.len() < 2 {
+                return Err(ContractError::InvalidRoute {
+                    reason: format!("candidate pool {} must contain at least two denoms", pool.id),
+                });
+            }
+            self.candidate_pools.save(deps.storage, pool.id.clone(), &pool.denoms)?;
+        }
+
+        for id in &remove {
+            self.candidate_pools.remove(deps.storage, id.clone());
+        }
+
+        let count = self.candidate_pools.keys(deps.storage, None, None, Order::Ascending).count();
+        if count > MAX_CANDIDATE_POOLS {
+            return Err(ContractError::TooManyCandidatePools {
+                count,
+                maximum: MAX_CANDIDATE_POOLS,
+            });
+        }
+
+        Ok(Response::new()
+            .add_attribute("action", "rover/base/update_candidate_pools")
+            .add_attribute(
+                "added",
+                add.iter().map(|pool| pool.id.as_str()).collect::<Vec<_>>().join(","),
+            )
+            .add_attribute("removed", remove.join(",")))
     }
 
     fn update_owner(
@@ -263,3 +820,53 @@ where
         Ok(self.owner.update(deps, info, update)?)
     }
 }
+
+fn validate_oracle_check(
+    api: &dyn Api,
+    unchecked: &OracleCheckUnchecked,
+) -> ContractResult<OracleCheck> {
+    if unchecked.tolerance >= Decimal::one() {
+        return Err(ContractError::InvalidOracleTolerance {
+            tolerance: unchecked.tolerance,
+        });
+    }
+    Ok(unchecked.check(api)?)
+}
+
+/// Amount of `denom_to` worth `amount` of `denom_from` at oracle prices
+fn oracle_amount<Q: CustomQuery>(
+    deps: Deps<Q>,
+    oracle_check: &OracleCheck,
+    amount: Uint128,
+    denom_from: &str,
+    denom_to: &str,
+) -> ContractResult<Uint128> {
+    // Oracle queries are chain agnostic
+    let querier = QuerierWrapper::<Empty>::new(&*deps.querier);
+    let price_from = oracle_check.oracle.query_price(&querier, denom_from)?.price;
+    let price_to = oracle_check.oracle.query_price(&querier, denom_to)?.price;
+    let rate = price_from.checked_div(price_to)?;
+    Ok(amount.checked_multiply_ratio(rate.numerator(), rate.denominator())?)
+}
+
+fn assert_deviation_within(
+    estimated: Uint128,
+    oracle_estimated: Uint128,
+    tolerance: Decimal,
+) -> ContractResult<()> {
+    let deviation = if estimated > oracle_estimated {
+        estimated - oracle_estimated
+    } else {
+        oracle_estimated - estimated
+    };
+    let max_deviation =
+        oracle_estimated.checked_multiply_ratio(tolerance.numerator(), tolerance.denominator())?;
+    if deviation > max_deviation {
+        return Err(ContractError::OracleDeviation {
+            estimated,
+            oracle_estimated,
+            tolerance,
+        });
+    }
+    Ok(())
+}
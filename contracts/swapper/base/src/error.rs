@@ -1,4 +1,7 @@
-use cosmwasm_std::{CheckedMultiplyRatioError, DecimalRangeExceeded, OverflowError, StdError};
+use cosmwasm_std::{
+    CheckedFromRatioError, CheckedMultiplyRatioError, Decimal, DecimalRangeExceeded, OverflowError,
+    StdError, Uint128,
+};
 use mars_owner::OwnerError;
 use mars_rover::error::ContractError as RoverError;
 use thiserror::Error;
@@ -16,12 +19,46 @@ pub enum ContractError {
         reason: String,
     },
 
+    #[error("Swap requires {required} of input, more than the max of {max_in}")]
+    MaxInExceeded {
+        required: Uint128,
+        max_in: Uint128,
+    },
+
+    #[error("Received {received} {denom}, less than the minimum of {min_receive}")]
+    MinReceiveNotMet {
+        denom: String,
+        received: Uint128,
+        min_receive: Uint128,
+    },
+
+    #[error("No route found from {denom_in} to {denom_out}")]
+    NoRouteFound {
+        denom_in: String,
+        denom_out: String,
+    },
+
     #[error("{0}")]
     Overflow(#[from] OverflowError),
 
     #[error("{0}")]
     CheckedMultiplyRatio(#[from] CheckedMultiplyRatioError),
 
+    #[error("{0}")]
+    CheckedFromRatio(#[from] CheckedFromRatioError),
+
+    #[error("Oracle tolerance must be less than 1, got {tolerance}")]
+    InvalidOracleTolerance {
+        tolerance: Decimal,
+    },
+
+    #[error("Route estimate of {estimated} deviates from oracle estimate of {oracle_estimated} by more than {tolerance}")]
+    OracleDeviation {
+        estimated: Uint128,
+        oracle_estimated: Uint128,
+        tolerance: Decimal,
+    },
+
     #[error("{denom_a:?}-{denom_b:?} is not an available pool")]
     PoolNotFound {
         denom_a: String,
@@ -31,6 +68,12 @@ pub enum ContractError {
     #[error("{0}")]
     Rover(#[from] RoverError),
 
+    #[error("Registry would hold {count} candidate pools, more than the max of {maximum}")]
+    TooManyCandidatePools {
+        count: usize,
+        maximum: usize,
+    },
+
     #[error("{0}")]
     Std(#[from] StdError),
 }
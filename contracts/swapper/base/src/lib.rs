@@ -1,7 +1,9 @@
 mod contract;
 mod error;
+mod split;
 mod traits;
 
 pub use contract::*;
 pub use error::*;
+pub use split::*;
 pub use traits::*;
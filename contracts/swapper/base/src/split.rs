@@ -0,0 +1,184 @@
+use std::fmt;
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    Coin, CosmosMsg, CustomMsg, CustomQuery, Decimal, Env, Fraction, QuerierWrapper, Uint128,
+};
+use mars_rover::adapters::swap::{EstimateExactInSwapResponse, EstimateExactOutSwapResponse};
+
+use crate::{ContractError, ContractResult, Route};
+
+#[cw_serde]
+pub struct WeightedPath<R> {
+    /// Share of the swap input sent through this path
+    pub weight: Decimal,
+    pub route: R,
+}
+
+/// A route dividing the swap input across several paths to reduce price impact.
+/// Weights must sum to one; any rounding remainder of the input goes through the last path.
+#[cw_serde]
+pub struct SplitRoute<R> {
+    pub paths: Vec<WeightedPath<R>>,
+}
+
+impl<R: fmt::Display> fmt::Display for SplitRoute<R> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = self
+            .paths
+            .iter()
+            .map(|path| format!("{}*({})", path.weight, path.route))
+            .collect::<Vec<_>>()
+            .join(",");
+        write!(f, "{s}")
+    }
+}
+
+impl<R> SplitRoute<R> {
+    /// Divide the input across the paths by weight
+    fn split_coin_in(&self, coin_in: &Coin) -> ContractResult<Vec<(&R, Coin)>> {
+        let mut remaining = coin_in.amount;
+        let last = self.paths.len().saturating_sub(1);
+        self.paths
+            .iter()
+            .enumerate()
+            .map(|(i, path)| {
+                let amount = if i == last {
+                    remaining
+                } else {
+                    coin_in.amount.checked_multiply_ratio(
+                        path.weight.numerator(),
+                        path.weight.denominator(),
+                    )?
+                };
+                remaining = remaining.checked_sub(amount)?;
+                Ok((
+                    &path.route,
+                    Coin {
+                        denom: coin_in.denom.clone(),
+                        amount,
+                    },
+                ))
+            })
+            .collect()
+    }
+}
+
+impl<M, Q, R> Route<M, Q> for SplitRoute<R>
+where
+    M: CustomMsg,
+    Q: CustomQuery,
+    R: Route<M, Q>,
+{
+    fn validate(
+        &self,
+        querier: &QuerierWrapper<Q>,
+        denom_in: &str,
+        denom_out: &str,
+    ) -> ContractResult<()> {
+        if self.paths.is_empty() {
+            return Err(ContractError::InvalidRoute {
+                reason: "a split route must contain at least one path".to_string(),
+            });
+        }
+
+        let mut total_weight = Decimal::zero();
+        for (i, path) in self.paths.iter().enumerate() {
+            if path.weight.is_zero() {
+                return Err(ContractError::InvalidRoute {
+                    reason: format!("path {}: weight must be greater than zero", i + 1),
+                });
+            }
+            total_weight = total_weight.checked_add(path.weight)?;
+            path.route.validate(querier, denom_in, denom_out)?;
+        }
+
+        if total_weight != Decimal::one() {
+            return Err(ContractError::InvalidRoute {
+                reason: format!("path weights must sum to one, got {total_weight}"),
+            });
+        }
+
+        Ok(())
+    }
+
+    fn from_candidate_path(path: &[(String, String)]) -> ContractResult<Self> {
+        Ok(SplitRoute {
+            paths: vec![WeightedPath {
+                weight: Decimal::one(),
+                route: R::from_candidate_path(path)?,
+            }],
+        })
+    }
+
+    /// Paths are swapped with the widest slippage allowance their routes accept. The swapper
+    /// checks the combined output against the minimum once every path has been swapped.
+    fn build_exact_in_swap_msgs(
+        &self,
+        querier: &QuerierWrapper<Q>,
+        env: &Env,
+        coin_in: &Coin,
+        _slippage: Decimal,
+        twap_window: Option<u64>,
+    ) -> ContractResult<Vec<CosmosMsg<M>>> {
+        let mut msgs = vec![];
+        for (route, path_coin_in) in self.split_coin_in(coin_in)? {
+            if path_coin_in.amount.is_zero() {
+                continue;
+            }
+            msgs.extend(route.build_exact_in_swap_msgs(
+                querier,
+                env,
+                &path_coin_in,
+                Decimal::one(),
+                twap_window,
+            )?);
+        }
+        Ok(msgs)
+    }
+
+    fn estimate_exact_in_swap(
+        &self,
+        querier: &QuerierWrapper<Q>,
+        env: &Env,
+        coin_in: &Coin,
+        twap_window: Option<u64>,
+    ) -> ContractResult<EstimateExactInSwapResponse> {
+        let mut amount = Uint128::zero();
+        for (route, path_coin_in) in self.split_coin_in(coin_in)? {
+            if path_coin_in.amount.is_zero() {
+                continue;
+            }
+            let res = route.estimate_exact_in_swap(querier, env, &path_coin_in, twap_window)?;
+            amount = amount.checked_add(res.amount)?;
+        }
+        Ok(EstimateExactInSwapResponse {
+            amount,
+        })
+    }
+
+    fn build_exact_out_swap_msg(
+        &self,
+        _querier: &QuerierWrapper<Q>,
+        _env: &Env,
+        _coin_out: &Coin,
+        _max_in: &Coin,
+    ) -> ContractResult<CosmosMsg<M>> {
+        Err(ContractError::InvalidRoute {
+            reason: "exact-out swaps are not supported by split routes".to_string(),
+        })
+    }
+
+    fn estimate_exact_out_swap(
+        &self,
+        _querier: &QuerierWrapper<Q>,
+        _env: &Env,
+        _coin_out: &Coin,
+        _denom_in: &str,
+        _twap_window: Option<u64>,
+    ) -> ContractResult<EstimateExactOutSwapResponse> {
+        Err(ContractError::InvalidRoute {
+            reason: "exact-out swaps are not supported by split routes".to_string(),
+        })
+    }
+}
@@ -1,7 +1,7 @@
 use std::fmt::{Debug, Display};
 
 use cosmwasm_std::{Coin, CosmosMsg, CustomMsg, CustomQuery, Decimal, Env, QuerierWrapper};
-use mars_rover::adapters::swap::EstimateExactInSwapResponse;
+use mars_rover::adapters::swap::{EstimateExactInSwapResponse, EstimateExactOutSwapResponse};
 use schemars::JsonSchema;
 use serde::{de::DeserializeOwned, Serialize};
 
@@ -21,14 +21,20 @@ where
         denom_out: &str,
     ) -> ContractResult<()>;
 
-    /// Build a message for executing the trade, given an input denom and amount
-    fn build_exact_in_swap_msg(
+    /// Build a route from a path through candidate pools, given as (pool id, output denom) steps.
+    /// Used to discover routes for pairs without an explicit route.
+    fn from_candidate_path(path: &[(String, String)]) -> ContractResult<Self>;
+
+    /// Build the messages for executing the trade, given an input denom and amount.
+    /// `twap_window` is the window in seconds to price the route with, if configured.
+    fn build_exact_in_swap_msgs(
         &self,
         querier: &QuerierWrapper<Q>,
         env: &Env,
         coin_in: &Coin,
         slippage: Decimal,
-    ) -> ContractResult<CosmosMsg<M>>;
+        twap_window: Option<u64>,
+    ) -> ContractResult<Vec<CosmosMsg<M>>>;
 
     /// Query to get the estimate result of a swap
     fn estimate_exact_in_swap(
@@ -36,5 +42,26 @@ where
         querier: &QuerierWrapper<Q>,
         env: &Env,
         coin_in: &Coin,
+        twap_window: Option<u64>,
     ) -> ContractResult<EstimateExactInSwapResponse>;
+
+    /// Build a message for executing the trade, given an output denom and amount. No more than
+    /// `max_in` may be spent.
+    fn build_exact_out_swap_msg(
+        &self,
+        querier: &QuerierWrapper<Q>,
+        env: &Env,
+        coin_out: &Coin,
+        max_in: &Coin,
+    ) -> ContractResult<CosmosMsg<M>>;
+
+    /// Query to get the estimated input needed for an exact-out swap
+    fn estimate_exact_out_swap(
+        &self,
+        querier: &QuerierWrapper<Q>,
+        env: &Env,
+        coin_out: &Coin,
+        denom_in: &str,
+        twap_window: Option<u64>,
+    ) -> ContractResult<EstimateExactOutSwapResponse>;
 }
@@ -3,10 +3,12 @@ use cosmwasm_std::{
     MessageInfo, Response, StdError, StdResult, Uint128,
 };
 use mars_rover::adapters::swap::{
-    EstimateExactInSwapResponse, ExecuteMsg, InstantiateMsg, QueryMsg,
+    EstimateExactInSwapResponse, EstimateExactOutSwapResponse, ExecuteMsg, InstantiateMsg, QueryMsg,
 };
 
 pub const MOCK_SWAP_RESULT: Uint128 = Uint128::new(1337);
+/// The amount of denom in spent for any exact-out swap. The rest of max in is refunded.
+pub const MOCK_SWAP_EXACT_OUT_COST: Uint128 = Uint128::new(42);
 
 #[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
 pub fn instantiate(
@@ -30,6 +32,18 @@ pub fn execute(
         ExecuteMsg::SetRoute {
             ..
         } => unimplemented!("not implemented"),
+        ExecuteMsg::SetRoutes {
+            ..
+        } => unimplemented!("not implemented"),
+        ExecuteMsg::DeleteRoute {
+            ..
+        } => unimplemented!("not implemented"),
+        ExecuteMsg::UpdateConfig {
+            ..
+        } => unimplemented!("not implemented"),
+        ExecuteMsg::UpdateCandidatePools {
+            ..
+        } => unimplemented!("not implemented"),
         ExecuteMsg::TransferResult {
             ..
         } => unimplemented!("not implemented"),
@@ -38,6 +52,11 @@ pub fn execute(
             denom_out,
             slippage,
         } => swap_exact_in(deps, env, info, coin_in, denom_out, slippage),
+        ExecuteMsg::SwapExactOut {
+            coin_out,
+            denom_in,
+            max_in,
+        } => swap_exact_out(deps, env, info, coin_out, denom_in, max_in),
     }
 }
 
@@ -47,15 +66,30 @@ pub fn query(_deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::Owner {
             ..
         } => unimplemented!("not implemented"),
+        QueryMsg::Config {
+            ..
+        } => unimplemented!("not implemented"),
         QueryMsg::Route {
             ..
         } => unimplemented!("not implemented"),
         QueryMsg::Routes {
             ..
         } => unimplemented!("not implemented"),
+        QueryMsg::ValidateRoutes {
+            ..
+        } => unimplemented!("not implemented"),
+        QueryMsg::CandidatePools {
+            ..
+        } => unimplemented!("not implemented"),
+        QueryMsg::BestRoute {
+            ..
+        } => unimplemented!("not implemented"),
         QueryMsg::EstimateExactInSwap {
             ..
         } => to_binary(&estimate_exact_in_swap()),
+        QueryMsg::EstimateExactOutSwap {
+            ..
+        } => to_binary(&estimate_exact_out_swap()),
     }
 }
 
@@ -65,6 +99,12 @@ pub fn estimate_exact_in_swap() -> EstimateExactInSwapResponse {
     }
 }
 
+pub fn estimate_exact_out_swap() -> EstimateExactOutSwapResponse {
+    EstimateExactOutSwapResponse {
+        amount: MOCK_SWAP_EXACT_OUT_COST,
+    }
+}
+
 pub fn swap_exact_in(
     deps: DepsMut,
     env: Env,
@@ -91,3 +131,43 @@ pub fn swap_exact_in(
 
     Ok(Response::new().add_attribute("action", "transfer_result").add_message(transfer_msg))
 }
+
+pub fn swap_exact_out(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    coin_out: Coin,
+    denom_in: String,
+    max_in: Uint128,
+) -> StdResult<Response> {
+    let denom_in_balance = deps.querier.query_balance(env.contract.address, &denom_in)?;
+    if denom_in_balance.amount < max_in {
+        return Err(StdError::generic_err("Did not send funds"));
+    }
+
+    if max_in < MOCK_SWAP_EXACT_OUT_COST {
+        return Err(StdError::generic_err("Max in is lesser than required amount in"));
+    }
+
+    if coin_out.denom != "uosmo" {
+        return Err(StdError::generic_err("Mock swapper can only have uosmo as denom out"));
+    }
+
+    // This is dependent on the mock env to pre-fund this contract with uosmo coins
+    // simulating a swap has taken place
+    let mut amount = vec![coin_out];
+    let refund = max_in - MOCK_SWAP_EXACT_OUT_COST;
+    if !refund.is_zero() {
+        amount.push(Coin {
+            denom: denom_in,
+            amount: refund,
+        });
+    }
+    amount.sort_by(|a, b| a.denom.cmp(&b.denom));
+    let transfer_msg = CosmosMsg::Bank(BankMsg::Send {
+        to_address: info.sender.to_string(),
+        amount,
+    });
+
+    Ok(Response::new().add_attribute("action", "transfer_result").add_message(transfer_msg))
+}
@@ -3,10 +3,10 @@ use cw2::set_contract_version;
 use mars_rover::adapters::swap::{ExecuteMsg, InstantiateMsg, QueryMsg};
 use mars_swapper_base::{ContractResult, SwapBase};
 
-use crate::route::OsmosisRoute;
+use crate::route::OsmosisSwapRoute;
 
 /// The Osmosis swapper contract inherits logic from the base swapper contract
-pub type OsmosisSwap<'a> = SwapBase<'a, Empty, Empty, OsmosisRoute>;
+pub type OsmosisSwap<'a> = SwapBase<'a, Empty, Empty, OsmosisSwapRoute>;
 
 const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -27,7 +27,7 @@ pub fn execute(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
-    msg: ExecuteMsg<OsmosisRoute>,
+    msg: ExecuteMsg<OsmosisSwapRoute>,
 ) -> ContractResult<Response> {
     OsmosisSwap::default().execute(deps, env, info, msg)
 }
@@ -5,14 +5,18 @@ use cosmwasm_std::{
     BlockInfo, Coin, CosmosMsg, Decimal, Empty, Env, Fraction, QuerierWrapper, Uint128,
 };
 use mars_osmosis::helpers::{has_denom, query_arithmetic_twap_price, query_pool};
-use mars_rover::adapters::swap::EstimateExactInSwapResponse;
-use mars_swapper_base::{ContractError, ContractResult, Route};
-use osmosis_std::types::osmosis::gamm::v1beta1::{MsgSwapExactAmountIn, SwapAmountInRoute};
+use mars_rover::adapters::swap::{EstimateExactInSwapResponse, EstimateExactOutSwapResponse};
+use mars_swapper_base::{ContractError, ContractResult, Route, SplitRoute};
+use osmosis_std::types::osmosis::gamm::v1beta1::{
+    MsgSwapExactAmountIn, MsgSwapExactAmountOut, SwapAmountInRoute, SwapAmountOutRoute,
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 
 use crate::helpers::hashset;
 
-/// 10 min in seconds (Risk Team recommendation)
-const TWAP_WINDOW_SIZE_SECONDS: u64 = 600u64;
+/// 10 min in seconds (Risk Team recommendation). Used for routes without a configured window.
+pub const TWAP_WINDOW_SIZE_SECONDS: u64 = 600u64;
 
 #[cw_serde]
 pub struct OsmosisRoute(pub Vec<SwapAmountInRoute>);
@@ -29,6 +33,134 @@ impl fmt::Display for OsmosisRoute {
     }
 }
 
+/// The route stored by the Osmosis swapper: a single path, or a split across weighted paths.
+/// Untagged so single-path routes keep the plain `OsmosisRoute` format.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(untagged)]
+pub enum OsmosisSwapRoute {
+    Single(OsmosisRoute),
+    Split(SplitRoute<OsmosisRoute>),
+}
+
+impl From<OsmosisRoute> for OsmosisSwapRoute {
+    fn from(route: OsmosisRoute) -> Self {
+        Self::Single(route)
+    }
+}
+
+impl fmt::Display for OsmosisSwapRoute {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            OsmosisSwapRoute::Single(route) => write!(f, "{route}"),
+            OsmosisSwapRoute::Split(route) => write!(f, "{route}"),
+        }
+    }
+}
+
+impl Route<Empty, Empty> for OsmosisSwapRoute {
+    fn validate(
+        &self,
+        querier: &QuerierWrapper,
+        denom_in: &str,
+        denom_out: &str,
+    ) -> ContractResult<()> {
+        match self {
+            OsmosisSwapRoute::Single(route) => route.validate(querier, denom_in, denom_out),
+            OsmosisSwapRoute::Split(route) => {
+                Route::<Empty, Empty>::validate(route, querier, denom_in, denom_out)
+            }
+        }
+    }
+
+    fn from_candidate_path(path: &[(String, String)]) -> ContractResult<Self> {
+        Ok(OsmosisSwapRoute::Single(OsmosisRoute::from_candidate_path(path)?))
+    }
+
+    fn build_exact_in_swap_msgs(
+        &self,
+        querier: &QuerierWrapper,
+        env: &Env,
+        coin_in: &Coin,
+        slippage: Decimal,
+        twap_window: Option<u64>,
+    ) -> ContractResult<Vec<CosmosMsg>> {
+        match self {
+            OsmosisSwapRoute::Single(route) => {
+                route.build_exact_in_swap_msgs(querier, env, coin_in, slippage, twap_window)
+            }
+            OsmosisSwapRoute::Split(route) => Route::<Empty, Empty>::build_exact_in_swap_msgs(
+                route,
+                querier,
+                env,
+                coin_in,
+                slippage,
+                twap_window,
+            ),
+        }
+    }
+
+    fn estimate_exact_in_swap(
+        &self,
+        querier: &QuerierWrapper,
+        env: &Env,
+        coin_in: &Coin,
+        twap_window: Option<u64>,
+    ) -> ContractResult<EstimateExactInSwapResponse> {
+        match self {
+            OsmosisSwapRoute::Single(route) => {
+                route.estimate_exact_in_swap(querier, env, coin_in, twap_window)
+            }
+            OsmosisSwapRoute::Split(route) => Route::<Empty, Empty>::estimate_exact_in_swap(
+                route,
+                querier,
+                env,
+                coin_in,
+                twap_window,
+            ),
+        }
+    }
+
+    fn build_exact_out_swap_msg(
+        &self,
+        querier: &QuerierWrapper,
+        env: &Env,
+        coin_out: &Coin,
+        max_in: &Coin,
+    ) -> ContractResult<CosmosMsg> {
+        match self {
+            OsmosisSwapRoute::Single(route) => {
+                route.build_exact_out_swap_msg(querier, env, coin_out, max_in)
+            }
+            OsmosisSwapRoute::Split(route) => Route::<Empty, Empty>::build_exact_out_swap_msg(
+                route, querier, env, coin_out, max_in,
+            ),
+        }
+    }
+
+    fn estimate_exact_out_swap(
+        &self,
+        querier: &QuerierWrapper,
+        env: &Env,
+        coin_out: &Coin,
+        denom_in: &str,
+        twap_window: Option<u64>,
+    ) -> ContractResult<EstimateExactOutSwapResponse> {
+        match self {
+            OsmosisSwapRoute::Single(route) => {
+                route.estimate_exact_out_swap(querier, env, coin_out, denom_in, twap_window)
+            }
+            OsmosisSwapRoute::Split(route) => Route::<Empty, Empty>::estimate_exact_out_swap(
+                route,
+                querier,
+                env,
+                coin_out,
+                denom_in,
+                twap_window,
+            ),
+        }
+    }
+}
+
 impl Route<Empty, Empty> for OsmosisRoute {
     // Perform basic validation of the swap steps
     fn validate(
@@ -101,22 +233,38 @@ impl Route<Empty, Empty> for OsmosisRoute {
         Ok(())
     }
 
+    fn from_candidate_path(path: &[(String, String)]) -> ContractResult<Self> {
+        path.iter()
+            .map(|(pool_id, denom_out)| {
+                Ok(SwapAmountInRoute {
+                    pool_id: pool_id.parse().map_err(|_| ContractError::InvalidRoute {
+                        reason: format!("invalid pool id {pool_id}"),
+                    })?,
+                    token_out_denom: denom_out.clone(),
+                })
+            })
+            .collect::<ContractResult<Vec<_>>>()
+            .map(OsmosisRoute)
+    }
+
     /// Build a CosmosMsg that swaps given an input denom and amount
-    fn build_exact_in_swap_msg(
+    fn build_exact_in_swap_msgs(
         &self,
         querier: &QuerierWrapper,
         env: &Env,
         coin_in: &Coin,
         slippage: Decimal,
-    ) -> ContractResult<CosmosMsg> {
+        twap_window: Option<u64>,
+    ) -> ContractResult<Vec<CosmosMsg>> {
         let steps = &self.0;
 
         steps.first().ok_or(ContractError::InvalidRoute {
             reason: "the route must contain at least one step".to_string(),
         })?;
 
-        let out_amount = query_out_amount(querier, &env.block, coin_in, steps)?;
-        let min_out_amount = (Decimal::one() - slippage) * out_amount;
+        let out_amount = query_out_amount(querier, &env.block, coin_in, steps, twap_window)?;
+        // Osmosis rejects a zero minimum, which a full slippage allowance would give
+        let min_out_amount = ((Decimal::one() - slippage) * out_amount).max(Uint128::one());
 
         let swap_msg: CosmosMsg = MsgSwapExactAmountIn {
             sender: env.contract.address.to_string(),
@@ -128,7 +276,7 @@ impl Route<Empty, Empty> for OsmosisRoute {
             token_out_min_amount: min_out_amount.to_string(),
         }
         .into();
-        Ok(swap_msg)
+        Ok(vec![swap_msg])
     }
 
     fn estimate_exact_in_swap(
@@ -136,12 +284,76 @@ impl Route<Empty, Empty> for OsmosisRoute {
         querier: &QuerierWrapper,
         env: &Env,
         coin_in: &Coin,
+        twap_window: Option<u64>,
     ) -> ContractResult<EstimateExactInSwapResponse> {
-        let out_amount = query_out_amount(querier, &env.block, coin_in, &self.0)?;
+        let out_amount = query_out_amount(querier, &env.block, coin_in, &self.0, twap_window)?;
         Ok(EstimateExactInSwapResponse {
             amount: out_amount,
         })
     }
+
+    /// Build a CosmosMsg that swaps given an output denom and amount.
+    /// The max input amount acts as the slippage guard.
+    fn build_exact_out_swap_msg(
+        &self,
+        _querier: &QuerierWrapper,
+        env: &Env,
+        coin_out: &Coin,
+        max_in: &Coin,
+    ) -> ContractResult<CosmosMsg> {
+        let swap_msg: CosmosMsg = MsgSwapExactAmountOut {
+            sender: env.contract.address.to_string(),
+            routes: exact_out_steps(&max_in.denom, &self.0)?,
+            token_in_max_amount: max_in.amount.to_string(),
+            token_out: Some(osmosis_std::types::cosmos::base::v1beta1::Coin {
+                denom: coin_out.denom.clone(),
+                amount: coin_out.amount.to_string(),
+            }),
+        }
+        .into();
+        Ok(swap_msg)
+    }
+
+    fn estimate_exact_out_swap(
+        &self,
+        querier: &QuerierWrapper,
+        env: &Env,
+        coin_out: &Coin,
+        denom_in: &str,
+        twap_window: Option<u64>,
+    ) -> ContractResult<EstimateExactOutSwapResponse> {
+        let price = query_route_price(querier, &env.block, denom_in, &self.0, twap_window)?;
+        // Rounded up, so the estimate is never short of the input the swap requires
+        let in_amount = coin_out.amount.checked_div_ceil(price)?;
+        Ok(EstimateExactOutSwapResponse {
+            amount: in_amount,
+        })
+    }
+}
+
+/// Exact-out routes name the input denom of each pool rather than the output denom
+fn exact_out_steps(
+    denom_in: &str,
+    steps: &[SwapAmountInRoute],
+) -> ContractResult<Vec<SwapAmountOutRoute>> {
+    if steps.is_empty() {
+        return Err(ContractError::InvalidRoute {
+            reason: "the route must contain at least one step".to_string(),
+        });
+    }
+
+    let mut step_denom_in = denom_in.to_string();
+    Ok(steps
+        .iter()
+        .map(|step| {
+            let out_step = SwapAmountOutRoute {
+                pool_id: step.pool_id,
+                token_in_denom: step_denom_in.clone(),
+            };
+            step_denom_in = step.token_out_denom.clone();
+            out_step
+        })
+        .collect())
 }
 
 /// Query how much amount of denom_out we get for denom_in.
@@ -157,11 +369,31 @@ fn query_out_amount(
     block: &BlockInfo,
     coin_in: &Coin,
     steps: &[SwapAmountInRoute],
+    twap_window: Option<u64>,
 ) -> ContractResult<Uint128> {
-    let start_time = block.time.seconds() - TWAP_WINDOW_SIZE_SECONDS;
+    let price = query_route_price(querier, block, &coin_in.denom, steps, twap_window)?;
+    let out_amount =
+        coin_in.amount.checked_multiply_ratio(price.numerator(), price.denominator())?;
+    Ok(out_amount)
+}
+
+/// Query the TWAP price of denom_in denominated in the route's final output denom
+fn query_route_price(
+    querier: &QuerierWrapper,
+    block: &BlockInfo,
+    denom_in: &str,
+    steps: &[SwapAmountInRoute],
+    twap_window: Option<u64>,
+) -> ContractResult<Decimal> {
+    let start_time =
+        block.time.seconds().checked_sub(twap_window.unwrap_or(TWAP_WINDOW_SIZE_SECONDS)).ok_or(
+            ContractError::InvalidRoute {
+                reason: "TWAP window exceeds block time".to_string(),
+            },
+        )?;
 
     let mut price = Decimal::one();
-    let mut denom_in = coin_in.denom.clone();
+    let mut denom_in = denom_in.to_string();
     for step in steps {
         let step_price = query_arithmetic_twap_price(
             querier,
@@ -173,8 +405,5 @@ fn query_out_amount(
         price = price.checked_mul(step_price)?;
         denom_in = step.token_out_denom.clone();
     }
-
-    let out_amount =
-        coin_in.amount.checked_multiply_ratio(price.numerator(), price.denominator())?;
-    Ok(out_amount)
+    Ok(price)
 }
@@ -1,7 +1,8 @@
 use std::{fmt::Display, str::FromStr};
 
-use cosmwasm_std::{Coin, Decimal, Uint128};
-use mars_rover::adapters::swap::InstantiateMsg;
+use cosmwasm_std::{coin, Coin, Decimal, Uint128};
+use mars_mock_oracle::msg::{CoinPrice, InstantiateMsg as OracleInstantiateMsg};
+use mars_rover::adapters::swap::{InstantiateMsg, OracleCheckUnchecked};
 use osmosis_std::types::osmosis::gamm::v1beta1::{
     MsgSwapExactAmountIn, MsgSwapExactAmountInResponse, SwapAmountInRoute,
 };
@@ -13,13 +14,25 @@ use osmosis_test_tube::{
 const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
 
 pub fn wasm_file() -> String {
+    artifact_file(CONTRACT_NAME)
+}
+
+fn artifact_file(contract_name: &str) -> String {
     let artifacts_dir =
         std::env::var("ARTIFACTS_DIR_PATH").unwrap_or_else(|_| "artifacts".to_string());
-    let snaked_name = CONTRACT_NAME.replace('-', "_");
+    let snaked_name = contract_name.replace('-', "_");
     format!("../../../{artifacts_dir}/{snaked_name}.wasm")
 }
 
 pub fn instantiate_contract(wasm: &Wasm<OsmosisTestApp>, owner: &SigningAccount) -> String {
+    instantiate_contract_with_oracle_check(wasm, owner, None)
+}
+
+pub fn instantiate_contract_with_oracle_check(
+    wasm: &Wasm<OsmosisTestApp>,
+    owner: &SigningAccount,
+    oracle_check: Option<OracleCheckUnchecked>,
+) -> String {
     let wasm_byte_code = std::fs::read(wasm_file()).unwrap();
     let code_id = wasm.store_code(&wasm_byte_code, None, owner).unwrap().data.code_id;
 
@@ -27,6 +40,7 @@ pub fn instantiate_contract(wasm: &Wasm<OsmosisTestApp>, owner: &SigningAccount)
         code_id,
         &InstantiateMsg {
             owner: owner.address(),
+            oracle_check,
         },
         None,
         Some("swapper-osmosis-contract"),
@@ -38,6 +52,39 @@ pub fn instantiate_contract(wasm: &Wasm<OsmosisTestApp>, owner: &SigningAccount)
     .address
 }
 
+/// Account funded with enough uatom, uosmo & umars to create pools and swap between them
+pub fn init_signer(app: &OsmosisTestApp) -> SigningAccount {
+    app.init_account(&[
+        coin(1_000_000_000_000, "uatom"),
+        coin(1_000_000_000_000, "uosmo"),
+        coin(1_000_000_000_000, "umars"),
+    ])
+    .unwrap()
+}
+
+pub fn instantiate_mock_oracle(
+    wasm: &Wasm<OsmosisTestApp>,
+    owner: &SigningAccount,
+    prices: Vec<CoinPrice>,
+) -> String {
+    let wasm_byte_code = std::fs::read(artifact_file("mars-mock-oracle")).unwrap();
+    let code_id = wasm.store_code(&wasm_byte_code, None, owner).unwrap().data.code_id;
+
+    wasm.instantiate(
+        code_id,
+        &OracleInstantiateMsg {
+            prices,
+        },
+        None,
+        Some("mock-oracle-contract"),
+        &[],
+        owner,
+    )
+    .unwrap()
+    .data
+    .address
+}
+
 /// Every execution creates new block and block timestamp will +5 secs from last block
 /// (see https://github.com/osmosis-labs/osmosis-rust/issues/53#issuecomment-1311451418).
 ///
@@ -0,0 +1,375 @@
+use cosmwasm_std::{coin, Decimal};
+use mars_owner::OwnerError;
+use mars_rover::adapters::swap::{
+    BestRouteResponse, CandidatePool, CandidatePoolsResponse, ConfigUpdate,
+    EstimateExactInSwapResponse, ExecuteMsg, QueryMsg,
+};
+use mars_swapper_base::{ContractError, MAX_CANDIDATE_POOLS};
+use mars_swapper_osmosis::route::OsmosisRoute;
+use osmosis_test_tube::{Account, Bank, Gamm, Module, OsmosisTestApp, SigningAccount, Wasm};
+
+use crate::helpers::{
+    assert_err, init_signer, instantiate_contract, query_balance, swap_to_create_twap_records,
+};
+
+pub mod helpers;
+
+#[test]
+fn only_owner_can_update_candidate_pools() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+
+    let accs = app.init_accounts(&[coin(1_000_000_000_000, "uosmo")], 2).unwrap();
+    let owner = &accs[0];
+    let bad_guy = &accs[1];
+
+    let contract_addr = instantiate_contract(&wasm, owner);
+
+    let res_err = wasm
+        .execute(
+            &contract_addr,
+            &ExecuteMsg::<OsmosisRoute>::UpdateCandidatePools {
+                add: vec![CandidatePool {
+                    id: "1".to_string(),
+                    denoms: vec!["umars".to_string(), "uosmo".to_string()],
+                }],
+                remove: vec![],
+            },
+            &[],
+            bad_guy,
+        )
+        .unwrap_err();
+
+    assert_err(res_err, OwnerError::NotOwner {});
+}
+
+#[test]
+fn add_and_remove_candidate_pools() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+    let owner = app.init_account(&[coin(1_000_000_000_000, "uosmo")]).unwrap();
+
+    let contract_addr = instantiate_contract(&wasm, &owner);
+
+    let pool_1 = CandidatePool {
+        id: "1".to_string(),
+        denoms: vec!["umars".to_string(), "uosmo".to_string()],
+    };
+    let pool_2 = CandidatePool {
+        id: "2".to_string(),
+        denoms: vec!["uatom".to_string(), "uosmo".to_string()],
+    };
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::<OsmosisRoute>::UpdateCandidatePools {
+            add: vec![pool_1.clone(), pool_2.clone()],
+            remove: vec![],
+        },
+        &[],
+        &owner,
+    )
+    .unwrap();
+
+    let res: CandidatePoolsResponse = wasm
+        .query(
+            &contract_addr,
+            &QueryMsg::CandidatePools {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(res, vec![pool_1.clone(), pool_2]);
+
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::<OsmosisRoute>::UpdateCandidatePools {
+            add: vec![],
+            remove: vec!["2".to_string()],
+        },
+        &[],
+        &owner,
+    )
+    .unwrap();
+
+    let res: CandidatePoolsResponse = wasm
+        .query(
+            &contract_addr,
+            &QueryMsg::CandidatePools {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(res, vec![pool_1]);
+}
+
+#[test]
+fn candidate_pool_needs_two_denoms() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+    let owner = app.init_account(&[coin(1_000_000_000_000, "uosmo")]).unwrap();
+
+    let contract_addr = instantiate_contract(&wasm, &owner);
+
+    let res_err = wasm
+        .execute(
+            &contract_addr,
+            &ExecuteMsg::<OsmosisRoute>::UpdateCandidatePools {
+                add: vec![CandidatePool {
+                    id: "1".to_string(),
+                    denoms: vec!["umars".to_string()],
+                }],
+                remove: vec![],
+            },
+            &[],
+            &owner,
+        )
+        .unwrap_err();
+
+    assert_err(
+        res_err,
+        ContractError::InvalidRoute {
+            reason: "candidate pool 1 must contain at least two denoms".to_string(),
+        },
+    );
+}
+
+#[test]
+fn candidate_pools_are_capped() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+    let owner = app.init_account(&[coin(1_000_000_000_000, "uosmo")]).unwrap();
+
+    let contract_addr = instantiate_contract(&wasm, &owner);
+
+    let pools = (1..=MAX_CANDIDATE_POOLS + 1)
+        .map(|id| CandidatePool {
+            id: id.to_string(),
+            denoms: vec![format!("coin_{id}"), "uosmo".to_string()],
+        })
+        .collect::<Vec<_>>();
+
+    let res_err = wasm
+        .execute(
+            &contract_addr,
+            &ExecuteMsg::<OsmosisRoute>::UpdateCandidatePools {
+                add: pools[..MAX_CANDIDATE_POOLS + 1].to_vec(),
+                remove: vec![],
+            },
+            &[],
+            &owner,
+        )
+        .unwrap_err();
+    assert_err(
+        res_err,
+        ContractError::TooManyCandidatePools {
+            count: MAX_CANDIDATE_POOLS + 1,
+            maximum: MAX_CANDIDATE_POOLS,
+        },
+    );
+
+    // Pools removed in the same update make room for the ones added
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::<OsmosisRoute>::UpdateCandidatePools {
+            add: pools[..MAX_CANDIDATE_POOLS].to_vec(),
+            remove: vec![],
+        },
+        &[],
+        &owner,
+    )
+    .unwrap();
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::<OsmosisRoute>::UpdateCandidatePools {
+            add: pools[MAX_CANDIDATE_POOLS..].to_vec(),
+            remove: vec!["1".to_string()],
+        },
+        &[],
+        &owner,
+    )
+    .unwrap();
+}
+
+#[test]
+fn best_route_picks_highest_output() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+    let signer = init_signer(&app);
+
+    let contract_addr = instantiate_contract(&wasm, &signer);
+    let (pool_mars_osmo, pool_osmo_atom, pool_mars_atom) =
+        setup_candidate_pools(&app, &wasm, &signer, &contract_addr);
+
+    // umars -> uosmo -> uatom prices 1 umars at 0.25 * 0.1 = 0.025 uatom
+    let res: BestRouteResponse<OsmosisRoute> = wasm
+        .query(
+            &contract_addr,
+            &QueryMsg::BestRoute {
+                coin_in: coin(10_000, "umars"),
+                denom_out: "uatom".to_string(),
+                max_hops: 2,
+            },
+        )
+        .unwrap();
+    assert_eq!(res.route.to_string(), format!("{pool_mars_osmo}:uosmo|{pool_osmo_atom}:uatom"));
+
+    // The direct pool prices 1 umars at 0.01 uatom, but is the only route within a single hop
+    let direct: BestRouteResponse<OsmosisRoute> = wasm
+        .query(
+            &contract_addr,
+            &QueryMsg::BestRoute {
+                coin_in: coin(10_000, "umars"),
+                denom_out: "uatom".to_string(),
+                max_hops: 1,
+            },
+        )
+        .unwrap();
+    assert_eq!(direct.route.to_string(), format!("{pool_mars_atom}:uatom"));
+    assert!(res.amount > direct.amount);
+}
+
+#[test]
+fn best_route_errors_if_none_found() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+    let signer = init_signer(&app);
+
+    let contract_addr = instantiate_contract(&wasm, &signer);
+    setup_candidate_pools(&app, &wasm, &signer, &contract_addr);
+
+    let res_err = wasm
+        .query::<_, BestRouteResponse<OsmosisRoute>>(
+            &contract_addr,
+            &QueryMsg::BestRoute {
+                coin_in: coin(10_000, "umars"),
+                denom_out: "uusdc".to_string(),
+                max_hops: 3,
+            },
+        )
+        .unwrap_err();
+
+    assert_err(
+        res_err,
+        ContractError::NoRouteFound {
+            denom_in: "umars".to_string(),
+            denom_out: "uusdc".to_string(),
+        },
+    );
+}
+
+#[test]
+fn swap_falls_back_to_best_route() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+    let signer = init_signer(&app);
+    let user = app.init_account(&[coin(10_000, "umars")]).unwrap();
+
+    let contract_addr = instantiate_contract(&wasm, &signer);
+    setup_candidate_pools(&app, &wasm, &signer, &contract_addr);
+
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::<OsmosisRoute>::UpdateConfig {
+            oracle_check: None,
+            fallback_max_hops: Some(ConfigUpdate::Set(2)),
+        },
+        &[],
+        &signer,
+    )
+    .unwrap();
+
+    let best: BestRouteResponse<OsmosisRoute> = wasm
+        .query(
+            &contract_addr,
+            &QueryMsg::BestRoute {
+                coin_in: coin(10_000, "umars"),
+                denom_out: "uatom".to_string(),
+                max_hops: 2,
+            },
+        )
+        .unwrap();
+    let estimate: EstimateExactInSwapResponse = wasm
+        .query(
+            &contract_addr,
+            &QueryMsg::EstimateExactInSwap {
+                coin_in: coin(10_000, "umars"),
+                denom_out: "uatom".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(estimate.amount, best.amount);
+
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::<OsmosisRoute>::SwapExactIn {
+            coin_in: coin(10_000, "umars"),
+            denom_out: "uatom".to_string(),
+            slippage: Decimal::percent(6),
+        },
+        &[coin(10_000, "umars")],
+        &user,
+    )
+    .unwrap();
+
+    let bank = Bank::new(&app);
+    let atom_balance = query_balance(&bank, &user.address(), "uatom");
+    let mars_balance = query_balance(&bank, &user.address(), "umars");
+    assert!(atom_balance > 0);
+    assert_eq!(mars_balance, 0);
+}
+
+/// Creates umars/uosmo, uosmo/uatom and umars/uatom pools and registers them as candidates.
+/// Returns the pool ids in that order.
+fn setup_candidate_pools(
+    app: &OsmosisTestApp,
+    wasm: &Wasm<OsmosisTestApp>,
+    signer: &SigningAccount,
+    contract_addr: &str,
+) -> (u64, u64, u64) {
+    let gamm = Gamm::new(app);
+    let pool_mars_osmo = gamm
+        .create_basic_pool(&[coin(6_000_000, "umars"), coin(1_500_000, "uosmo")], signer)
+        .unwrap()
+        .data
+        .pool_id;
+    let pool_osmo_atom = gamm
+        .create_basic_pool(&[coin(1_000_000, "uosmo"), coin(100_000, "uatom")], signer)
+        .unwrap()
+        .data
+        .pool_id;
+    let pool_mars_atom = gamm
+        .create_basic_pool(&[coin(1_000_000, "umars"), coin(10_000, "uatom")], signer)
+        .unwrap()
+        .data
+        .pool_id;
+
+    swap_to_create_twap_records(app, signer, pool_mars_osmo, coin(10u128, "umars"), "uosmo");
+
+    wasm.execute(
+        contract_addr,
+        &ExecuteMsg::<OsmosisRoute>::UpdateCandidatePools {
+            add: vec![
+                CandidatePool {
+                    id: pool_mars_osmo.to_string(),
+                    denoms: vec!["umars".to_string(), "uosmo".to_string()],
+                },
+                CandidatePool {
+                    id: pool_osmo_atom.to_string(),
+                    denoms: vec!["uosmo".to_string(), "uatom".to_string()],
+                },
+                CandidatePool {
+                    id: pool_mars_atom.to_string(),
+                    denoms: vec!["umars".to_string(), "uatom".to_string()],
+                },
+            ],
+            remove: vec![],
+        },
+        &[],
+        signer,
+    )
+    .unwrap();
+
+    (pool_mars_osmo, pool_osmo_atom, pool_mars_atom)
+}
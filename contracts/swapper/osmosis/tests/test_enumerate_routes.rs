@@ -35,6 +35,7 @@ fn enumerating_routes() {
             denom_in: "uatom".to_string(),
             denom_out: "umars".to_string(),
             route: routes.get(&("uatom", "umars")).unwrap().clone(),
+            twap_window: None,
         },
         &[],
         &signer,
@@ -47,6 +48,7 @@ fn enumerating_routes() {
             denom_in: "uatom".to_string(),
             denom_out: "uusdc".to_string(),
             route: routes.get(&("uatom", "uusdc")).unwrap().clone(),
+            twap_window: None,
         },
         &[],
         &signer,
@@ -59,6 +61,7 @@ fn enumerating_routes() {
             denom_in: "uosmo".to_string(),
             denom_out: "umars".to_string(),
             route: routes.get(&("uosmo", "umars")).unwrap().clone(),
+            twap_window: Some(3600),
         },
         &[],
         &signer,
@@ -71,16 +74,19 @@ fn enumerating_routes() {
             denom_in: "uatom".to_string(),
             denom_out: "umars".to_string(),
             route: routes.get(&("uatom", "umars")).unwrap().clone(),
+            twap_window: None,
         },
         RouteResponse {
             denom_in: "uatom".to_string(),
             denom_out: "uusdc".to_string(),
             route: routes.get(&("uatom", "uusdc")).unwrap().clone(),
+            twap_window: None,
         },
         RouteResponse {
             denom_in: "uosmo".to_string(),
             denom_out: "umars".to_string(),
             route: routes.get(&("uosmo", "umars")).unwrap().clone(),
+            twap_window: Some(3600),
         },
     ];
 
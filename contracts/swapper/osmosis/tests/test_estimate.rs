@@ -1,5 +1,7 @@
 use cosmwasm_std::{coin, Uint128};
-use mars_rover::adapters::swap::{EstimateExactInSwapResponse, ExecuteMsg, QueryMsg};
+use mars_rover::adapters::swap::{
+    EstimateExactInSwapResponse, EstimateExactOutSwapResponse, ExecuteMsg, QueryMsg,
+};
 use mars_swapper_osmosis::route::OsmosisRoute;
 use osmosis_std::types::osmosis::gamm::v1beta1::SwapAmountInRoute;
 use osmosis_test_tube::{Gamm, Module, OsmosisTestApp, RunnerResult, Wasm};
@@ -58,6 +60,7 @@ fn estimate_swap_one_step() {
                 pool_id: pool_atom_osmo,
                 token_out_denom: "uatom".to_string(),
             }]),
+            twap_window: None,
         },
         &[],
         &signer,
@@ -130,6 +133,7 @@ fn estimate_swap_multi_step() {
                     token_out_denom: "umars".to_string(),
                 },
             ]),
+            twap_window: None,
         },
         &[],
         &signer,
@@ -151,6 +155,7 @@ fn estimate_swap_multi_step() {
                     token_out_denom: "uusdc".to_string(),
                 },
             ]),
+            twap_window: None,
         },
         &[],
         &signer,
@@ -181,3 +186,55 @@ fn estimate_swap_multi_step() {
         .unwrap();
     assert_eq!(res.amount, expected_output);
 }
+
+#[test]
+fn estimate_exact_out_swap_one_step() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+
+    let signer = app
+        .init_account(&[coin(1_000_000_000_000, "uatom"), coin(1_000_000_000_000, "uosmo")])
+        .unwrap();
+
+    let contract_addr = instantiate_contract(&wasm, &signer);
+
+    let gamm = Gamm::new(&app);
+    let pool_atom_osmo = gamm
+        .create_basic_pool(&[coin(1_500_000, "uatom"), coin(6_000_000, "uosmo")], &signer)
+        .unwrap()
+        .data
+        .pool_id;
+
+    swap_to_create_twap_records(&app, &signer, pool_atom_osmo, coin(10u128, "uatom"), "uosmo");
+
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::SetRoute {
+            denom_in: "uosmo".to_string(),
+            denom_out: "uatom".to_string(),
+            route: OsmosisRoute(vec![SwapAmountInRoute {
+                pool_id: pool_atom_osmo,
+                token_out_denom: "uatom".to_string(),
+            }]),
+            twap_window: None,
+        },
+        &[],
+        &signer,
+    )
+    .unwrap();
+
+    let coin_out_amount = Uint128::from(1000u128);
+    let uosmo_price = query_price_from_pool(&gamm, pool_atom_osmo, "uosmo");
+    let expected_input = coin_out_amount.checked_div_ceil(uosmo_price).unwrap();
+
+    let res: EstimateExactOutSwapResponse = wasm
+        .query(
+            &contract_addr,
+            &QueryMsg::EstimateExactOutSwap {
+                coin_out: coin(coin_out_amount.u128(), "uatom"),
+                denom_in: "uosmo".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(res.amount, expected_input);
+}
@@ -33,6 +33,7 @@ fn raises_on_invalid_owner_addr() {
         code_id,
         &InstantiateMsg {
             owner: owner.to_string(),
+            oracle_check: None,
         },
         None,
         Some("swapper-osmosis-contract"),
@@ -0,0 +1,263 @@
+use cosmwasm_std::coin;
+use mars_owner::OwnerError;
+use mars_rover::adapters::swap::{
+    ExecuteMsg, NewRoute, QueryMsg, RouteValidityResponse, RoutesResponse, ValidateRoutesResponse,
+};
+use mars_swapper_base::ContractError;
+use mars_swapper_osmosis::route::OsmosisRoute;
+use osmosis_std::types::osmosis::gamm::v1beta1::SwapAmountInRoute;
+use osmosis_test_tube::{Gamm, Module, OsmosisTestApp, SigningAccount, Wasm};
+
+use crate::helpers::{assert_err, init_signer, instantiate_contract};
+
+pub mod helpers;
+
+#[test]
+fn only_owner_can_set_routes() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+
+    let accs = app.init_accounts(&[coin(1_000_000_000_000, "uosmo")], 2).unwrap();
+    let owner = &accs[0];
+    let bad_guy = &accs[1];
+
+    let contract_addr = instantiate_contract(&wasm, owner);
+
+    let res_err = wasm
+        .execute(
+            &contract_addr,
+            &ExecuteMsg::SetRoutes {
+                routes: vec![new_route(1, "umars", "uosmo")],
+            },
+            &[],
+            bad_guy,
+        )
+        .unwrap_err();
+
+    assert_err(res_err, OwnerError::NotOwner {});
+}
+
+#[test]
+fn set_routes_validates_every_route() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+    let signer = init_signer(&app);
+
+    let contract_addr = instantiate_contract(&wasm, &signer);
+    let (pool_mars_osmo, pool_atom_osmo) = create_pools(&app, &signer);
+
+    let res_err = wasm
+        .execute(
+            &contract_addr,
+            &ExecuteMsg::SetRoutes {
+                routes: vec![
+                    new_route(pool_mars_osmo, "umars", "uosmo"),
+                    new_route(pool_atom_osmo, "umars", "uosmo"),
+                ],
+            },
+            &[],
+            &signer,
+        )
+        .unwrap_err();
+
+    assert_err(
+        res_err,
+        ContractError::InvalidRoute {
+            reason: format!("step 1: pool {pool_atom_osmo} does not contain input denom umars"),
+        },
+    );
+
+    // No route is saved if any fails validation
+    let res: RoutesResponse<OsmosisRoute> = wasm
+        .query(
+            &contract_addr,
+            &QueryMsg::Routes {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert!(res.is_empty());
+}
+
+#[test]
+fn set_routes_and_delete_route() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+    let signer = init_signer(&app);
+
+    let contract_addr = instantiate_contract(&wasm, &signer);
+    let (pool_mars_osmo, pool_atom_osmo) = create_pools(&app, &signer);
+
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::SetRoutes {
+            routes: vec![
+                new_route(pool_mars_osmo, "umars", "uosmo"),
+                new_route(pool_atom_osmo, "uatom", "uosmo"),
+            ],
+        },
+        &[],
+        &signer,
+    )
+    .unwrap();
+
+    let res: RoutesResponse<OsmosisRoute> = wasm
+        .query(
+            &contract_addr,
+            &QueryMsg::Routes {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(res.len(), 2);
+
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::<OsmosisRoute>::DeleteRoute {
+            denom_in: "umars".to_string(),
+            denom_out: "uosmo".to_string(),
+        },
+        &[],
+        &signer,
+    )
+    .unwrap();
+
+    let res: RoutesResponse<OsmosisRoute> = wasm
+        .query(
+            &contract_addr,
+            &QueryMsg::Routes {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(res.len(), 1);
+    assert_eq!(res[0].denom_in, "uatom".to_string());
+
+    // Deleting a missing route errors
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::<OsmosisRoute>::DeleteRoute {
+            denom_in: "umars".to_string(),
+            denom_out: "uosmo".to_string(),
+        },
+        &[],
+        &signer,
+    )
+    .unwrap_err();
+}
+
+#[test]
+fn only_owner_can_delete_route() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+
+    let accs = app.init_accounts(&[coin(1_000_000_000_000, "uosmo")], 2).unwrap();
+    let owner = &accs[0];
+    let bad_guy = &accs[1];
+
+    let contract_addr = instantiate_contract(&wasm, owner);
+
+    let res_err = wasm
+        .execute(
+            &contract_addr,
+            &ExecuteMsg::<OsmosisRoute>::DeleteRoute {
+                denom_in: "umars".to_string(),
+                denom_out: "uosmo".to_string(),
+            },
+            &[],
+            bad_guy,
+        )
+        .unwrap_err();
+
+    assert_err(res_err, OwnerError::NotOwner {});
+}
+
+#[test]
+fn validate_routes_reports_each_route() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+    let signer = init_signer(&app);
+
+    let contract_addr = instantiate_contract(&wasm, &signer);
+    let (pool_mars_osmo, pool_atom_osmo) = create_pools(&app, &signer);
+
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::SetRoutes {
+            routes: vec![
+                new_route(pool_mars_osmo, "umars", "uosmo"),
+                new_route(pool_atom_osmo, "uatom", "uosmo"),
+            ],
+        },
+        &[],
+        &signer,
+    )
+    .unwrap();
+
+    let res: ValidateRoutesResponse = wasm
+        .query(
+            &contract_addr,
+            &QueryMsg::ValidateRoutes {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        res,
+        vec![
+            RouteValidityResponse {
+                denom_in: "uatom".to_string(),
+                denom_out: "uosmo".to_string(),
+                error: None,
+            },
+            RouteValidityResponse {
+                denom_in: "umars".to_string(),
+                denom_out: "uosmo".to_string(),
+                error: None,
+            },
+        ]
+    );
+
+    let res: ValidateRoutesResponse = wasm
+        .query(
+            &contract_addr,
+            &QueryMsg::ValidateRoutes {
+                start_after: Some(("uatom".to_string(), "uosmo".to_string())),
+                limit: Some(1),
+            },
+        )
+        .unwrap();
+    assert_eq!(res.len(), 1);
+    assert_eq!(res[0].denom_in, "umars".to_string());
+}
+
+fn create_pools(app: &OsmosisTestApp, signer: &SigningAccount) -> (u64, u64) {
+    let gamm = Gamm::new(app);
+    let pool_mars_osmo = gamm
+        .create_basic_pool(&[coin(6_000_000, "umars"), coin(1_500_000, "uosmo")], signer)
+        .unwrap()
+        .data
+        .pool_id;
+    let pool_atom_osmo = gamm
+        .create_basic_pool(&[coin(6_000_000, "uatom"), coin(1_500_000, "uosmo")], signer)
+        .unwrap()
+        .data
+        .pool_id;
+    (pool_mars_osmo, pool_atom_osmo)
+}
+
+fn new_route(pool_id: u64, denom_in: &str, denom_out: &str) -> NewRoute<OsmosisRoute> {
+    NewRoute {
+        denom_in: denom_in.to_string(),
+        denom_out: denom_out.to_string(),
+        route: OsmosisRoute(vec![SwapAmountInRoute {
+            pool_id,
+            token_out_denom: denom_out.to_string(),
+        }]),
+        twap_window: None,
+    }
+}
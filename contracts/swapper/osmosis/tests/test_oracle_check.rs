@@ -0,0 +1,322 @@
+use cosmwasm_std::{coin, Decimal};
+use mars_mock_oracle::msg::CoinPrice;
+use mars_owner::OwnerError;
+use mars_rover::adapters::{
+    oracle::OracleUnchecked,
+    swap::{
+        ConfigResponse, ConfigUpdate, EstimateExactInSwapResponse, ExecuteMsg,
+        OracleCheckUnchecked, QueryMsg,
+    },
+};
+use mars_swapper_osmosis::route::OsmosisRoute;
+use osmosis_std::types::osmosis::gamm::v1beta1::SwapAmountInRoute;
+use osmosis_test_tube::{Account, Bank, Gamm, Module, OsmosisTestApp, SigningAccount, Wasm};
+
+use crate::helpers::{
+    assert_err, instantiate_contract, instantiate_contract_with_oracle_check,
+    instantiate_mock_oracle, query_balance, swap_to_create_twap_records,
+};
+
+pub mod helpers;
+
+#[test]
+fn oracle_check_not_set_by_default() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+    let owner = app.init_account(&[coin(1_000_000_000_000, "uosmo")]).unwrap();
+
+    let contract_addr = instantiate_contract(&wasm, &owner);
+
+    let res: ConfigResponse = wasm.query(&contract_addr, &QueryMsg::Config {}).unwrap();
+    assert_eq!(res.oracle_check, None);
+}
+
+#[test]
+fn only_owner_can_update_config() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+
+    let accs = app.init_accounts(&[coin(1_000_000_000_000, "uosmo")], 2).unwrap();
+    let owner = &accs[0];
+    let bad_guy = &accs[1];
+
+    let contract_addr = instantiate_contract(&wasm, owner);
+    let oracle_addr = instantiate_mock_oracle(&wasm, owner, vec![]);
+
+    let res_err = wasm
+        .execute(
+            &contract_addr,
+            &ExecuteMsg::<OsmosisRoute>::UpdateConfig {
+                oracle_check: Some(ConfigUpdate::Set(OracleCheckUnchecked {
+                    oracle: OracleUnchecked::new(oracle_addr),
+                    tolerance: Decimal::percent(5),
+                })),
+                fallback_max_hops: None,
+            },
+            &[],
+            bad_guy,
+        )
+        .unwrap_err();
+
+    assert_err(res_err, OwnerError::NotOwner {});
+}
+
+#[test]
+fn owner_can_set_and_remove_oracle_check() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+    let owner = app.init_account(&[coin(1_000_000_000_000, "uosmo")]).unwrap();
+
+    let contract_addr = instantiate_contract(&wasm, &owner);
+    let oracle_addr = instantiate_mock_oracle(&wasm, &owner, vec![]);
+
+    let oracle_check = OracleCheckUnchecked {
+        oracle: OracleUnchecked::new(oracle_addr),
+        tolerance: Decimal::percent(5),
+    };
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::<OsmosisRoute>::UpdateConfig {
+            oracle_check: Some(ConfigUpdate::Set(oracle_check.clone())),
+            fallback_max_hops: None,
+        },
+        &[],
+        &owner,
+    )
+    .unwrap();
+
+    let res: ConfigResponse = wasm.query(&contract_addr, &QueryMsg::Config {}).unwrap();
+    assert_eq!(res.oracle_check, Some(oracle_check.clone()));
+
+    // Fields not set are left unchanged
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::<OsmosisRoute>::UpdateConfig {
+            oracle_check: None,
+            fallback_max_hops: Some(ConfigUpdate::Set(2)),
+        },
+        &[],
+        &owner,
+    )
+    .unwrap();
+
+    let res: ConfigResponse = wasm.query(&contract_addr, &QueryMsg::Config {}).unwrap();
+    assert_eq!(res.oracle_check, Some(oracle_check));
+    assert_eq!(res.fallback_max_hops, Some(2));
+
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::<OsmosisRoute>::UpdateConfig {
+            oracle_check: Some(ConfigUpdate::Clear),
+            fallback_max_hops: None,
+        },
+        &[],
+        &owner,
+    )
+    .unwrap();
+
+    let res: ConfigResponse = wasm.query(&contract_addr, &QueryMsg::Config {}).unwrap();
+    assert_eq!(res.oracle_check, None);
+    assert_eq!(res.fallback_max_hops, Some(2));
+}
+
+#[test]
+fn oracle_tolerance_must_be_below_one() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+    let owner = app.init_account(&[coin(1_000_000_000_000, "uosmo")]).unwrap();
+
+    let contract_addr = instantiate_contract(&wasm, &owner);
+    let oracle_addr = instantiate_mock_oracle(&wasm, &owner, vec![]);
+
+    let res_err = wasm
+        .execute(
+            &contract_addr,
+            &ExecuteMsg::<OsmosisRoute>::UpdateConfig {
+                oracle_check: Some(ConfigUpdate::Set(OracleCheckUnchecked {
+                    oracle: OracleUnchecked::new(oracle_addr),
+                    tolerance: Decimal::one(),
+                })),
+                fallback_max_hops: None,
+            },
+            &[],
+            &owner,
+        )
+        .unwrap_err();
+
+    assert_err(res_err, "Oracle tolerance must be less than 1, got 1");
+}
+
+#[test]
+fn swap_fails_if_route_deviates_from_oracle() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+
+    let signer = app
+        .init_account(&[coin(1_000_000_000_000, "uosmo"), coin(1_000_000_000_000, "umars")])
+        .unwrap();
+    let user = app.init_account(&[coin(10_000, "umars")]).unwrap();
+
+    // Pool prices umars at 0.25 uosmo while the oracle prices it at 0.5 uosmo
+    let contract_addr = setup_swapper_with_oracle(&app, &wasm, &signer, Decimal::percent(50));
+
+    let res_err = wasm
+        .execute(
+            &contract_addr,
+            &ExecuteMsg::<OsmosisRoute>::SwapExactIn {
+                coin_in: coin(10_000, "umars"),
+                denom_out: "uosmo".to_string(),
+                slippage: Decimal::percent(6),
+            },
+            &[coin(10_000, "umars")],
+            &user,
+        )
+        .unwrap_err();
+
+    assert_err(res_err, "deviates from oracle estimate of 5000 by more than 0.05");
+}
+
+#[test]
+fn swap_exact_out_fails_if_route_deviates_from_oracle() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+
+    let signer = app
+        .init_account(&[coin(1_000_000_000_000, "uosmo"), coin(1_000_000_000_000, "umars")])
+        .unwrap();
+    let user = app.init_account(&[coin(10_000, "umars")]).unwrap();
+
+    // Pool prices umars at 0.25 uosmo while the oracle prices it at 0.5 uosmo, so the route needs
+    // about 4000 umars for what the oracle values at 2000 umars
+    let contract_addr = setup_swapper_with_oracle(&app, &wasm, &signer, Decimal::percent(50));
+
+    let res_err = wasm
+        .execute(
+            &contract_addr,
+            &ExecuteMsg::<OsmosisRoute>::SwapExactOut {
+                coin_out: coin(1_000, "uosmo"),
+                denom_in: "umars".to_string(),
+                max_in: 10_000u128.into(),
+            },
+            &[coin(10_000, "umars")],
+            &user,
+        )
+        .unwrap_err();
+
+    assert_err(res_err, "deviates from oracle estimate of 2000 by more than 0.05");
+}
+
+#[test]
+fn estimate_fails_if_route_deviates_from_oracle() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+
+    let signer = app
+        .init_account(&[coin(1_000_000_000_000, "uosmo"), coin(1_000_000_000_000, "umars")])
+        .unwrap();
+
+    // Pool prices umars at 0.25 uosmo while the oracle prices it at 0.5 uosmo
+    let contract_addr = setup_swapper_with_oracle(&app, &wasm, &signer, Decimal::percent(50));
+
+    let res_err = wasm
+        .query::<_, EstimateExactInSwapResponse>(
+            &contract_addr,
+            &QueryMsg::EstimateExactInSwap {
+                coin_in: coin(10_000, "umars"),
+                denom_out: "uosmo".to_string(),
+            },
+        )
+        .unwrap_err();
+
+    assert_err(res_err, "deviates from oracle estimate of 5000 by more than 0.05");
+}
+
+#[test]
+fn swap_succeeds_within_oracle_tolerance() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+
+    let signer = app
+        .init_account(&[coin(1_000_000_000_000, "uosmo"), coin(1_000_000_000_000, "umars")])
+        .unwrap();
+    let user = app.init_account(&[coin(10_000, "umars")]).unwrap();
+
+    let contract_addr = setup_swapper_with_oracle(&app, &wasm, &signer, Decimal::percent(25));
+
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::<OsmosisRoute>::SwapExactIn {
+            coin_in: coin(10_000, "umars"),
+            denom_out: "uosmo".to_string(),
+            slippage: Decimal::percent(6),
+        },
+        &[coin(10_000, "umars")],
+        &user,
+    )
+    .unwrap();
+
+    let bank = Bank::new(&app);
+    let osmo_balance = query_balance(&bank, &user.address(), "uosmo");
+    let mars_balance = query_balance(&bank, &user.address(), "umars");
+    assert_eq!(osmo_balance, 2470);
+    assert_eq!(mars_balance, 0);
+}
+
+/// Deploys a swapper with a 5% oracle tolerance and a umars/uosmo route through a pool pricing
+/// umars at 0.25 uosmo
+fn setup_swapper_with_oracle(
+    app: &OsmosisTestApp,
+    wasm: &Wasm<OsmosisTestApp>,
+    signer: &SigningAccount,
+    oracle_mars_price: Decimal,
+) -> String {
+    let oracle_addr = instantiate_mock_oracle(
+        wasm,
+        signer,
+        vec![
+            CoinPrice {
+                denom: "umars".to_string(),
+                price: oracle_mars_price,
+            },
+            CoinPrice {
+                denom: "uosmo".to_string(),
+                price: Decimal::one(),
+            },
+        ],
+    );
+    let contract_addr = instantiate_contract_with_oracle_check(
+        wasm,
+        signer,
+        Some(OracleCheckUnchecked {
+            oracle: OracleUnchecked::new(oracle_addr),
+            tolerance: Decimal::percent(5),
+        }),
+    );
+
+    let gamm = Gamm::new(app);
+    let pool_mars_osmo = gamm
+        .create_basic_pool(&[coin(6_000_000, "umars"), coin(1_500_000, "uosmo")], signer)
+        .unwrap()
+        .data
+        .pool_id;
+
+    swap_to_create_twap_records(app, signer, pool_mars_osmo, coin(10u128, "umars"), "uosmo");
+
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::SetRoute {
+            denom_in: "umars".to_string(),
+            denom_out: "uosmo".to_string(),
+            route: OsmosisRoute(vec![SwapAmountInRoute {
+                pool_id: pool_mars_osmo,
+                token_out_denom: "uosmo".to_string(),
+            }]),
+            twap_window: None,
+        },
+        &[],
+        signer,
+    )
+    .unwrap();
+
+    contract_addr
+}
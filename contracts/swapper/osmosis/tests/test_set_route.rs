@@ -37,6 +37,7 @@ fn only_owner_can_set_routes() {
                         token_out_denom: "weth".to_string(),
                     },
                 ]),
+                twap_window: None,
             },
             &[],
             bad_guy,
@@ -62,6 +63,7 @@ fn must_pass_at_least_one_step() {
                 denom_in: "mars".to_string(),
                 denom_out: "weth".to_string(),
                 route: OsmosisRoute(vec![]),
+                twap_window: None,
             },
             &[],
             &signer,
@@ -95,6 +97,7 @@ fn must_be_available_in_osmosis() {
                     pool_id: 1,
                     token_out_denom: "osmo".to_string(),
                 }]),
+                twap_window: None,
             },
             &[],
             &signer,
@@ -137,6 +140,7 @@ fn step_does_not_contain_input_denom() {
                     pool_id: pool_atom_osmo,
                     token_out_denom: "uosmo".to_string(),
                 }]),
+                twap_window: None,
             },
             &[],
             &signer,
@@ -179,6 +183,7 @@ fn step_does_not_contain_output_denom() {
                     pool_id: pool_mars_osmo,
                     token_out_denom: "uweth".to_string(),
                 }]),
+                twap_window: None,
             },
             &[],
             &signer,
@@ -250,6 +255,7 @@ fn steps_do_not_loop() {
                         token_out_denom: "umars".to_string(),
                     },
                 ]),
+                twap_window: None,
             },
             &[],
             &signer,
@@ -294,6 +300,7 @@ fn step_output_does_not_match() {
                     pool_id: pool_atom_osmo,
                     token_out_denom: "uosmo".to_string(),
                 }]),
+                twap_window: None,
             },
             &[],
             &signer,
@@ -351,6 +358,7 @@ fn set_route_success() {
                     token_out_denom: "uweth".to_string(),
                 },
             ]),
+            twap_window: None,
         },
         &[],
         &signer,
@@ -370,4 +378,144 @@ fn set_route_success() {
     assert_eq!(res.denom_in, "umars".to_string());
     assert_eq!(res.denom_out, "uweth".to_string());
     assert_eq!(res.route.to_string(), format!("{pool_mars_osmo}:uosmo|{pool_weth_osmo}:uweth"));
+    assert_eq!(res.twap_window, None);
+}
+
+#[test]
+fn set_route_with_twap_window() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+
+    let signer = app
+        .init_account(&[coin(1_000_000_000_000, "uosmo"), coin(1_000_000_000_000, "umars")])
+        .unwrap();
+
+    let contract_addr = instantiate_contract(&wasm, &signer);
+
+    let gamm = Gamm::new(&app);
+    let pool_mars_osmo = gamm
+        .create_basic_pool(&[coin(6_000_000, "umars"), coin(1_500_000, "uosmo")], &signer)
+        .unwrap()
+        .data
+        .pool_id;
+
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::SetRoute {
+            denom_in: "umars".to_string(),
+            denom_out: "uosmo".to_string(),
+            route: OsmosisRoute(vec![SwapAmountInRoute {
+                pool_id: pool_mars_osmo,
+                token_out_denom: "uosmo".to_string(),
+            }]),
+            twap_window: Some(3600),
+        },
+        &[],
+        &signer,
+    )
+    .unwrap();
+
+    let res: RouteResponse<OsmosisRoute> = wasm
+        .query(
+            &contract_addr,
+            &QueryMsg::Route {
+                denom_in: "umars".to_string(),
+                denom_out: "uosmo".to_string(),
+            },
+        )
+        .unwrap();
+
+    assert_eq!(res.twap_window, Some(3600));
+}
+
+#[test]
+fn resetting_route_clears_twap_window() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+
+    let signer = app
+        .init_account(&[coin(1_000_000_000_000, "uosmo"), coin(1_000_000_000_000, "umars")])
+        .unwrap();
+
+    let contract_addr = instantiate_contract(&wasm, &signer);
+
+    let gamm = Gamm::new(&app);
+    let pool_mars_osmo = gamm
+        .create_basic_pool(&[coin(6_000_000, "umars"), coin(1_500_000, "uosmo")], &signer)
+        .unwrap()
+        .data
+        .pool_id;
+
+    for twap_window in [Some(3600), None] {
+        wasm.execute(
+            &contract_addr,
+            &ExecuteMsg::SetRoute {
+                denom_in: "umars".to_string(),
+                denom_out: "uosmo".to_string(),
+                route: OsmosisRoute(vec![SwapAmountInRoute {
+                    pool_id: pool_mars_osmo,
+                    token_out_denom: "uosmo".to_string(),
+                }]),
+                twap_window,
+            },
+            &[],
+            &signer,
+        )
+        .unwrap();
+    }
+
+    let res: RouteResponse<OsmosisRoute> = wasm
+        .query(
+            &contract_addr,
+            &QueryMsg::Route {
+                denom_in: "umars".to_string(),
+                denom_out: "uosmo".to_string(),
+            },
+        )
+        .unwrap();
+
+    assert_eq!(res.twap_window, None);
+}
+
+#[test]
+fn zero_twap_window_rejected() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+
+    let signer = app
+        .init_account(&[coin(1_000_000_000_000, "uosmo"), coin(1_000_000_000_000, "umars")])
+        .unwrap();
+
+    let contract_addr = instantiate_contract(&wasm, &signer);
+
+    let gamm = Gamm::new(&app);
+    let pool_mars_osmo = gamm
+        .create_basic_pool(&[coin(6_000_000, "umars"), coin(1_500_000, "uosmo")], &signer)
+        .unwrap()
+        .data
+        .pool_id;
+
+    let res_err = wasm
+        .execute(
+            &contract_addr,
+            &ExecuteMsg::SetRoute {
+                denom_in: "umars".to_string(),
+                denom_out: "uosmo".to_string(),
+                route: OsmosisRoute(vec![SwapAmountInRoute {
+                    pool_id: pool_mars_osmo,
+                    token_out_denom: "uosmo".to_string(),
+                }]),
+                twap_window: Some(0),
+            },
+            &[],
+            &signer,
+        )
+        .unwrap_err();
+
+    assert_err(
+        res_err,
+        ContractError::InvalidRoute {
+            reason: "TWAP window must be greater than zero".to_string(),
+        },
+    );
 }
@@ -0,0 +1,250 @@
+use cosmwasm_std::{coin, Decimal, Uint128};
+use mars_rover::adapters::swap::{
+    EstimateExactInSwapResponse, ExecuteMsg, QueryMsg, RouteResponse,
+};
+use mars_swapper_base::{ContractError, SplitRoute, WeightedPath};
+use mars_swapper_osmosis::route::{OsmosisRoute, OsmosisSwapRoute};
+use osmosis_std::types::osmosis::gamm::v1beta1::SwapAmountInRoute;
+use osmosis_test_tube::{Account, Bank, Gamm, Module, OsmosisTestApp, SigningAccount, Wasm};
+
+use crate::helpers::{
+    assert_err, init_signer, instantiate_contract, query_balance, query_price_from_pool,
+    swap_to_create_twap_records,
+};
+
+pub mod helpers;
+
+#[test]
+fn split_route_weights_must_sum_to_one() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+    let signer = init_signer(&app);
+
+    let contract_addr = instantiate_contract(&wasm, &signer);
+    let (pool_1, pool_2) = create_mars_osmo_pools(&app, &signer);
+
+    let res_err = wasm
+        .execute(
+            &contract_addr,
+            &ExecuteMsg::SetRoute {
+                denom_in: "umars".to_string(),
+                denom_out: "uosmo".to_string(),
+                route: split_route(pool_1, Decimal::percent(50), pool_2, Decimal::percent(40)),
+                twap_window: None,
+            },
+            &[],
+            &signer,
+        )
+        .unwrap_err();
+
+    assert_err(
+        res_err,
+        ContractError::InvalidRoute {
+            reason: "path weights must sum to one, got 0.9".to_string(),
+        },
+    );
+}
+
+#[test]
+fn set_and_query_split_route() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+    let signer = init_signer(&app);
+
+    let contract_addr = instantiate_contract(&wasm, &signer);
+    let (pool_1, pool_2) = create_mars_osmo_pools(&app, &signer);
+
+    let route = split_route(pool_1, Decimal::percent(60), pool_2, Decimal::percent(40));
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::SetRoute {
+            denom_in: "umars".to_string(),
+            denom_out: "uosmo".to_string(),
+            route: route.clone(),
+            twap_window: None,
+        },
+        &[],
+        &signer,
+    )
+    .unwrap();
+
+    let res: RouteResponse<OsmosisSwapRoute> = wasm
+        .query(
+            &contract_addr,
+            &QueryMsg::Route {
+                denom_in: "umars".to_string(),
+                denom_out: "uosmo".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(res.route, route);
+    assert_eq!(res.route.to_string(), format!("0.6*({pool_1}:uosmo),0.4*({pool_2}:uosmo)"));
+}
+
+#[test]
+fn estimate_sums_split_paths() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+    let signer = init_signer(&app);
+
+    let contract_addr = instantiate_contract(&wasm, &signer);
+    let (pool_1, pool_2) = create_mars_osmo_pools(&app, &signer);
+
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::SetRoute {
+            denom_in: "umars".to_string(),
+            denom_out: "uosmo".to_string(),
+            route: split_route(pool_1, Decimal::percent(60), pool_2, Decimal::percent(40)),
+            twap_window: None,
+        },
+        &[],
+        &signer,
+    )
+    .unwrap();
+
+    let gamm = Gamm::new(&app);
+    let expected_output = Uint128::new(6_000) * query_price_from_pool(&gamm, pool_1, "umars")
+        + Uint128::new(4_000) * query_price_from_pool(&gamm, pool_2, "umars");
+
+    let res: EstimateExactInSwapResponse = wasm
+        .query(
+            &contract_addr,
+            &QueryMsg::EstimateExactInSwap {
+                coin_in: coin(10_000, "umars"),
+                denom_out: "uosmo".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(res.amount, expected_output);
+}
+
+#[test]
+fn swap_exact_in_through_split_route() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+    let signer = init_signer(&app);
+    let user = app.init_account(&[coin(10_000, "umars")]).unwrap();
+
+    let contract_addr = instantiate_contract(&wasm, &signer);
+    let (pool_1, pool_2) = create_mars_osmo_pools(&app, &signer);
+
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::SetRoute {
+            denom_in: "umars".to_string(),
+            denom_out: "uosmo".to_string(),
+            route: split_route(pool_1, Decimal::percent(50), pool_2, Decimal::percent(50)),
+            twap_window: None,
+        },
+        &[],
+        &signer,
+    )
+    .unwrap();
+
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::<OsmosisSwapRoute>::SwapExactIn {
+            coin_in: coin(10_000, "umars"),
+            denom_out: "uosmo".to_string(),
+            slippage: Decimal::percent(6),
+        },
+        &[coin(10_000, "umars")],
+        &user,
+    )
+    .unwrap();
+
+    // Each half takes less price impact than the full amount through a single pool (2470 uosmo)
+    let bank = Bank::new(&app);
+    let osmo_balance = query_balance(&bank, &user.address(), "uosmo");
+    let mars_balance = query_balance(&bank, &user.address(), "umars");
+    assert!(osmo_balance > 2470);
+    assert_eq!(mars_balance, 0);
+
+    // Assert no tokens in contract left over
+    let osmo_balance = query_balance(&bank, &contract_addr, "uosmo");
+    let mars_balance = query_balance(&bank, &contract_addr, "umars");
+    assert_eq!(osmo_balance, 0);
+    assert_eq!(mars_balance, 0);
+}
+
+#[test]
+fn split_route_enforces_slippage_on_combined_output() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+    let signer = init_signer(&app);
+    let whale = app.init_account(&[coin(1_000_000, "umars")]).unwrap();
+
+    let contract_addr = instantiate_contract(&wasm, &signer);
+    let (pool_1, pool_2) = create_mars_osmo_pools(&app, &signer);
+
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::SetRoute {
+            denom_in: "umars".to_string(),
+            denom_out: "uosmo".to_string(),
+            route: split_route(pool_1, Decimal::percent(50), pool_2, Decimal::percent(50)),
+            twap_window: None,
+        },
+        &[],
+        &signer,
+    )
+    .unwrap();
+
+    // The price impact of both halves together (~230k uosmo) is more than 5% off the estimate
+    // (250k uosmo), so the combined minimum fails the swap
+    let res_err = wasm
+        .execute(
+            &contract_addr,
+            &ExecuteMsg::<OsmosisSwapRoute>::SwapExactIn {
+                coin_in: coin(1_000_000, "umars"),
+                denom_out: "uosmo".to_string(),
+                slippage: Decimal::percent(5),
+            },
+            &[coin(1_000_000, "umars")],
+            &whale,
+        )
+        .unwrap_err();
+
+    assert_err(res_err, "less than the minimum");
+}
+
+/// Creates two umars/uosmo pools pricing 1 umars at 0.25 uosmo, with TWAP records
+fn create_mars_osmo_pools(app: &OsmosisTestApp, signer: &SigningAccount) -> (u64, u64) {
+    let gamm = Gamm::new(app);
+    let pool_1 = gamm
+        .create_basic_pool(&[coin(6_000_000, "umars"), coin(1_500_000, "uosmo")], signer)
+        .unwrap()
+        .data
+        .pool_id;
+    let pool_2 = gamm
+        .create_basic_pool(&[coin(6_000_000, "umars"), coin(1_500_000, "uosmo")], signer)
+        .unwrap()
+        .data
+        .pool_id;
+
+    swap_to_create_twap_records(app, signer, pool_1, coin(10u128, "umars"), "uosmo");
+
+    (pool_1, pool_2)
+}
+
+fn split_route(pool_1: u64, weight_1: Decimal, pool_2: u64, weight_2: Decimal) -> OsmosisSwapRoute {
+    let path = |pool_id| {
+        OsmosisRoute(vec![SwapAmountInRoute {
+            pool_id,
+            token_out_denom: "uosmo".to_string(),
+        }])
+    };
+    OsmosisSwapRoute::Split(SplitRoute {
+        paths: vec![
+            WeightedPath {
+                weight: weight_1,
+                route: path(pool_1),
+            },
+            WeightedPath {
+                weight: weight_2,
+                route: path(pool_2),
+            },
+        ],
+    })
+}
@@ -1,4 +1,4 @@
-use cosmwasm_std::{coin, Addr, Decimal};
+use cosmwasm_std::{coin, Addr, Decimal, Uint128};
 use mars_rover::{adapters::swap::ExecuteMsg, error::ContractError as RoverError};
 use mars_swapper_base::ContractError;
 use mars_swapper_osmosis::route::OsmosisRoute;
@@ -29,6 +29,7 @@ fn transfer_callback_only_internal() {
                 recipient: Addr::unchecked(bad_guy.address()),
                 denom_in: "mars".to_string(),
                 denom_out: "osmo".to_string(),
+                min_receive: None,
             },
             &[],
             bad_guy,
@@ -76,6 +77,7 @@ fn swap_exact_in_slippage_too_high() {
             denom_in: "umars".to_string(),
             denom_out: "uosmo".to_string(),
             route,
+            twap_window: None,
         },
         &[],
         &signer,
@@ -132,6 +134,7 @@ fn swap_exact_in_success() {
                 pool_id: pool_mars_osmo,
                 token_out_denom: "uosmo".to_string(),
             }]),
+            twap_window: None,
         },
         &[],
         &signer,
@@ -168,3 +171,67 @@ fn swap_exact_in_success() {
     assert_eq!(osmo_balance, 0);
     assert_eq!(mars_balance, 0);
 }
+
+#[test]
+fn swap_exact_out_success() {
+    let app = OsmosisTestApp::new();
+    let wasm = Wasm::new(&app);
+
+    let signer = app
+        .init_account(&[coin(1_000_000_000_000, "uosmo"), coin(1_000_000_000_000, "umars")])
+        .unwrap();
+    let user = app.init_account(&[coin(10_000, "umars")]).unwrap();
+
+    let contract_addr = instantiate_contract(&wasm, &signer);
+
+    let gamm = Gamm::new(&app);
+    let pool_mars_osmo = gamm
+        .create_basic_pool(&[coin(6_000_000, "umars"), coin(1_500_000, "uosmo")], &signer)
+        .unwrap()
+        .data
+        .pool_id;
+
+    swap_to_create_twap_records(&app, &signer, pool_mars_osmo, coin(10u128, "umars"), "uosmo");
+
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::SetRoute {
+            denom_in: "umars".to_string(),
+            denom_out: "uosmo".to_string(),
+            route: OsmosisRoute(vec![SwapAmountInRoute {
+                pool_id: pool_mars_osmo,
+                token_out_denom: "uosmo".to_string(),
+            }]),
+            twap_window: None,
+        },
+        &[],
+        &signer,
+    )
+    .unwrap();
+
+    wasm.execute(
+        &contract_addr,
+        &ExecuteMsg::<OsmosisRoute>::SwapExactOut {
+            coin_out: coin(2_000, "uosmo"),
+            denom_in: "umars".to_string(),
+            max_in: Uint128::new(10_000),
+        },
+        &[coin(10_000, "umars")],
+        &user,
+    )
+    .unwrap();
+
+    // Assert user receives the exact amount out, with unspent input refunded.
+    // At a price of 4 umars per uosmo, at least 8_000 umars are spent.
+    let bank = Bank::new(&app);
+    let osmo_balance = query_balance(&bank, &user.address(), "uosmo");
+    let mars_balance = query_balance(&bank, &user.address(), "umars");
+    assert_eq!(osmo_balance, 2_000);
+    assert!(mars_balance > 0 && mars_balance <= 2_000);
+
+    // Assert no tokens in contract left over
+    let osmo_balance = query_balance(&bank, &contract_addr, "uosmo");
+    let mars_balance = query_balance(&bank, &contract_addr, "umars");
+    assert_eq!(osmo_balance, 0);
+    assert_eq!(mars_balance, 0);
+}
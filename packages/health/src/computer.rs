@@ -0,0 +1,250 @@
+use std::collections::{HashMap, HashSet};
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Coin, Decimal, Uint128};
+
+use crate::{Health, HealthError, HealthResult};
+
+/// Risk parameters of a denom, as configured in its red bank market
+#[cw_serde]
+pub struct AssetParams {
+    pub max_ltv: Decimal,
+    pub liquidation_threshold: Decimal,
+}
+
+/// Risk parameters of a vault, as configured in the credit manager
+#[cw_serde]
+pub struct VaultParams {
+    pub base_token: String,
+    pub max_ltv: Decimal,
+    pub liquidation_threshold: Decimal,
+    pub whitelisted: bool,
+}
+
+/// A vault position denominated in the vault's base token
+#[cw_serde]
+pub struct VaultCollateral {
+    pub vault_address: String,
+    /// Locked & unlocked vault coins, redeemed into the base token
+    pub base_amount: Uint128,
+    /// Amounts of each unlocking position, denominated in the base token
+    pub unlocking: Vec<Uint128>,
+}
+
+#[cw_serde]
+pub struct CoinValue {
+    pub denom: String,
+    pub amount: Uint128,
+    pub price: Decimal,
+    pub value: Uint128,
+}
+
+#[cw_serde]
+pub struct CollateralValue {
+    pub coin: CoinValue,
+    /// Zero if the asset has been de-listed
+    pub max_ltv: Decimal,
+    pub liquidation_threshold: Decimal,
+    pub max_ltv_adjusted_value: Uint128,
+    pub liquidation_threshold_adjusted_value: Uint128,
+}
+
+#[cw_serde]
+pub struct VaultCollateralValue {
+    pub vault_address: String,
+    /// Locked & unlocked vault coins, denominated in the base token
+    pub vault_coins: CollateralValue,
+    /// Unlocking positions, denominated in the base token
+    pub unlocking: Vec<CollateralValue>,
+}
+
+/// Computes the health of a credit account without access to chain state. All prices and risk
+/// parameters for the denoms & vaults in the positions must be provided. Assets missing from
+/// `allowed_coins` are treated as de-listed, contributing nothing towards max LTV.
+#[cw_serde]
+pub struct HealthComputer {
+    pub deposits: Vec<Coin>,
+    pub debts: Vec<Coin>,
+    pub vaults: Vec<VaultCollateral>,
+    pub prices: HashMap<String, Decimal>,
+    pub asset_params: HashMap<String, AssetParams>,
+    pub vault_params: HashMap<String, VaultParams>,
+    pub allowed_coins: HashSet<String>,
+}
+
+impl HealthComputer {
+    pub fn compute_health(&self) -> HealthResult<Health> {
+        let mut total_collateral_value = Uint128::zero();
+        let mut max_ltv_adjusted_collateral = Uint128::zero();
+        let mut liquidation_threshold_adjusted_collateral = Uint128::zero();
+
+        let vault_values = self.vault_values()?;
+        let vault_collaterals =
+            vault_values.iter().flat_map(|v| std::iter::once(&v.vault_coins).chain(&v.unlocking));
+        for c in self.deposit_values()?.iter().chain(vault_collaterals) {
+            total_collateral_value = total_collateral_value.checked_add(c.coin.value)?;
+            max_ltv_adjusted_collateral =
+                max_ltv_adjusted_collateral.checked_add(c.max_ltv_adjusted_value)?;
+            liquidation_threshold_adjusted_collateral =
+                liquidation_threshold_adjusted_collateral
+                    .checked_add(c.liquidation_threshold_adjusted_value)?;
+        }
+
+        let mut total_debt_value = Uint128::zero();
+        for d in self.debt_values()? {
+            total_debt_value = total_debt_value.checked_add(d.value)?;
+        }
+
+        let max_ltv_health_factor = if total_debt_value.is_zero() {
+            None
+        } else {
+            Some(Decimal::checked_from_ratio(max_ltv_adjusted_collateral, total_debt_value)?)
+        };
+
+        let liquidation_health_factor = if total_debt_value.is_zero() {
+            None
+        } else {
+            Some(Decimal::checked_from_ratio(
+                liquidation_threshold_adjusted_collateral,
+                total_debt_value,
+            )?)
+        };
+
+        Ok(Health {
+            total_debt_value,
+            total_collateral_value,
+            max_ltv_adjusted_collateral,
+            liquidation_threshold_adjusted_collateral,
+            max_ltv_health_factor,
+            liquidation_health_factor,
+        })
+    }
+
+    pub fn deposit_values(&self) -> HealthResult<Vec<CollateralValue>> {
+        self.deposits
+            .iter()
+            .map(|c| {
+                let params = self.get_asset_params(&c.denom)?;
+
+                // If coin has been de-listed, drop MaxLTV to zero
+                let checked_max_ltv = if self.allowed_coins.contains(&c.denom) {
+                    params.max_ltv
+                } else {
+                    Decimal::zero()
+                };
+
+                self.collateral_value(c, checked_max_ltv, params.liquidation_threshold)
+            })
+            .collect()
+    }
+
+    pub fn debt_values(&self) -> HealthResult<Vec<CoinValue>> {
+        self.debts.iter().map(|c| self.coin_value(c)).collect()
+    }
+
+    pub fn vault_values(&self) -> HealthResult<Vec<VaultCollateralValue>> {
+        self.vaults
+            .iter()
+            .map(|v| {
+                let params = self
+                    .vault_params
+                    .get(&v.vault_address)
+                    .ok_or_else(|| HealthError::MissingVaultParams(v.vault_address.clone()))?;
+                let base_allowed = self.allowed_coins.contains(&params.base_token);
+
+                // If vault or base token has been de-listed, drop MaxLTV to zero
+                let checked_vault_max_ltv = if params.whitelisted && base_allowed {
+                    params.max_ltv
+                } else {
+                    Decimal::zero()
+                };
+
+                let vault_coins = self.collateral_value(
+                    &Coin {
+                        denom: params.base_token.clone(),
+                        amount: v.base_amount,
+                    },
+                    checked_vault_max_ltv,
+                    params.liquidation_threshold,
+                )?;
+
+                // Unlocking positions are weighed by the base token's own params
+                let base_params = self.get_asset_params(&params.base_token)?;
+
+                // If base token has been de-listed, drop MaxLTV to zero
+                let checked_base_max_ltv = if base_allowed {
+                    base_params.max_ltv
+                } else {
+                    Decimal::zero()
+                };
+
+                let unlocking = v
+                    .unlocking
+                    .iter()
+                    .map(|amount| {
+                        self.collateral_value(
+                            &Coin {
+                                denom: params.base_token.clone(),
+                                amount: *amount,
+                            },
+                            checked_base_max_ltv,
+                            base_params.liquidation_threshold,
+                        )
+                    })
+                    .collect::<HealthResult<Vec<_>>>()?;
+
+                Ok(VaultCollateralValue {
+                    vault_address: v.vault_address.clone(),
+                    vault_coins,
+                    unlocking,
+                })
+            })
+            .collect()
+    }
+
+    fn get_asset_params(&self, denom: &str) -> HealthResult<&AssetParams> {
+        self.asset_params
+            .get(denom)
+            .ok_or_else(|| HealthError::MissingAssetParams(denom.to_string()))
+    }
+
+    fn coin_value(&self, coin: &Coin) -> HealthResult<CoinValue> {
+        let price = *self
+            .prices
+            .get(&coin.denom)
+            .ok_or_else(|| HealthError::MissingPrice(coin.denom.clone()))?;
+        Ok(CoinValue {
+            denom: coin.denom.clone(),
+            amount: coin.amount,
+            price,
+            value: coin
+                .amount
+                .checked_mul_floor(price)
+                .map_err(|_| HealthError::CheckedMultiplyFraction)?,
+        })
+    }
+
+    fn collateral_value(
+        &self,
+        coin: &Coin,
+        max_ltv: Decimal,
+        liquidation_threshold: Decimal,
+    ) -> HealthResult<CollateralValue> {
+        let coin = self.coin_value(coin)?;
+        let max_ltv_adjusted_value = coin
+            .value
+            .checked_mul_floor(max_ltv)
+            .map_err(|_| HealthError::CheckedMultiplyFraction)?;
+        let liquidation_threshold_adjusted_value = coin
+            .value
+            .checked_mul_floor(liquidation_threshold)
+            .map_err(|_| HealthError::CheckedMultiplyFraction)?;
+        Ok(CollateralValue {
+            coin,
+            max_ltv,
+            liquidation_threshold,
+            max_ltv_adjusted_value,
+            liquidation_threshold_adjusted_value,
+        })
+    }
+}
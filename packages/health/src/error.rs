@@ -0,0 +1,25 @@
+use cosmwasm_std::{CheckedFromRatioError, OverflowError};
+use thiserror::Error;
+
+pub type HealthResult<T> = Result<T, HealthError>;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum HealthError {
+    #[error("{0}")]
+    CheckedFromRatio(#[from] CheckedFromRatioError),
+
+    #[error("CheckedMultiplyFractionError")]
+    CheckedMultiplyFraction,
+
+    #[error("{0}")]
+    Overflow(#[from] OverflowError),
+
+    #[error("Missing price for {0}")]
+    MissingPrice(String),
+
+    #[error("Missing asset params for {0}")]
+    MissingAssetParams(String),
+
+    #[error("Missing params for vault {0}")]
+    MissingVaultParams(String),
+}
@@ -1,3 +1,5 @@
+mod computer;
+mod error;
 mod health;
 
-pub use self::health::*;
+pub use self::{computer::*, error::*, health::*};
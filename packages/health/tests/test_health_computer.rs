@@ -0,0 +1,126 @@
+use std::collections::{HashMap, HashSet};
+
+use cosmwasm_std::{coin, Decimal, Uint128};
+use mars_health::{AssetParams, HealthComputer, HealthError, VaultCollateral, VaultParams};
+
+fn osmo_params() -> AssetParams {
+    AssetParams {
+        max_ltv: Decimal::percent(70),
+        liquidation_threshold: Decimal::percent(78),
+    }
+}
+
+fn atom_params() -> AssetParams {
+    AssetParams {
+        max_ltv: Decimal::percent(82),
+        liquidation_threshold: Decimal::percent(90),
+    }
+}
+
+fn computer() -> HealthComputer {
+    HealthComputer {
+        deposits: vec![coin(300, "uosmo"), coin(50, "uatom")],
+        debts: vec![coin(51, "uatom")],
+        vaults: vec![],
+        prices: HashMap::from([
+            ("uosmo".to_string(), Decimal::percent(25)),
+            ("uatom".to_string(), Decimal::one()),
+        ]),
+        asset_params: HashMap::from([
+            ("uosmo".to_string(), osmo_params()),
+            ("uatom".to_string(), atom_params()),
+        ]),
+        vault_params: HashMap::new(),
+        allowed_coins: HashSet::from(["uosmo".to_string(), "uatom".to_string()]),
+    }
+}
+
+#[test]
+fn deposits_and_debts() {
+    let health = computer().compute_health().unwrap();
+    assert_eq!(health.total_collateral_value, Uint128::new(125));
+    assert_eq!(health.max_ltv_adjusted_collateral, Uint128::new(93));
+    assert_eq!(health.liquidation_threshold_adjusted_collateral, Uint128::new(103));
+    assert_eq!(health.total_debt_value, Uint128::new(51));
+    assert_eq!(health.max_ltv_health_factor, Some(Decimal::from_ratio(93u128, 51u128)));
+    assert_eq!(health.liquidation_health_factor, Some(Decimal::from_ratio(103u128, 51u128)));
+    assert!(!health.is_liquidatable());
+    assert!(!health.is_above_max_ltv());
+}
+
+#[test]
+fn no_debts_has_no_health_factors() {
+    let mut computer = computer();
+    computer.debts = vec![];
+
+    let health = computer.compute_health().unwrap();
+    assert_eq!(health.total_debt_value, Uint128::zero());
+    assert_eq!(health.max_ltv_health_factor, None);
+    assert_eq!(health.liquidation_health_factor, None);
+}
+
+#[test]
+fn delisted_coins_do_not_count_towards_max_ltv() {
+    let mut computer = computer();
+    computer.allowed_coins.remove("uosmo");
+
+    let health = computer.compute_health().unwrap();
+    assert_eq!(health.max_ltv_adjusted_collateral, Uint128::new(41));
+    assert_eq!(health.liquidation_threshold_adjusted_collateral, Uint128::new(103));
+}
+
+#[test]
+fn vaults_are_weighed_by_vault_and_base_token_params() {
+    let mut computer = computer();
+    computer.debts = vec![];
+    computer.deposits = vec![];
+    computer.prices.insert("ugamm22".to_string(), Decimal::from_atomics(9874u128, 3).unwrap());
+    computer.asset_params.insert(
+        "ugamm22".to_string(),
+        AssetParams {
+            max_ltv: Decimal::percent(63),
+            liquidation_threshold: Decimal::percent(68),
+        },
+    );
+    computer.allowed_coins.insert("ugamm22".to_string());
+    computer.vault_params.insert(
+        "vault".to_string(),
+        VaultParams {
+            base_token: "ugamm22".to_string(),
+            max_ltv: Decimal::percent(60),
+            liquidation_threshold: Decimal::percent(70),
+            whitelisted: true,
+        },
+    );
+    computer.vaults = vec![VaultCollateral {
+        vault_address: "vault".to_string(),
+        base_amount: Uint128::new(100),
+        unlocking: vec![Uint128::new(100)],
+    }];
+
+    let values = computer.vault_values().unwrap();
+    assert_eq!(values.len(), 1);
+    assert_eq!(values[0].vault_coins.coin.value, Uint128::new(987));
+    assert_eq!(values[0].vault_coins.max_ltv_adjusted_value, Uint128::new(592));
+    assert_eq!(values[0].vault_coins.liquidation_threshold_adjusted_value, Uint128::new(690));
+    assert_eq!(values[0].unlocking[0].max_ltv_adjusted_value, Uint128::new(621));
+    assert_eq!(values[0].unlocking[0].liquidation_threshold_adjusted_value, Uint128::new(671));
+
+    let health = computer.compute_health().unwrap();
+    assert_eq!(health.total_collateral_value, Uint128::new(1974));
+    assert_eq!(health.max_ltv_adjusted_collateral, Uint128::new(1213));
+
+    // De-listing the vault only affects the vault coins
+    computer.vault_params.get_mut("vault").unwrap().whitelisted = false;
+    let health = computer.compute_health().unwrap();
+    assert_eq!(health.max_ltv_adjusted_collateral, Uint128::new(621));
+}
+
+#[test]
+fn missing_price_errors() {
+    let mut computer = computer();
+    computer.prices.remove("uatom");
+
+    let err = computer.compute_health().unwrap_err();
+    assert_eq!(err, HealthError::MissingPrice("uatom".to_string()));
+}
@@ -1,11 +1,52 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, Coin, Decimal, Uint128};
+use cosmwasm_std::{Addr, Api, Coin, Decimal, StdResult, Uint128};
 use mars_owner::OwnerUpdate;
 
+use crate::adapters::oracle::OracleBase;
+
 #[cw_serde]
 pub struct InstantiateMsg {
     /// The contract's owner, who can update config
     pub owner: String,
+    /// Cross-check of route estimates against oracle prices, applied before every swap
+    pub oracle_check: Option<OracleCheckUnchecked>,
+}
+
+#[cw_serde]
+pub struct OracleCheckBase<T> {
+    /// Oracle used to price the input and output denoms of a swap
+    pub oracle: OracleBase<T>,
+    /// Max relative difference allowed between the route's estimate and the amount derived from
+    /// oracle prices. Must be less than 1.
+    pub tolerance: Decimal,
+}
+
+pub type OracleCheckUnchecked = OracleCheckBase<String>;
+pub type OracleCheck = OracleCheckBase<Addr>;
+
+impl From<OracleCheck> for OracleCheckUnchecked {
+    fn from(oracle_check: OracleCheck) -> Self {
+        Self {
+            oracle: oracle_check.oracle.into(),
+            tolerance: oracle_check.tolerance,
+        }
+    }
+}
+
+impl OracleCheckUnchecked {
+    pub fn check(&self, api: &dyn Api) -> StdResult<OracleCheck> {
+        Ok(OracleCheckBase {
+            oracle: self.oracle.check(api)?,
+            tolerance: self.tolerance,
+        })
+    }
+}
+
+/// Change to a config field that can be unset
+#[cw_serde]
+pub enum ConfigUpdate<T> {
+    Set(T),
+    Clear,
 }
 
 #[cw_serde]
@@ -15,11 +56,36 @@ pub enum ExecuteMsg<Route> {
     /// Configure the route for swapping an asset
     ///
     /// This is chain-specific, and can include parameters such as slippage tolerance and the routes
-    /// for multi-step swaps
+    /// for multi-step swaps.
     SetRoute {
         denom_in: String,
         denom_out: String,
         route: Route,
+        /// TWAP window in seconds used to price the route. The swapper's default is used if not set.
+        twap_window: Option<u64>,
+    },
+    /// Configure the routes for several pairs at once
+    SetRoutes {
+        routes: Vec<NewRoute<Route>>,
+    },
+    /// Remove the route for swapping an asset
+    DeleteRoute {
+        denom_in: String,
+        denom_out: String,
+    },
+    /// Update the swapper config. Fields that are not set are left unchanged. Owner only.
+    UpdateConfig {
+        /// Oracle check applied before swaps. Disabled once cleared.
+        oracle_check: Option<ConfigUpdate<OracleCheckUnchecked>>,
+        /// Max hops of the best route through candidate pools used for exact-in swaps of pairs
+        /// without an explicit route. Disabled once cleared.
+        fallback_max_hops: Option<ConfigUpdate<u8>>,
+    },
+    /// Add or remove pools from the registry searched for routes. The registry is capped at a
+    /// handful of pools, as the search may estimate every path through them. Owner only.
+    UpdateCandidatePools {
+        add: Vec<CandidatePool>,
+        remove: Vec<String>,
     },
     /// Perform a swapper with an exact-in amount. Requires slippage allowance %.
     SwapExactIn {
@@ -27,11 +93,21 @@ pub enum ExecuteMsg<Route> {
         denom_out: String,
         slippage: Decimal,
     },
+    /// Perform a swapper with an exact-out amount. Up to `max_in` of `denom_in` must be sent with
+    /// the message; whatever is not needed to receive `coin_out` is refunded.
+    /// Only explicitly set routes are used, the fallback route does not apply to exact-out swaps.
+    SwapExactOut {
+        coin_out: Coin,
+        denom_in: String,
+        max_in: Uint128,
+    },
     /// Send swapper results back to swapper. Also refunds extra if sent more than needed. Internal use only.
     TransferResult {
         recipient: Addr,
         denom_in: String,
         denom_out: String,
+        /// Fails if less than this amount of `denom_out` was received across all swap messages
+        min_receive: Option<Uint128>,
     },
 }
 
@@ -41,6 +117,9 @@ pub enum QueryMsg {
     /// Query contract owner config
     #[returns(mars_owner::OwnerResponse)]
     Owner {},
+    /// Query the oracle check applied before swaps
+    #[returns(ConfigResponse)]
+    Config {},
     /// Get route for swapping an input denom into an output denom
     #[returns(RouteResponse<cosmwasm_std::Empty>)]
     Route {
@@ -53,13 +132,66 @@ pub enum QueryMsg {
         start_after: Option<(String, String)>,
         limit: Option<u32>,
     },
+    /// Re-validate stored routes against current pool state, reporting why each failing route no
+    /// longer works
+    #[returns(ValidateRoutesResponse)]
+    ValidateRoutes {
+        start_after: Option<(String, String)>,
+        limit: Option<u32>,
+    },
+    /// Enumerate pools in the registry searched for routes
+    #[returns(CandidatePoolsResponse)]
+    CandidatePools {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Search the candidate pools for the route with the best estimated output, using up to
+    /// `max_hops` pools
+    #[returns(BestRouteResponse<cosmwasm_std::Empty>)]
+    BestRoute {
+        coin_in: Coin,
+        denom_out: String,
+        max_hops: u8,
+    },
     /// Return current spot price swapping In for Out
+    /// Fails if the swap would fail the oracle check.
     /// Warning: Do not use this as an oracle price feed. Use Mars-Oracle for pricing.
     #[returns(EstimateExactInSwapResponse)]
     EstimateExactInSwap {
         coin_in: Coin,
         denom_out: String,
     },
+    /// Return the current spot amount of denom_in needed to receive coin_out, rounded up.
+    /// Only explicitly set routes are used, as for `SwapExactOut`. Fails if the swap would fail the
+    /// oracle check.
+    /// Warning: Do not use this as an oracle price feed. Use Mars-Oracle for pricing.
+    #[returns(EstimateExactOutSwapResponse)]
+    EstimateExactOutSwap {
+        coin_out: Coin,
+        denom_in: String,
+    },
+}
+
+#[cw_serde]
+pub struct NewRoute<Route> {
+    pub denom_in: String,
+    pub denom_out: String,
+    pub route: Route,
+    pub twap_window: Option<u64>,
+}
+
+#[cw_serde]
+pub struct CandidatePool {
+    /// Chain-specific pool identifier, e.g. an Osmosis pool id
+    pub id: String,
+    /// Denoms that can be swapped through the pool
+    pub denoms: Vec<String>,
+}
+
+#[cw_serde]
+pub struct ConfigResponse {
+    pub oracle_check: Option<OracleCheckUnchecked>,
+    pub fallback_max_hops: Option<u8>,
 }
 
 #[cw_serde]
@@ -67,11 +199,36 @@ pub struct RouteResponse<Route> {
     pub denom_in: String,
     pub denom_out: String,
     pub route: Route,
+    pub twap_window: Option<u64>,
 }
 
 pub type RoutesResponse<Route> = Vec<RouteResponse<Route>>;
 
+#[cw_serde]
+pub struct RouteValidityResponse {
+    pub denom_in: String,
+    pub denom_out: String,
+    /// Reason the route failed validation. None if the route is still valid.
+    pub error: Option<String>,
+}
+
+pub type ValidateRoutesResponse = Vec<RouteValidityResponse>;
+
+pub type CandidatePoolsResponse = Vec<CandidatePool>;
+
+#[cw_serde]
+pub struct BestRouteResponse<Route> {
+    pub route: Route,
+    /// Estimated output of swapping `coin_in` through the route
+    pub amount: Uint128,
+}
+
 #[cw_serde]
 pub struct EstimateExactInSwapResponse {
     pub amount: Uint128,
 }
+
+#[cw_serde]
+pub struct EstimateExactOutSwapResponse {
+    pub amount: Uint128,
+}
@@ -1,7 +1,12 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{to_binary, Addr, Api, Coin, CosmosMsg, Decimal, Empty, StdResult, WasmMsg};
+use cosmwasm_std::{
+    to_binary, Addr, Api, Coin, CosmosMsg, Decimal, Empty, QuerierWrapper, StdResult, Uint128,
+    WasmMsg,
+};
 
-use crate::adapters::swap::ExecuteMsg;
+use crate::adapters::swap::{
+    EstimateExactInSwapResponse, EstimateExactOutSwapResponse, ExecuteMsg, QueryMsg,
+};
 
 #[cw_serde]
 pub struct SwapperBase<T>(T);
@@ -49,4 +54,59 @@ impl Swapper {
             funds: vec![coin_in.clone()],
         }))
     }
+
+    /// Estimate the amount of denom_out received for swapping coin_in
+    pub fn estimate_exact_in_swap(
+        &self,
+        querier: &QuerierWrapper,
+        coin_in: &Coin,
+        denom_out: &str,
+    ) -> StdResult<Uint128> {
+        let res: EstimateExactInSwapResponse = querier.query_wasm_smart(
+            self.address().to_string(),
+            &QueryMsg::EstimateExactInSwap {
+                coin_in: coin_in.clone(),
+                denom_out: denom_out.to_string(),
+            },
+        )?;
+        Ok(res.amount)
+    }
+
+    /// Generate message for performing a swapper with an exact-out amount
+    pub fn swap_exact_out_msg(
+        &self,
+        coin_out: &Coin,
+        denom_in: &str,
+        max_in: Uint128,
+    ) -> StdResult<CosmosMsg> {
+        Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: self.address().to_string(),
+            msg: to_binary(&ExecuteMsg::<Empty>::SwapExactOut {
+                coin_out: coin_out.clone(),
+                denom_in: denom_in.to_string(),
+                max_in,
+            })?,
+            funds: vec![Coin {
+                denom: denom_in.to_string(),
+                amount: max_in,
+            }],
+        }))
+    }
+
+    /// Estimate the amount of denom_in needed to receive coin_out
+    pub fn estimate_exact_out_swap(
+        &self,
+        querier: &QuerierWrapper,
+        coin_out: &Coin,
+        denom_in: &str,
+    ) -> StdResult<Uint128> {
+        let res: EstimateExactOutSwapResponse = querier.query_wasm_smart(
+            self.address().to_string(),
+            &QueryMsg::EstimateExactOutSwap {
+                coin_out: coin_out.clone(),
+                denom_in: denom_in.to_string(),
+            },
+        )?;
+        Ok(res.amount)
+    }
 }
@@ -27,6 +27,21 @@ pub const VAULT_REQUEST_REPLY_ID: u64 = 10_001;
 pub type ExecuteMsg = VaultStandardExecuteMsg<ExtensionExecuteMsg>;
 pub type QueryMsg = VaultStandardQueryMsg<ExtensionQueryMsg>;
 
+/// Lockup extension messages not defined by cw-vault-standard, for vaults that can re-lock an
+/// unlocking position. Sent under the same `lockup` key as the standard lockup messages.
+#[cw_serde]
+pub enum RelockExecuteMsg {
+    /// Cancels an unlocking position, returning vault tokens for its base tokens to the owner
+    CancelUnlock {
+        lockup_id: u64,
+    },
+}
+
+#[cw_serde]
+pub enum RelockExtensionExecuteMsg {
+    Lockup(RelockExecuteMsg),
+}
+
 #[cw_serde]
 #[derive(Eq, Hash)]
 pub struct VaultBase<T> {
@@ -170,6 +185,19 @@ impl Vault {
         Ok(withdraw_msg)
     }
 
+    pub fn cancel_unlock_msg(&self, lockup_id: u64) -> StdResult<CosmosMsg> {
+        let cancel_msg = CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: self.address.to_string(),
+            funds: vec![],
+            msg: to_binary(&VaultStandardExecuteMsg::VaultExtension(
+                RelockExtensionExecuteMsg::Lockup(RelockExecuteMsg::CancelUnlock {
+                    lockup_id,
+                }),
+            ))?,
+        });
+        Ok(cancel_msg)
+    }
+
     pub fn query_info(&self, querier: &QuerierWrapper) -> StdResult<VaultInfoResponse> {
         querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
             contract_addr: self.address.to_string(),
@@ -210,6 +238,19 @@ impl Vault {
         Ok(res.amount.amount)
     }
 
+    pub fn query_preview_deposit(
+        &self,
+        querier: &QuerierWrapper,
+        amount: Uint128,
+    ) -> StdResult<Uint128> {
+        querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+            contract_addr: self.address.to_string(),
+            msg: to_binary(&QueryMsg::PreviewDeposit {
+                amount,
+            })?,
+        }))
+    }
+
     pub fn query_preview_redeem(
         &self,
         querier: &QuerierWrapper,
@@ -9,6 +9,9 @@ pub struct VaultConfig {
     pub max_ltv: Decimal,
     pub liquidation_threshold: Decimal,
     pub whitelisted: bool,
+    /// Underlying denoms of the vault's base token if it is an LP token the zapper provides
+    /// liquidity for. None for vaults with a plain base token.
+    pub lp_underlying_denoms: Option<Vec<String>>,
 }
 
 impl VaultConfig {
@@ -22,6 +25,12 @@ impl VaultConfig {
                 reason: "max ltv or liquidation threshold are invalid".to_string(),
             });
         }
+
+        if self.lp_underlying_denoms.as_ref().map_or(false, |denoms| denoms.is_empty()) {
+            return Err(InvalidConfig {
+                reason: "lp underlying denoms must not be empty".to_string(),
+            });
+        }
         Ok(())
     }
 }
@@ -4,6 +4,7 @@ use cosmwasm_std::{
     CheckedFromRatioError, CheckedMultiplyRatioError, Coin, DecimalRangeExceeded, OverflowError,
     StdError, Uint128,
 };
+use mars_health::HealthError;
 use mars_owner::OwnerError;
 use thiserror::Error;
 
@@ -43,12 +44,24 @@ pub enum ContractError {
     #[error("{0}")]
     DecimalRangeExceeded(#[from] DecimalRangeExceeded),
 
+    #[error("{0}")]
+    Health(#[from] HealthError),
+
     #[error("New unlocking positions: {new_amount:?}. Maximum: {maximum:?}.")]
     ExceedsMaxUnlockingPositions {
         new_amount: Uint128,
         maximum: Uint128,
     },
 
+    #[error("New vault positions: {new_amount:?}. Maximum: {maximum:?}.")]
+    ExceedsMaxVaultPositions {
+        new_amount: Uint128,
+        maximum: Uint128,
+    },
+
+    #[error("Account cannot be closed while it has locked or unlocking vault positions")]
+    CloseAccountVaultLocked,
+
     #[error("Callbacks cannot be invoked externally")]
     ExternalInvocation,
 
@@ -69,6 +82,13 @@ pub enum ContractError {
         new_hf: String,
     },
 
+    #[error("Not enough {denom} to repay debt. Balance: {balance:?}, debt: {debt:?}")]
+    InsufficientFundsToRepay {
+        denom: String,
+        balance: Uint128,
+        debt: Uint128,
+    },
+
     #[error("{reason:?}")]
     InvalidConfig {
         reason: String,
@@ -115,12 +135,16 @@ pub enum ContractError {
     #[error("Expected vault coins in exchange for deposit, but none were sent")]
     NoVaultCoinsReceived,
 
-    #[error("No more than one vault positions is allowed")]
-    OnlyOneVaultPositionAllowed,
-
     #[error("{0}")]
     Overflow(#[from] OverflowError),
 
+    #[error("Received {received:?} {denom:?}, which is below the minimum of {min_receive:?}")]
+    ReceivedBelowMinimum {
+        denom: String,
+        received: Uint128,
+        min_receive: Uint128,
+    },
+
     #[error("Reply id: {0} not valid")]
     ReplyIdError(u64),
 
@@ -109,21 +109,50 @@ pub enum Action {
     EnterVault {
         vault: VaultUnchecked,
         coin: ActionCoin,
+        /// Minimum amount of vault coins that must be received
+        min_receive: Option<Uint128>,
+    },
+    /// Deposit coins of any allowed denom into a vault strategy. Coins not already usable by the
+    /// vault are swapped, and liquidity is provided if the vault is configured with the underlying
+    /// denoms of an LP base token. Fails if fewer than `min_vault_tokens` vault coins are received.
+    EnterVaultWithZap {
+        vault: VaultUnchecked,
+        coins_in: Vec<ActionCoin>,
+        slippage: Decimal,
+        min_vault_tokens: Uint128,
     },
     /// Withdraw underlying coins from vault
     ExitVault {
         vault: VaultUnchecked,
         amount: Uint128,
+        /// Minimum amount of the vault's base token that must be received
+        min_receive: Option<Uint128>,
+    },
+    /// Withdraw from vault and convert everything received into `denom_out`. LP base tokens, as set
+    /// in the vault's config, are withdrawn through the zapper and every resulting coin is swapped.
+    ExitVaultInto {
+        vault: VaultUnchecked,
+        amount: Uint128,
+        denom_out: String,
+        slippage: Decimal,
     },
     /// Requests unlocking of shares for a vault with a required lock period
     RequestVaultUnlock {
         vault: VaultUnchecked,
         amount: Uint128,
     },
+    /// Cancels the unlocking position id, returning its shares to the locked amount. The vault
+    /// must support cancelling unlocks.
+    CancelVaultUnlock {
+        vault: VaultUnchecked,
+        id: u64,
+    },
     /// Withdraws the assets for unlocking position id from vault. Required time must have elapsed.
     ExitVaultUnlocked {
         id: u64,
         vault: VaultUnchecked,
+        /// Minimum amount of the vault's base token that must be received
+        min_receive: Option<Uint128>,
     },
     /// Pay back debt of a liquidatable rover account for a bonus. Requires specifying 1) the debt
     /// denom/amount of what the liquidator wants to payoff and 2) the request coin denom which the
@@ -155,10 +184,19 @@ pub enum Action {
     },
     /// Perform a swapper with an exact-in amount. Requires slippage allowance %.
     /// If `coin_in.amount: AccountBalance`, the accounts entire balance of `coin_in.denom` will be used.
+    /// If `min_receive` is set, the swap fails if less than that amount of `denom_out` is credited.
     SwapExactIn {
         coin_in: ActionCoin,
         denom_out: String,
         slippage: Decimal,
+        min_receive: Option<Uint128>,
+    },
+    /// Perform a swapper with an exact-out amount. At most `max_in` of `denom_in` will be spent,
+    /// with the unspent remainder returned to the account.
+    SwapExactOut {
+        coin_out: Coin,
+        denom_in: String,
+        max_in: Uint128,
     },
     /// Add Vec<Coin> to liquidity pool in exchange for LP tokens
     ProvideLiquidity {
@@ -170,9 +208,37 @@ pub enum Action {
     /// If `lp_token.amount: AccountBalance`, the account balance of `lp_token.denom` will be used.
     WithdrawLiquidity {
         lp_token: ActionCoin,
+        /// Minimum amount of each listed coin that must be received
+        min_receive: Option<Vec<Coin>>,
     },
     /// Refunds all coin balances back to user wallet
     RefundAllCoinBalances {},
+    /// Unwinds the entire account: exits unlocked vault positions, withdraws LP tokens, swaps
+    /// balances into the denoms needed to repay every debt, repays all debt and refunds what
+    /// remains to the NFT owner. Balances left after covering debts are swapped into `target_denom`.
+    /// Fails if the account has locked or unlocking vault positions.
+    CloseAccount {
+        target_denom: String,
+        slippage: Decimal,
+    },
+    /// Swaps collateral into the debt denom and repays it, sized so that the account ends at the
+    /// target max LTV health factor. Priced using the same oracle prices as health computation,
+    /// assuming the swap fills at `slippage` below the oracle price.
+    Deleverage {
+        target_max_ltv_health_factor: Decimal,
+        collateral_denom: String,
+        debt_denom: String,
+        slippage: Decimal,
+    },
+    /// Borrows `borrow_denom` and swaps it into `collateral_denom`, kept as a deposit, until the
+    /// account's collateral value over its equity reaches `target_leverage`. The borrowed amount
+    /// is capped so the account stays at or below max LTV.
+    Leverage {
+        collateral_denom: String,
+        borrow_denom: String,
+        target_leverage: Decimal,
+        slippage: Decimal,
+    },
 }
 
 /// Internal actions made by the contract with pre-validated inputs
@@ -213,12 +279,51 @@ pub enum CallbackMsg {
         account_id: String,
         vault: Vault,
         coin: ActionCoin,
+        min_receive: Option<Uint128>,
+    },
+    /// Swaps coins into the vault's base token, or the underlying of an LP base token, and
+    /// continues with `ZapIntoVault`
+    EnterVaultWithZap {
+        account_id: String,
+        vault: Vault,
+        coins_in: Vec<ActionCoin>,
+        slippage: Decimal,
+        min_vault_tokens: Uint128,
+    },
+    /// Deposits the account's balances above `retained` into the vault. If those are not yet in
+    /// the vault's base token, liquidity is provided first and this callback is repeated.
+    ZapIntoVault {
+        account_id: String,
+        vault: Vault,
+        /// Account balances held before the zap, which are not deposited
+        retained: Vec<Coin>,
+        min_vault_tokens: Uint128,
     },
     /// Exchanges vault LP shares for assets
     ExitVault {
         account_id: String,
         vault: Vault,
         amount: Uint128,
+        min_receive: Option<Uint128>,
+    },
+    /// Exits the vault, then continues with `SwapWithdrawnInto` for the base token received
+    ExitVaultInto {
+        account_id: String,
+        vault: Vault,
+        amount: Uint128,
+        denom_out: String,
+        slippage: Decimal,
+    },
+    /// Converts the account's balances above `retained` into `denom_out`. LP tokens are withdrawn
+    /// first and this callback is repeated for their underlying coins.
+    SwapWithdrawnInto {
+        account_id: String,
+        /// Account balances held before the withdrawal, which are not converted
+        retained: Vec<Coin>,
+        /// Underlying denoms of the coins in `retained` if they are LP tokens, else empty
+        lp_underlying: Vec<String>,
+        denom_out: String,
+        slippage: Decimal,
     },
     /// Used to update the account balance of vault coins after a vault action has taken place
     UpdateVaultCoinBalance {
@@ -227,6 +332,8 @@ pub enum CallbackMsg {
         account_id: String,
         /// Total vault coin balance in Rover
         previous_total_balance: Uint128,
+        /// Minimum amount of vault coins that must be credited to the account, if any
+        min_receive: Option<Uint128>,
     },
     /// Requests unlocking of shares for a vault with a lock period
     RequestVaultUnlock {
@@ -234,11 +341,18 @@ pub enum CallbackMsg {
         vault: Vault,
         amount: Uint128,
     },
+    /// Cancels an unlocking position, re-locking its shares in the vault
+    CancelVaultUnlock {
+        account_id: String,
+        vault: Vault,
+        position_id: u64,
+    },
     /// Withdraws assets from vault for a locked position having a lockup period that has been fulfilled
     ExitVaultUnlocked {
         account_id: String,
         vault: Vault,
         position_id: u64,
+        min_receive: Option<Uint128>,
     },
     /// Pay back debts of a liquidatable rover account for a bonus
     LiquidateCoin {
@@ -261,6 +375,14 @@ pub enum CallbackMsg {
         coin_in: ActionCoin,
         denom_out: String,
         slippage: Decimal,
+        min_receive: Option<Uint128>,
+    },
+    /// Perform a swapper with an exact-out amount, spending at most `max_in` of `denom_in`
+    SwapExactOut {
+        account_id: String,
+        coin_out: Coin,
+        denom_in: String,
+        max_in: Uint128,
     },
     /// Used to update the coin balance of account after an async action
     UpdateCoinBalance {
@@ -268,6 +390,8 @@ pub enum CallbackMsg {
         account_id: String,
         /// Total balance for coin in Rover prior to withdraw
         previous_balance: Coin,
+        /// Minimum amount that must be credited to the account, if any
+        min_receive: Option<Uint128>,
     },
     /// Add Vec<Coin> to liquidity pool in exchange for LP tokens
     ProvideLiquidity {
@@ -281,15 +405,53 @@ pub enum CallbackMsg {
     WithdrawLiquidity {
         account_id: String,
         lp_token: ActionCoin,
+        min_receive: Option<Vec<Coin>>,
     },
-    /// Checks to ensure only one vault position is taken per credit account
-    AssertOneVaultPositionOnly {
+    /// Checks the account holds no more vault positions than the configured maximum.
+    /// An account already above a lowered maximum may keep its positions, but not add more.
+    AssertMaxVaultPositions {
         account_id: String,
+        prev_vault_positions: Uint128,
     },
     /// Refunds all coin balances back to user wallet
     RefundAllCoinBalances {
         account_id: String,
     },
+    /// Exits every unlocked vault position. Errors if any locked or unlocking positions remain.
+    ExitUnlockedVaults {
+        account_id: String,
+    },
+    /// Withdraws liquidity for every coin balance the zapper recognizes as an LP token
+    WithdrawAllLiquidity {
+        account_id: String,
+    },
+    /// Swaps balances into the denoms needed to cover the account's debts. Whatever is not
+    /// needed for repayment is swapped into `target_denom`.
+    SwapForDebts {
+        account_id: String,
+        target_denom: String,
+        slippage: Decimal,
+    },
+    /// Repays all debt of the account. Errors if any coin balance does not cover its debt.
+    RepayAllDebts {
+        account_id: String,
+    },
+    /// Swaps collateral into the debt denom and repays it to reach the target max LTV health factor
+    Deleverage {
+        account_id: String,
+        target_max_ltv_health_factor: Decimal,
+        collateral_denom: String,
+        debt_denom: String,
+        slippage: Decimal,
+    },
+    /// Borrows and swaps into collateral to reach the target leverage
+    Leverage {
+        account_id: String,
+        collateral_denom: String,
+        borrow_denom: String,
+        target_leverage: Decimal,
+        slippage: Decimal,
+    },
 }
 
 impl CallbackMsg {
@@ -31,6 +31,10 @@ pub struct InstantiateMsg {
     /// Note: As health checking requires looping through each, this number must not be too large.
     ///       If so, having too many could prevent the account from being liquidated due to gas constraints.
     pub max_unlocking_positions: Uint128,
+    /// The maximum number of vault positions an account can have simultaneously
+    /// Note: Health checks query every vault an account holds, so this number must not be too large.
+    ///       If so, having too many could prevent the account from being liquidated due to gas constraints.
+    pub max_vault_positions: Uint128,
     /// Helper contract for making swaps
     pub swapper: SwapperUnchecked,
     /// Helper contract for adding/removing liquidity
@@ -72,6 +76,7 @@ pub struct ConfigUpdates {
     pub red_bank: Option<RedBankUnchecked>,
     pub max_close_factor: Option<Decimal>,
     pub max_unlocking_positions: Option<Uint128>,
+    pub max_vault_positions: Option<Uint128>,
     pub swapper: Option<SwapperUnchecked>,
     pub zapper: Option<ZapperUnchecked>,
 }
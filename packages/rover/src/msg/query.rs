@@ -1,9 +1,12 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{Coin, Decimal, Uint128};
+use mars_health::HealthResponse;
+pub use mars_health::{CoinValue, CollateralValue};
 use mars_owner::OwnerResponse;
 
 use crate::{
-    adapters::vault::{Vault, VaultConfig, VaultPosition, VaultUnchecked},
+    adapters::vault::{Vault, VaultConfig, VaultPosition, VaultPositionType, VaultUnchecked},
+    msg::execute::Action,
     traits::Coins,
 };
 
@@ -30,11 +33,66 @@ pub enum QueryMsg {
     Positions {
         account_id: String,
     },
+    /// All positions of the account with their price, value & contribution to health
+    #[returns(PositionsWithValue)]
+    PositionsWithValue {
+        account_id: String,
+    },
     /// The health of the account represented by token
     #[returns(mars_health::HealthResponse)]
     Health {
         account_id: String,
     },
+    /// The health of the account if the oracle reported the given prices.
+    /// Denoms without an override are priced by the oracle.
+    #[returns(mars_health::HealthResponse)]
+    HealthUnderScenario {
+        account_id: String,
+        price_overrides: Vec<CoinPrice>,
+    },
+    /// The price of a denom at which the account becomes liquidatable, holding all other prices fixed.
+    /// None if the account has no debt or the price of the denom cannot trigger liquidation.
+    #[returns(Option<Decimal>)]
+    LiquidationPrice {
+        account_id: String,
+        denom: String,
+    },
+    /// Largest amount of a coin that can be borrowed while staying at or below max LTV
+    #[returns(Uint128)]
+    MaxBorrowAmount {
+        account_id: String,
+        denom: String,
+    },
+    /// Largest amount of a coin that can be withdrawn while staying at or below max LTV
+    #[returns(Uint128)]
+    MaxWithdrawAmount {
+        account_id: String,
+        denom: String,
+    },
+    /// Enumerate accounts with debt that are currently liquidatable.
+    /// `limit` bounds the accounts with debt scanned, not the accounts returned, so a page may hold
+    /// fewer (or no) accounts. Continue from `next_start_after` until it is None.
+    #[returns(LiquidatableAccountsResponse)]
+    LiquidatableAccounts {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Preview the debt repaid and collateral received for a liquidation given current state
+    #[returns(LiquidationPreview)]
+    PreviewLiquidation {
+        liquidatee_account_id: String,
+        debt_coin: Coin,
+        request: LiquidationRequest,
+    },
+    /// Preview the positions and health of an account after applying a batch of actions.
+    /// Nothing is committed to state. Swaps, zaps and vault deposits assume they execute at the
+    /// estimates returned by the swapper, zapper and vault. Liquidations are previewed against the
+    /// liquidatee's positions before the batch.
+    #[returns(SimulateActionsResponse)]
+    SimulateActions {
+        account_id: String,
+        actions: Vec<Action>,
+    },
     /// Enumerate coin balances for all token positions; start_after accepts (account_id, denom)
     #[returns(Vec<CoinBalanceResponseItem>)]
     AllCoinBalances {
@@ -136,11 +194,17 @@ impl Coins for Vec<DebtAmount> {
 }
 
 #[cw_serde]
-pub struct CoinValue {
+pub struct CoinPrice {
     pub denom: String,
-    pub amount: Uint128,
     pub price: Decimal,
-    pub value: Decimal,
+}
+
+#[cw_serde]
+pub struct PositionsWithValue {
+    pub account_id: String,
+    pub deposits: Vec<CollateralValue>,
+    pub debts: Vec<CoinValue>,
+    pub vaults: Vec<VaultPositionValue>,
 }
 
 #[cw_serde]
@@ -151,6 +215,65 @@ pub struct Positions {
     pub vaults: Vec<VaultPosition>,
 }
 
+#[cw_serde]
+pub struct SimulateActionsResponse {
+    /// Positions of the account after all actions have been applied
+    pub positions: Positions,
+    /// Health of the account after all actions have been applied
+    pub health: HealthResponse,
+    /// Whether the max LTV assertion at the end of the batch would pass
+    pub passes_max_ltv_check: bool,
+}
+
+#[cw_serde]
+pub struct LiquidatableAccount {
+    pub account_id: String,
+    pub health: HealthResponse,
+}
+
+#[cw_serde]
+pub struct LiquidatableAccountsResponse {
+    pub accounts: Vec<LiquidatableAccount>,
+    /// Last account scanned, if there may be more accounts with debt to scan
+    pub next_start_after: Option<String>,
+}
+
+/// The collateral a liquidator requests in exchange for repaying debt
+#[cw_serde]
+pub enum LiquidationRequest {
+    /// Denom of a coin balance
+    Coin(String),
+    /// A bucket of a vault position
+    Vault {
+        request_vault: VaultUnchecked,
+        position_type: VaultPositionType,
+    },
+}
+
+/// The constraint that determined the amount of debt repaid in a liquidation
+#[cw_serde]
+pub enum LiquidationLimit {
+    /// The debt amount the liquidator requested to repay
+    RequestedAmount,
+    /// The liquidatee's total debt for the denom
+    TotalDebt,
+    /// The max close factor applied to the liquidatee's total debt value
+    CloseFactor,
+    /// The liquidatee's balance of the requested collateral, net of the liquidation bonus
+    CollateralBalance,
+}
+
+#[cw_serde]
+pub struct LiquidationPreview {
+    /// Debt the liquidator would repay
+    pub debt: Coin,
+    /// Collateral the liquidator would receive. Denominated in vault coins for locked and
+    /// unlocked vault positions, and in the underlying base token for unlocking positions.
+    pub request: Coin,
+    /// The constraint that capped the debt amount
+    pub limit: LiquidationLimit,
+}
+
 #[cw_serde]
 pub struct VaultPositionResponseItem {
     pub account_id: String,
@@ -166,7 +289,10 @@ pub struct VaultWithBalance {
 #[cw_serde]
 pub struct VaultPositionValue {
     pub position: VaultPosition,
-    pub value: Decimal,
+    /// Locked & unlocked vault coins, denominated in the underlying base token
+    pub vault_coins: CollateralValue,
+    /// Unlocking positions, denominated in the underlying base token
+    pub unlocking: Vec<CollateralValue>,
 }
 
 #[cw_serde]
@@ -177,6 +303,7 @@ pub struct ConfigResponse {
     pub oracle: String,
     pub max_close_factor: Decimal,
     pub max_unlocking_positions: Uint128,
+    pub max_vault_positions: Uint128,
     pub swapper: String,
     pub zapper: String,
 }
@@ -154,6 +154,7 @@ export class Deployer {
   async instantiateCreditManager() {
     const msg: RoverInstantiateMsg = {
       max_unlocking_positions: this.config.maxUnlockingPositions,
+      max_vault_positions: this.config.maxVaultPositions,
       allowed_coins: this.config.allowedCoins,
       vault_configs: this.config.vaults.map((v) => ({ config: v.config, vault: v.vault })),
       oracle: this.config.oracle.addr,
@@ -20,6 +20,7 @@ export const osmosisMainnetConfig: DeploymentConfig = {
   deployerMnemonic: 'TO BE INSERTED AT TIME OF DEPLOYMENT',
   maxCloseFactor: '0.5',
   maxUnlockingPositions: '1',
+  maxVaultPositions: '1',
   maxValueForBurn: '10000',
   // oracle and redbank contract addresses can be found:  https://github.com/mars-protocol/red-bank/blob/master/README.md#osmosis-1
   oracle: { addr: 'osmo1mhznfr60vjdp2gejhyv2gax9nvyyzhd3z0qcwseyetkfustjauzqycsy2g' },
@@ -15,6 +15,7 @@ const osmoUsdcConfig = {
     max_ltv: '0.63',
     liquidation_threshold: '0.65',
     whitelisted: true,
+    lp_underlying_denoms: [uosmo, axlUSDC],
   },
 }
 
@@ -44,6 +45,7 @@ export const osmosisTestnetConfig: DeploymentConfig = {
     'rely wonder join knock during sudden slow plate segment state agree also arrest mandate grief ordinary lonely lawsuit hurt super banana rule velvet cart',
   maxCloseFactor: '0.6',
   maxUnlockingPositions: '10',
+  maxVaultPositions: '5',
   maxValueForBurn: '1000000',
   // Latest from: https://github.com/mars-protocol/outposts/blob/master/scripts/deploy/addresses/osmo-test-5.json
   oracle: { addr: 'osmo1khe29uw3t85nmmp3mtr8dls7v2qwsfk3tndu5h4w5g2r5tzlz5qqarq2e2' },
@@ -98,6 +100,7 @@ export const osmosisTestnetConfig: DeploymentConfig = {
           liquidation_threshold: '0.585',
           max_ltv: '0.569',
           whitelisted: true,
+          lp_underlying_denoms: [uosmo, axlUSDC],
         },
         vaultTokenDenom: axlUSDC,
         type: VaultType.LOCKED,
@@ -31,6 +31,7 @@ export interface DeploymentConfig {
   maxCloseFactor: string
   maxValueForBurn: string
   maxUnlockingPositions: string
+  maxVaultPositions: string
   swapRoutes: SwapRoute[]
   testActions?: TestActions
   swapperContractName: string
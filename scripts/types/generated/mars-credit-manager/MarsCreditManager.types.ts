@@ -15,6 +15,7 @@ export interface InstantiateMsg {
   allowed_coins: string[]
   max_close_factor: Decimal
   max_unlocking_positions: Uint128
+  max_vault_positions: Uint128
   oracle: OracleBaseForString
   owner: string
   red_bank: RedBankBaseForString
@@ -29,6 +30,7 @@ export interface VaultInstantiateConfig {
 export interface VaultConfig {
   deposit_cap: Coin
   liquidation_threshold: Decimal
+  lp_underlying_denoms?: string[] | null
   max_ltv: Decimal
   whitelisted: boolean
 }
@@ -281,8 +283,9 @@ export type CallbackMsg =
       }
     }
   | {
-      assert_one_vault_position_only: {
+      assert_max_vault_positions: {
         account_id: string
+        prev_vault_positions: Uint128
       }
     }
   | {
@@ -300,6 +303,7 @@ export interface ConfigUpdates {
   allowed_coins?: string[] | null
   max_close_factor?: Decimal | null
   max_unlocking_positions?: Uint128 | null
+  max_vault_positions?: Uint128 | null
   oracle?: OracleBaseForString | null
   red_bank?: RedBankBaseForString | null
   swapper?: SwapperBaseForString | null
@@ -450,6 +454,7 @@ export interface ConfigResponse {
   account_nft?: string | null
   max_close_factor: Decimal
   max_unlocking_positions: Uint128
+  max_vault_positions: Uint128
   oracle: string
   ownership: OwnerResponse
   red_bank: string
@@ -201,6 +201,7 @@ export interface VaultInfoResponse {
 export interface VaultConfig {
   deposit_cap: Coin
   liquidation_threshold: Decimal
+  lp_underlying_denoms?: string[] | null
   max_ltv: Decimal
   whitelisted: boolean
 }